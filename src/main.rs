@@ -10,6 +10,7 @@ use std::path::Path;
 
 use vaaktra_lexer::VaaktraLexer;
 use vaaktra_parser::VaaktraParser;
+use vaaktra_parser::{ast, loader::ModuleLoader, SourceFile};
 use vaaktra_codegen::simple::SimpleVaaktraCodegen;
 use vaaktra_vm::VaaktraVm;
 
@@ -119,12 +120,16 @@ fn run_demo() {
 fn compile_and_run(filename: &str) -> Result<String, Box<dyn std::error::Error>> {
     info!("Compiling Vāktra file: {}", filename);
     
-    // Read source code
-    let source_code = fs::read_to_string(filename)
-        .map_err(|e| format!("Failed to read file {}: {}", filename, e))?;
+    // Load the entry file together with every `यन्त्र नाम;` module it declares
+    println!("1️⃣  Loading modules and parsing...");
+    let mut loader = ModuleLoader::new();
+    let mut program = loader.load_program(filename).map_err(|errors| {
+        errors.iter().map(|e| format!("Load error: {}", e)).collect::<Vec<_>>().join("\n")
+    })?;
+    println!("   ✓ Parsed {} source files into {} items", loader.files().len(), program.items.len());
     
     // Process the code
-    let result = process_code(&source_code)?;
+    let result = run_program(&mut program)?;
     
     println!("🎯 Execution result: {}", result);
     Ok(result)
@@ -141,15 +146,25 @@ fn process_code(source_code: &str) -> Result<String, Box<dyn std::error::Error>>
     // Step 2: Parsing
     println!("2️⃣  Parsing (AST generation with Vedic concepts)...");
     let mut parser = Parser::new(tokens.into_iter());
-    let program = parser.parse_program()
-        .map_err(|e| format!("Parse error: {}", e))?;
+    let mut program = parser.parse_program().map_err(|e| {
+        let offset = e.span().map_or(0, |span| span.start);
+        format!("Parse error at {}: {}", SourceFile::new("<input>", source_code).location(offset), e)
+    })?;
     println!("   ✓ Generated AST with {} items", program.items.len());
     
+    run_program(&mut program)
+}
+
+/// Run a parsed program through semantic analysis, compilation and the VM
+fn run_program(program: &mut ast::Program) -> Result<String, Box<dyn std::error::Error>> {
     // Step 3: Semantic Analysis
     println!("3️⃣  Semantic Analysis (type checking and validation)...");
     let mut analyzer = VaaktraSemanticAnalyzer::new();
-    analyzer.analyze_program(&program)
-        .map_err(|e| format!("Semantic error: {}", e))?;
+    let analyzed = analyzer.analyze_program(program);
+    for warning in analyzer.errors().warnings() {
+        eprintln!("चेतावनी (Warning): {}", warning);
+    }
+    analyzed.map_err(|e| format!("Semantic error: {}", e))?;
     println!("   ✓ Semantic analysis completed successfully");
     
     // Step 4: JIT Compilation Setup
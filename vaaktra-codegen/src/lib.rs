@@ -91,6 +91,11 @@ impl VaaktraCodegen {
     /// Initialize type mappings from Vāktra to Cranelift types
     fn initialize_type_mappings(&mut self) {
         self.type_map.insert("सङ्ख्या".to_string(), types::I64);
+        self.type_map.insert("दशांश".to_string(), types::F64);
+        for (name, ty) in [("i8", types::I8), ("i16", types::I16), ("i32", types::I32), ("i64", types::I64), ("i128", types::I128)] {
+            self.type_map.insert(name.to_string(), ty);
+            self.type_map.insert(name.replacen('i', "u", 1), ty);
+        }
         self.type_map.insert("सत्यासत्य".to_string(), types::I8);
         self.type_map.insert("शब्द".to_string(), types::I64); // Pointer to string
         self.type_map.insert("शून्य".to_string(), types::I32); // Void represented as i32
@@ -250,6 +255,8 @@ impl VaaktraCodegen {
     fn generate_literal(&self, builder: &mut FunctionBuilder, literal: &str) -> CodegenResult<Value> {
         if let Ok(int_val) = literal.parse::<i64>() {
             Ok(builder.ins().iconst(types::I64, int_val))
+        } else if let Ok(float_val) = literal.parse::<f64>() {
+            Ok(builder.ins().f64const(float_val))
         } else if literal == "सत्य" {
             Ok(builder.ins().iconst(types::I8, 1))
         } else if literal == "असत्य" {
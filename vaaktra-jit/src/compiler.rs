@@ -13,7 +13,7 @@ use inkwell::{
     module::Module,
     builder::Builder,
     execution_engine::{ExecutionEngine, JitFunction},
-    values::{BasicValueEnum, BasicValue, FunctionValue, PointerValue},
+    values::{BasicValueEnum, BasicValue, FloatValue, FunctionValue, PointerValue},
     types::{BasicTypeEnum, BasicType, FunctionType},
     OptimizationLevel,
 };
@@ -28,7 +28,7 @@ use std::collections::HashMap;
 use thiserror::Error;
 
 use vaaktra_parser::ast::{Program, Item, MantraDef, DharmaDef, Expr, Statement, Type};
-use crate::{OptimizationLevel, VaaktraType, JitResult, JitError};
+use crate::{IntegerWidth, OptimizationLevel, VaaktraType, JitResult, JitError};
 
 /// Compilation errors
 #[derive(Debug, Error)]
@@ -243,6 +243,15 @@ impl<'ctx> VaaktraCompiler<'ctx> {
             Expr::Literal(literal, _span) => {
                 match literal {
                     vaaktra_parser::ast::Literal::Int(n) => Ok(self.context.i64_type().const_int(*n as u64, false).into()),
+                    vaaktra_parser::ast::Literal::TypedInt(value, width) => {
+                        let words = [*value as u64, (*value >> 64) as u64];
+                        Ok(self.int_type(*width).const_int_arbitrary_precision(&words).into())
+                    }
+                    vaaktra_parser::ast::Literal::NegativeTypedInt(value, width) => {
+                        let words = [*value as u64, (*value >> 64) as u64];
+                        Ok(self.int_type(*width).const_int_arbitrary_precision(&words).const_neg().into())
+                    }
+                    vaaktra_parser::ast::Literal::Float(x) => Ok(self.context.f64_type().const_float(*x).into()),
                     vaaktra_parser::ast::Literal::Bool(b) => Ok(self.context.bool_type().const_int(*b as u64, false).into()),
                     vaaktra_parser::ast::Literal::String(s) => {
                         let string_val = self.context.const_string(s.as_bytes(), false);
@@ -260,7 +269,7 @@ impl<'ctx> VaaktraCompiler<'ctx> {
             Expr::Binary(left, op, right, _span) => {
                 let left_val = self.compile_expression(left, local_symbols)?;
                 let right_val = self.compile_expression(right, local_symbols)?;
-                self.compile_binary_op(left_val, &format!("{:?}", op), right_val)
+                self.compile_binary_op(left_val, operator_symbol(*op), right_val)
             }
             Expr::Call(function, args, _span) => {
                 // For now, assume function is a variable reference
@@ -298,6 +307,13 @@ impl<'ctx> VaaktraCompiler<'ctx> {
     
     /// Compile a binary operation
     fn compile_binary_op(&mut self, left: BasicValueEnum<'ctx>, op: &str, right: BasicValueEnum<'ctx>) -> JitResult<BasicValueEnum<'ctx>> {
+        // Any दशांश operand makes the whole operation floating point
+        if left.is_float_value() || right.is_float_value() {
+            let l = self.promote_to_float(left)?;
+            let r = self.promote_to_float(right)?;
+            return self.compile_float_binary_op(l, op, r);
+        }
+        
         match op {
             "धन" | "+" => {
                 if let (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) = (left, right) {
@@ -345,6 +361,36 @@ impl<'ctx> VaaktraCompiler<'ctx> {
         }
     }
     
+    /// Convert an integer operand to दशांश for a mixed operation
+    fn promote_to_float(&self, value: BasicValueEnum<'ctx>) -> JitResult<FloatValue<'ctx>> {
+        match value {
+            BasicValueEnum::FloatValue(f) => Ok(f),
+            BasicValueEnum::IntValue(i) => {
+                Ok(self.builder.build_signed_int_to_float(i, self.context.f64_type(), "promote"))
+            }
+            _ => Err(JitError::CompilationFailed("Expected a numeric operand".to_string())),
+        }
+    }
+    
+    /// Compile a binary operation on two दशांश values
+    fn compile_float_binary_op(&mut self, l: FloatValue<'ctx>, op: &str, r: FloatValue<'ctx>) -> JitResult<BasicValueEnum<'ctx>> {
+        match op {
+            "धन" | "+" => Ok(self.builder.build_float_add(l, r, "fadd").into()),
+            "ऋण" | "-" => Ok(self.builder.build_float_sub(l, r, "fsub").into()),
+            "गुण" | "*" => Ok(self.builder.build_float_mul(l, r, "fmul").into()),
+            "भाग" | "/" => Ok(self.builder.build_float_div(l, r, "fdiv").into()),
+            "शेष" | "%" => Ok(self.builder.build_float_rem(l, r, "frem").into()),
+            // Ordered comparisons: any comparison with NaN is असत्य
+            "समान" | "==" => Ok(self.builder.build_float_compare(inkwell::FloatPredicate::OEQ, l, r, "feq").into()),
+            "असमान" | "!=" => Ok(self.builder.build_float_compare(inkwell::FloatPredicate::ONE, l, r, "fne").into()),
+            "लघुत्तर" | "<" => Ok(self.builder.build_float_compare(inkwell::FloatPredicate::OLT, l, r, "flt").into()),
+            "समानता" | "<=" => Ok(self.builder.build_float_compare(inkwell::FloatPredicate::OLE, l, r, "fle").into()),
+            "महत्तर" | ">" => Ok(self.builder.build_float_compare(inkwell::FloatPredicate::OGT, l, r, "fgt").into()),
+            ">=" => Ok(self.builder.build_float_compare(inkwell::FloatPredicate::OGE, l, r, "fge").into()),
+            _ => Err(JitError::CompilationFailed(format!("Unsupported binary operator: {}", op)))
+        }
+    }
+    
     /// Compile a function call
     fn compile_function_call(&mut self, function_name: &str, args: &[Expr], local_symbols: &HashMap<String, BasicValueEnum<'ctx>>) -> JitResult<BasicValueEnum<'ctx>> {
         let function = self.functions.get(function_name)
@@ -380,6 +426,10 @@ impl<'ctx> VaaktraCompiler<'ctx> {
                 let name = path.segments.last().unwrap().as_str();
                 match name {
                     "सङ्ख्या" => Ok(self.context.i64_type().into()),
+                    "दशांश" => Ok(self.context.f64_type().into()),
+                    _ if IntegerWidth::from_suffix(name).is_some() => {
+                        Ok(self.int_type(IntegerWidth::from_suffix(name).unwrap()).into())
+                    }
                     "सत्यासत्य" => Ok(self.context.bool_type().into()),
                     "शब्द" => Ok(self.context.i8_type().ptr_type(AddressSpace::Generic).into()),
                     "शून्य" => Ok(self.context.i32_type().into()), // Use i32 for void
@@ -390,6 +440,11 @@ impl<'ctx> VaaktraCompiler<'ctx> {
         }
     }
     
+    /// LLVM integer type of a width; signedness lives in the operations
+    fn int_type(&self, width: IntegerWidth) -> inkwell::types::IntType<'ctx> {
+        self.context.custom_width_int_type(width.bits())
+    }
+    
     /// Get default value for a type
     fn get_default_value(&self, llvm_type: BasicTypeEnum<'ctx>) -> BasicValueEnum<'ctx> {
         match llvm_type {
@@ -425,9 +480,97 @@ impl<'ctx> VaaktraCompiler<'ctx> {
     }
 }
 
+/// The symbol of a binary operator, as [`VaaktraCompiler::compile_binary_op`]
+/// expects it
+fn operator_symbol(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Subtract => "-",
+        BinaryOp::Multiply => "*",
+        BinaryOp::Divide => "/",
+        BinaryOp::Modulo => "%",
+        BinaryOp::Equal => "==",
+        BinaryOp::NotEqual => "!=",
+        BinaryOp::Less => "<",
+        BinaryOp::LessEqual => "<=",
+        BinaryOp::Greater => ">",
+        BinaryOp::GreaterEqual => ">=",
+        BinaryOp::And => "&&",
+        BinaryOp::Or => "||",
+    }
+}
+
 /// Trait for unsafe function pointers (required by inkwell)
 pub unsafe trait UnsafeFunctionPointer {}
 
 unsafe impl UnsafeFunctionPointer for unsafe extern "C" fn() -> i64 {}
 unsafe impl UnsafeFunctionPointer for unsafe extern "C" fn(i64) -> i64 {}
 unsafe impl UnsafeFunctionPointer for unsafe extern "C" fn(i64, i64) -> i64 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply `op` to two दशांश constants, which the builder folds, and
+    /// inspect the result
+    fn float_op<T>(l: f64, op: &str, r: f64, inspect: impl FnOnce(BasicValueEnum<'_>) -> T) -> T {
+        let context = Context::create();
+        let mut compiler = VaaktraCompiler::new(&context, "test").unwrap();
+        // The builder needs a block to insert into
+        let function = compiler.module.add_function("f", context.void_type().fn_type(&[], false), None);
+        compiler.builder.position_at_end(context.append_basic_block(function, "entry"));
+        let f64_type = context.f64_type();
+        let value = compiler.compile_float_binary_op(f64_type.const_float(l), op, f64_type.const_float(r)).unwrap();
+        inspect(value)
+    }
+
+    fn arithmetic(l: f64, op: &str, r: f64) -> f64 {
+        float_op(l, op, r, |value| value.into_float_value().get_constant().unwrap().0)
+    }
+
+    fn compare(l: f64, op: &str, r: f64) -> bool {
+        float_op(l, op, r, |value| value.into_int_value().get_zero_extended_constant() == Some(1))
+    }
+
+    #[test]
+    fn test_float_remainder() {
+        assert_eq!(arithmetic(7.5, "शेष", 2.0), 1.5);
+        assert_eq!(arithmetic(-7.5, "%", 2.0), -1.5);
+    }
+
+    #[test]
+    fn test_float_not_equal() {
+        assert!(compare(1.0, "असमान", 2.0));
+        assert!(!compare(1.5, "!=", 1.5));
+        assert!(!compare(f64::NAN, "!=", 1.0));
+    }
+
+    #[test]
+    fn test_float_less_equal() {
+        assert!(compare(1.5, "समानता", 1.5));
+        assert!(compare(1.0, "<=", 1.5));
+        assert!(!compare(2.0, "<=", 1.5));
+    }
+
+    #[test]
+    fn test_float_greater() {
+        assert!(compare(2.0, "महत्तर", 1.5));
+        assert!(!compare(1.5, ">", 1.5));
+    }
+
+    #[test]
+    fn test_float_greater_equal() {
+        assert!(compare(1.5, ">=", 1.5));
+        assert!(compare(2.0, ">=", 1.5));
+        assert!(!compare(1.0, ">=", 1.5));
+        assert!(!compare(f64::NAN, ">=", 1.0));
+    }
+
+    #[test]
+    fn test_binary_operators_reach_the_float_lowering() {
+        let ops = [BinaryOp::Modulo, BinaryOp::NotEqual, BinaryOp::LessEqual, BinaryOp::Greater, BinaryOp::GreaterEqual];
+        for op in ops {
+            float_op(3.0, operator_symbol(op), 2.0, |_| ());
+        }
+    }
+}
@@ -104,6 +104,9 @@ pub enum VaaktraType {
     /// सङ्ख्या (Number) - optimized integer types
     Sankhya(IntegerWidth),
     
+    /// दशांश (Fractional number) - IEEE 754 double precision
+    Dashamsha,
+    
     /// सत्यासत्य (Boolean) - single bit optimization
     Satyasatya,
     
@@ -123,12 +126,9 @@ pub enum VaaktraType {
     Dharma(String),
 }
 
-/// Integer width for optimal performance
-#[derive(Debug, Clone, PartialEq)]
-pub enum IntegerWidth {
-    I8, I16, I32, I64, I128,
-    U8, U16, U32, U64, U128,
-}
+/// Integer width for optimal performance; also the width of suffixed
+/// integer literals such as `२५५u8`
+pub use vaaktra_lexer::IntegerWidth;
 
 /// Optimization levels inspired by Vedic concepts
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -57,6 +57,9 @@ pub enum RuntimeValue {
     /// सङ्ख्या (Number)
     Sankhya(i64),
     
+    /// दशांश (Fractional number)
+    Dashamsha(f64),
+    
     /// सत्यासत्य (Boolean)
     Satyasatya(bool),
     
@@ -75,8 +78,48 @@ pub enum RuntimeValue {
     /// Function pointer
     Mantra(fn(&[RuntimeValue]) -> RuntimeResult<RuntimeValue>),
     
-    /// Object instance
-    Dharma(HashMap<String, RuntimeValue>),
+    /// Lambda together with the values it captured
+    Closure(Arc<Closure>),
+    
+    /// Object instance: the name of its dharma, which selects the vtable
+    /// its गुणधर्म methods are called through, and its fields
+    Dharma(String, HashMap<String, RuntimeValue>),
+}
+
+/// Two numeric operands after promotion. Integers stay integers; if either
+/// operand is a दशांश, both become दशांश.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericPair {
+    Sankhya(i64, i64),
+    Dashamsha(f64, f64),
+}
+
+/// A closure: compiled function plus its captured environment
+#[derive(Debug, Clone)]
+pub struct Closure {
+    /// Name of the compiled function in the program's function table
+    pub function: String,
+    
+    /// Captured values, passed ahead of the call's own arguments
+    pub captures: Vec<RuntimeValue>,
+}
+
+impl Closure {
+    /// Create a closure over `captures`
+    pub fn new(function: impl Into<String>, captures: Vec<RuntimeValue>) -> Self {
+        Closure {
+            function: function.into(),
+            captures,
+        }
+    }
+    
+    /// Arguments for calling the compiled function: captures, then `args`
+    pub fn call_args(&self, args: &[RuntimeValue]) -> Vec<RuntimeValue> {
+        let mut full = Vec::with_capacity(self.captures.len() + args.len());
+        full.extend(self.captures.iter().cloned());
+        full.extend(args.iter().cloned());
+        full
+    }
 }
 
 /// Execution context for a thread
@@ -381,6 +424,7 @@ impl RuntimeValue {
     pub fn to_string(&self) -> String {
         match self {
             RuntimeValue::Sankhya(n) => n.to_string(),
+            RuntimeValue::Dashamsha(x) => x.to_string(),
             RuntimeValue::Satyasatya(b) => if *b { "सत्य".to_string() } else { "असत्य".to_string() },
             RuntimeValue::Shabda(s) => s.clone(),
             RuntimeValue::Suchi(list) => {
@@ -395,26 +439,40 @@ impl RuntimeValue {
             }
             RuntimeValue::Shunya => "शून्य".to_string(),
             RuntimeValue::Mantra(_) => "<mantra>".to_string(),
-            RuntimeValue::Dharma(obj) => {
+            RuntimeValue::Closure(closure) => format!("<closure {}>", closure.function),
+            RuntimeValue::Dharma(dharma, obj) => {
                 let fields: Vec<String> = obj.iter()
                     .map(|(k, v)| format!("{}: {}", k, v.to_string()))
                     .collect();
-                format!("dharma {{{}}}", fields.join(", "))
+                format!("{} {{{}}}", dharma, fields.join(", "))
             }
         }
     }
     
+    /// Promote two numeric values to a common representation, or `None` if
+    /// either is not a number
+    pub fn numeric_pair(&self, other: &RuntimeValue) -> Option<NumericPair> {
+        match (self, other) {
+            (RuntimeValue::Sankhya(x), RuntimeValue::Sankhya(y)) => Some(NumericPair::Sankhya(*x, *y)),
+            (RuntimeValue::Dashamsha(x), RuntimeValue::Dashamsha(y)) => Some(NumericPair::Dashamsha(*x, *y)),
+            (RuntimeValue::Sankhya(x), RuntimeValue::Dashamsha(y)) => Some(NumericPair::Dashamsha(*x as f64, *y)),
+            (RuntimeValue::Dashamsha(x), RuntimeValue::Sankhya(y)) => Some(NumericPair::Dashamsha(*x, *y as f64)),
+            _ => None,
+        }
+    }
+    
     /// Check if the value is truthy
     pub fn is_truthy(&self) -> bool {
         match self {
             RuntimeValue::Satyasatya(b) => *b,
             RuntimeValue::Sankhya(n) => *n != 0,
+            RuntimeValue::Dashamsha(x) => *x != 0.0,
             RuntimeValue::Shabda(s) => !s.is_empty(),
             RuntimeValue::Suchi(list) => !list.is_empty(),
             RuntimeValue::Nidhaan(map) => !map.is_empty(),
             RuntimeValue::Shunya => false,
-            RuntimeValue::Mantra(_) => true,
-            RuntimeValue::Dharma(obj) => !obj.is_empty(),
+            RuntimeValue::Mantra(_) | RuntimeValue::Closure(_) => true,
+            RuntimeValue::Dharma(_, obj) => !obj.is_empty(),
         }
     }
 }
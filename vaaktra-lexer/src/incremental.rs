@@ -0,0 +1,175 @@
+//! Incremental re-lexing
+//!
+//! An editor changes a few bytes at a time. [`relex`] updates the tokens of
+//! the previous text for one [`TextEdit`]: it lexes from the last token
+//! boundary before the edit until the new tokens line up with old ones
+//! again, and keeps the old tokens on either side, moving those after the
+//! edit by the change in length. The result is the same as lexing the new
+//! text from scratch.
+//!
+//! Lexing can restart at the end of any token, since whitespace and
+//! comments lie only between tokens. Once a new token starts where a
+//! shifted old one did and is equal to it, the rest of the input is the
+//! same text lexed from the same state, so the old tokens are reused.
+
+use std::ops::Range;
+
+use crate::{Lexer, StringPart, Token};
+
+/// Replacement of `range` in a text by `replacement`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, replacement: impl Into<String>) -> Self {
+        TextEdit { range, replacement: replacement.into() }
+    }
+
+    /// The text after the edit
+    pub fn apply(&self, text: &str) -> String {
+        let mut edited = String::with_capacity(text.len() + self.replacement.len());
+        edited.push_str(&text[..self.range.start]);
+        edited.push_str(&self.replacement);
+        edited.push_str(&text[self.range.end..]);
+        edited
+    }
+
+    /// How far text after the edited range moves
+    pub fn delta(&self) -> isize {
+        self.replacement.len() as isize - self.range.len() as isize
+    }
+
+    /// End of the replacement in the new text
+    pub fn new_end(&self) -> usize {
+        self.range.start + self.replacement.len()
+    }
+}
+
+/// Tokens after an edit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relexed {
+    pub tokens: Vec<(Token, Range<usize>)>,
+    /// Indices in `tokens` of the freshly lexed tokens; the others were
+    /// taken from the old tokens
+    pub relexed: Range<usize>,
+    /// Indices in the old tokens of those the fresh ones replace
+    pub replaced: Range<usize>,
+}
+
+/// Update `old_tokens` for `edit`, where `new_text` is the edited text
+pub fn relex(old_tokens: &[(Token, Range<usize>)], new_text: &str, edit: &TextEdit) -> Relexed {
+    // The first token the edit touches, or that grows if text is added
+    // right after it. Two more before it are lexed again because
+    // `महत्तर व समान` joins three words into one token.
+    let touched = old_tokens
+        .iter()
+        .position(|(_, span)| span.end >= edit.range.start)
+        .unwrap_or(old_tokens.len());
+    let first = touched.saturating_sub(2);
+    let restart = first.checked_sub(1).map_or(0, |prev| old_tokens[prev].1.end);
+
+    let mut tokens = old_tokens[..first].to_vec();
+    let mut old = first;
+    for token in Lexer::new(&new_text[restart..]) {
+        let (token, span) = shift(token, restart as isize);
+        if span.start >= edit.new_end() {
+            // Skip old tokens that now start before this one
+            while old < old_tokens.len() && !starts_after_edit(&old_tokens[old], edit, span.start) {
+                old += 1;
+            }
+            if let Some(old_token) = old_tokens.get(old) {
+                let (shifted, shifted_span) = shift(old_token.clone(), edit.delta());
+                if shifted_span == span && shifted == token {
+                    let relexed = first..tokens.len();
+                    tokens.extend(old_tokens[old..].iter().map(|token| shift(token.clone(), edit.delta())));
+                    return Relexed { tokens, relexed, replaced: first..old };
+                }
+            }
+        }
+        tokens.push((token, span));
+    }
+
+    Relexed {
+        relexed: first..tokens.len(),
+        tokens,
+        replaced: first..old_tokens.len(),
+    }
+}
+
+/// Whether an old token lies after the edited range and, once shifted,
+/// starts at or after `offset` of the new text
+fn starts_after_edit((_, span): &(Token, Range<usize>), edit: &TextEdit, offset: usize) -> bool {
+    span.start >= edit.range.end && span.start.checked_add_signed(edit.delta()) >= Some(offset)
+}
+
+/// A token moved by `delta` bytes, with the offsets of its `{}` holes
+fn shift((mut token, span): (Token, Range<usize>), delta: isize) -> (Token, Range<usize>) {
+    let move_by = |offset: usize| offset.checked_add_signed(delta).expect("token moved before the start of the text");
+    if let Token::InterpolatedString(parts) = &mut token {
+        for part in parts {
+            if let StringPart::Code { offset, tokens, .. } = part {
+                *offset = move_by(*offset);
+                *tokens = std::mem::take(tokens).into_iter().map(|token| shift(token, delta)).collect();
+            }
+        }
+    }
+    (token, move_by(span.start)..move_by(span.end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "मन्त्र फ(अ: सङ्ख्या) -> सङ्ख्या {\n    सूत्र ब = \"{अ} और {अ धन १}\";\n    यदि अ महत्तर व समान ब { प्रत्याहर अ। } // टिप्पणी\n    अ /* खण्ड */ धन २\n}\n";
+
+    fn relex_matches_scratch(source: &str, edit: &TextEdit) -> Relexed {
+        let old: Vec<_> = Lexer::new(source).collect();
+        let new_text = edit.apply(source);
+        let relexed = relex(&old, &new_text, edit);
+        let scratch: Vec<_> = Lexer::new(&new_text).collect();
+        assert_eq!(relexed.tokens, scratch, "{:?} on {:?}", edit, source);
+        relexed
+    }
+
+    #[test]
+    fn test_relex_matches_lexing_from_scratch() {
+        let offset = |needle: &str| SOURCE.find(needle).unwrap();
+        let edits = [
+            // Inside and around names
+            TextEdit::new(offset("फ(")..offset("फ(") + "फ".len(), "फलन"),
+            TextEdit::new(offset("ब =")..offset("ब ="), "क"),
+            // Into and out of a string and its holes
+            TextEdit::new(offset("{अ}") + 1..offset("{अ}") + 1, "ब धन "),
+            TextEdit::new(offset("\"{अ}")..offset("\"{अ}"), "\""),
+            // Comments that swallow code, or stop doing so
+            TextEdit::new(offset("अ /*")..offset("अ /*"), "/*"),
+            TextEdit::new(offset("// टिप्पणी")..offset("// टिप्पणी") + 2, ""),
+            TextEdit::new(offset("खण्ड */") + "खण्ड ".len()..offset("खण्ड */") + "खण्ड */".len(), ""),
+            // Splitting and joining `महत्तर व समान`
+            TextEdit::new(offset("व समान")..offset("व समान") + "व".len(), "च"),
+            TextEdit::new(offset("समान ब")..offset("समान ब") + "समान".len(), ""),
+            // A danda right after a keyword
+            TextEdit::new(offset("। }")..offset("। }") + "।".len(), ""),
+            TextEdit::new(offset("अ। }")..offset("अ। }") + "अ".len(), ""),
+            // At either end, and everything at once
+            TextEdit::new(0..0, "सूत्र ग = १;\n"),
+            TextEdit::new(SOURCE.len()..SOURCE.len(), "फ(१)"),
+            TextEdit::new(0..SOURCE.len(), "अ"),
+        ];
+        for edit in &edits {
+            relex_matches_scratch(SOURCE, edit);
+        }
+    }
+
+    #[test]
+    fn test_relex_reuses_tokens_outside_the_edit() {
+        let at = SOURCE.find("ब =").unwrap();
+        let relexed = relex_matches_scratch(SOURCE, &TextEdit::new(at..at + "ब".len(), "बल"));
+        // The two tokens before the name, the name, then back in step
+        assert_eq!(relexed.relexed.len(), 3);
+        assert_eq!(relexed.replaced.len(), 3);
+    }
+}
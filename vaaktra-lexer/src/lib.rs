@@ -3,11 +3,61 @@
 //! Inspired by Vedic Sanskrit and ancient Indian computational concepts
 
 use logos::Logos;
+use std::collections::VecDeque;
 use std::fmt;
+use std::ops::Range;
+use thiserror::Error;
+
+pub mod incremental;
+mod normalize;
+mod number;
+mod string;
+pub mod translit;
+mod trivia;
+
+pub use incremental::{relex, Relexed, TextEdit};
+pub use normalize::confusable_skeleton;
+pub use number::IntegerWidth;
+pub use string::StringPart;
+pub use translit::{Scheme, TransliteratingLexer};
+pub use trivia::{LosslessLexer, LosslessToken, Trivia};
+
+/// Problems found while lexing, carried by `Token::Error`
+#[derive(Debug, Clone, Default, PartialEq, Error)]
+pub enum LexError {
+    #[default]
+    #[error("unrecognized input")]
+    UnrecognizedToken,
+    
+    #[error("unterminated string literal")]
+    UnterminatedString,
+    
+    #[error("invalid escape sequence \\{0}")]
+    InvalidEscape(char),
+    
+    #[error("invalid unicode escape \\u{{{0}}}")]
+    InvalidUnicodeEscape(String),
+    
+    #[error("unterminated {{...}} in string literal")]
+    UnterminatedInterpolation,
+    
+    #[error("empty {{}} in string literal")]
+    EmptyInterpolation,
+    
+    #[error("unmatched `}}` in string literal; write \\}} for a literal brace")]
+    UnmatchedBrace,
+    
+    #[error("integer literal {literal} does not fit in {width}")]
+    IntegerOverflow { literal: String, width: IntegerWidth },
+    
+    #[error("identifier {0} contains an invisible joiner (ZWJ/ZWNJ)")]
+    InvisibleJoiner(String),
+}
 
 /// Represents the fundamental units of Vāktra source code
 /// Each token maps to concepts from Vedic literature and Sanskrit grammar
 #[derive(Logos, Debug, Clone, PartialEq)]
+#[logos(error = LexError)]
 #[logos(skip r"[ \t\n\r\f]+")]  // Skip whitespace
 #[logos(skip r"//[^\n]*")]       // Single-line comments
 #[logos(skip r"/\*([^*]|\*[^/])*\*/")] // Multi-line comments
@@ -21,6 +71,26 @@ pub enum Token {
     Fn,
     #[token("सूत्र")]  // Sūtra: Variable declaration
     Let,
+    #[token("यन्त्र")]  // Yantra: Module declaration
+    Module,
+    #[token("आनय")]   // Ānaya ("bring"): Import
+    Import,
+    #[token("गुणधर्म")]  // Guṇadharma ("property"): Interface a dharma may implement
+    Trait,
+    #[token("कार्यान्वय")] // Kāryānvaya ("carrying out"): Implementation of a गुणधर्म
+    Impl,
+    
+    // Modifiers
+    #[token("चल")]        // Cala ("moving"): Mutable binding (सूत्र चल अ = ०)
+    Mut,
+    #[token("सार्वजनिक")]  // Public item, field or method; private otherwise
+    Pub,
+    #[token("असमकाल")]    // Asynchronous mantra or block
+    Async,
+    #[token("प्रतीक्षा")]   // Await (भविष्य.प्रतीक्षा)
+    Await,
+    #[token("असुरक्षित")]  // Unsafe mantra
+    Unsafe,
     
     // Control Flow
     #[token("यदि")]     // If
@@ -35,12 +105,20 @@ pub enum Token {
     Break,
     #[token("अनुवृत्ति")] // Continue
     Continue,
+    #[token("प्रत्याहर")]  // Pratyāhara ("bringing back"): Return
+    Return,
+    #[token("मध्ये")]    // In (प्रत्येक x मध्ये xs)
+    In,
+    #[token("विकल्प")]   // Match (Vikalpa: alternative)
+    Match,
     #[token("ऋत")]      // Constant (Ṛta: cosmic order)
     Const,
     
     // Types
     #[token("सङ्ख्या")]  // Number
     NumberType,
+    #[token("दशांश")]    // Fractional number (Daśāṃśa: decimal part)
+    FloatType,
     #[token("सत्यासत्य")] // Boolean
     BoolType,
     #[token("शब्द")]     // String
@@ -53,52 +131,88 @@ pub enum Token {
     Void,
     
     // Literals
-    #[regex(r"[०-९]+", callback = |lex| lex.slice().chars().map(|c| c as u16 - 0x0966).fold(0, |acc, d| acc * 10 + d as i64), priority = 2)]
+    // Integers: ४२, 42, 0xff, 0b1010, 0o17, with optional _ separators
+    #[regex(r"[०-९][०-९_]*", number::lex_integer, priority = 2)]
+    #[regex(r"[0-9][0-9_]*|0x_*[0-9a-fA-F][0-9a-fA-F_]*|0b_*[01][01_]*|0o_*[0-7][0-7_]*", number::lex_integer)]
     Number(i64),
+    // Integers with a width suffix (२५५u8, 0xffi32), as value and width
+    #[regex(r"[०-९][०-९_]*(i8|i16|i32|i64|i128|u8|u16|u32|u64|u128)", number::lex_typed_integer)]
+    #[regex(r"([0-9][0-9_]*|0x_*[0-9a-fA-F][0-9a-fA-F_]*|0b_*[01][01_]*|0o_*[0-7][0-7_]*)(i8|i16|i32|i64|i128|u8|u16|u32|u64|u128)", number::lex_typed_integer)]
+    TypedNumber((u128, IntegerWidth)),
+    // Fractional literals need a fraction or an exponent: ३.१४, 2.5e-3, १e३.
+    // Each literal uses a single script of digits.
+    #[regex(r"[०-९]+(\.[०-९]+)?[eE][+-]?[०-९]+|[०-९]+\.[०-९]+", number::lex_float)]
+    #[regex(r"[0-9]+(\.[0-9]+)?[eE][+-]?[0-9]+|[0-9]+\.[0-9]+", number::lex_float)]
+    Float(f64),
     #[token("सत्य")]     // True
     True,
     #[token("असत्य")]    // False
     False,
-    #[regex(r#""[^"]*""#, |lex| {
-        let s = lex.slice();
-        s[1..s.len()-1].to_string() // Remove surrounding quotes
-    })]
+    // Strings without interpolation, and raw strings (r"...", r#"..."#)
+    #[regex(r##"r#*""##, string::lex_raw_string)]
     StringLit(String),
+    // Quoted strings; `Lexer` reports those without {} holes as StringLit
+    #[token("\"", string::lex_string)]
+    InterpolatedString(Vec<StringPart>),
     
-    // Identifiers (can start with Devanagari or _)
-    #[regex(r"[\p{Script=Devanagari}_][\p{Script=Devanagari}\p{Nd}_]*", callback = |lex| lex.slice().to_string(), priority = 1)]
+    // Identifiers (can start with Devanagari or _), in NFC. Joiners are
+    // matched only so they can be reported.
+    #[regex(r"[\p{Script=Devanagari}_][\p{Script=Devanagari}\p{Nd}_\u{200C}\u{200D}]*", callback = |lex| normalize::identifier(lex.slice()), priority = 1)]
     Ident(String),
     
+    // Loop labels ('बाह्य) for निर्गम/अनुवृत्ति
+    #[regex(r"'[\p{Script=Devanagari}_][\p{Script=Devanagari}\p{Nd}_\u{200C}\u{200D}]*", callback = |lex| normalize::identifier(&lex.slice()[1..]))]
+    Label(String),
+    
     // ===== Operators =====
+    // Word operators are whole tokens: an identifier that merely starts
+    // with one (समानतम, नाम, चर, वाक्य) is the longer match and lexes as
+    // an identifier. Each has an ASCII alias for mathematical code.
     #[token("=")]   // Assignment
     Equals,
     #[token("समान")]  // Equality
+    #[token("==")]
     Eq,
     #[token("असमान")]  // Inequality
+    #[token("!=")]
     Neq,
     #[token("लघुत्तर")]   // Less than
+    #[token("<")]        // Also opens generic arguments (सूची<सङ्ख्या>)
     Lt,
-    #[token("समानता")]  // Less than or equal
+    // Less than or equal: समानता, or लघुत्तर व समान (see `Lexer`)
+    #[token("समानता")]
+    #[token("<=")]
     Le,
     #[token("महत्तर")]   // Greater than
+    #[token(">")]        // Also closes generic arguments
     Gt,
-    #[token("महत्तर व समान")]  // Greater than or equal
+    // Greater than or equal: महत्तर व समान (see `Lexer`)
+    #[token(">=")]
     Ge,
     #[token("धन")]   // Addition
+    #[token("+")]
     Plus,
     #[token("ऋण")]   // Subtraction/Negation
+    #[token("-")]
     Minus,
     #[token("गुण")]   // Multiplication
+    #[token("*")]
     Star,
     #[token("भाग")]   // Division
+    #[token("/")]
     Slash,
     #[token("शेष")]   // Modulo
+    #[token("%")]
     Percent,
     #[token("च")]  // Logical AND
+    #[token("&&")]
     And,
     #[token("वा")]  // Logical OR
     Or,
+    #[token("||")]  // Logical OR, or an empty lambda parameter list (|| १)
+    DoublePipe,
     #[token("न")]   // Logical NOT
+    #[token("!")]   // Also the never type (!)
     Not,
     
     // ===== Delimiters =====
@@ -108,10 +222,19 @@ pub enum Token {
     #[token("}")] RBrace,
     #[token("[")] LBracket,
     #[token("]")] RBracket,
-    #[token(";")] Semicolon,
+    #[token(";")]
+    #[token("।")]                // Danda, the Sanskrit full stop
+    Semicolon,
+    #[token("॥")] DoubleDanda,  // Optionally closes a statement, item or block
     #[token(",")] Comma,
     #[token(".")] Dot,
+    #[token("..")] DotDot,
     #[token(":")] Colon,
+    #[token("::")] PathSep,
+    #[token("->")] Arrow,
+    #[token("=>")] FatArrow,
+    #[token("&")] Ampersand,
+    #[token("|")] Pipe,        // Lambda parameters (|अ| अ गुण अ)
     
     // ===== Vedic Concepts =====
     #[token("ब्रह्मन्")]  // Base object type (Brahman)
@@ -121,8 +244,17 @@ pub enum Token {
     #[token("संस्कार")]  // Type casting/conversion (Saṃskāra)
     Cast,
     
+    // ===== Reserved =====
+    // Words kept back for planned features, so that introducing them will
+    // not break programs using them as names. The parser rejects them.
+    #[token("प्रकार", |lex| lex.slice().to_string())]    // Type alias
+    #[token("स्थिर", |lex| lex.slice().to_string())]     // Static item
+    #[token("पाश", |lex| lex.slice().to_string())]      // Unconditional loop
+    #[token("यत्न", |lex| lex.slice().to_string())]      // Try block
+    Reserved(String),
+    
     // Error token for invalid input
-    Error,
+    Error(LexError),
 }
 
 impl fmt::Display for Token {
@@ -131,25 +263,82 @@ impl fmt::Display for Token {
     }
 }
 
+/// Token stream for the parser
+///
+/// On top of the generated lexer this joins the three-word comparisons
+/// `महत्तर व समान` (≥) and `लघुत्तर व समान` (≤), which may be separated by
+/// any whitespace or comments. `व` between such a comparison word and
+/// `समान` always forms the operator, never a variable.
 pub struct Lexer<'a> {
     inner: logos::SpannedIter<'a, Token>,
+    input: &'a str,
+    /// Tokens read ahead while looking for a three-word comparison
+    lookahead: VecDeque<(Token, Range<usize>)>,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
         Lexer {
             inner: Token::lexer(input).spanned(),
+            input,
+            lookahead: VecDeque::new(),
         }
     }
+
+    /// The next token from the generated lexer
+    fn read_token(&mut self) -> Option<(Token, Range<usize>)> {
+        self.inner.next().map(|(token, span)| {
+            // The generated matcher can take a word right before a danda,
+            // whose bytes begin like a letter's, for the wrong keyword or
+            // for a name; on its own the word lexes correctly
+            let slice = &self.input[span.clone()];
+            let token = match token {
+                Ok(_) if self.input[span.end..].starts_with(['।', '॥']) => {
+                    let mut alone = Token::lexer(slice).spanned();
+                    match (alone.next(), alone.next()) {
+                        (Some((relexed, relexed_span)), None) if relexed_span == (0..slice.len()) => relexed,
+                        _ => token,
+                    }
+                }
+                token => token,
+            };
+            let token = match token {
+                Ok(Token::InterpolatedString(mut parts)) => match parts.as_mut_slice() {
+                    [StringPart::Text(text)] => Token::StringLit(std::mem::take(text)),
+                    _ => Token::InterpolatedString(parts),
+                },
+                Ok(token) => token,
+                Err(error) => Token::Error(error),
+            };
+            (token, span)
+        })
+    }
 }
 
 impl<'a> Iterator for Lexer<'a> {
-    type Item = (Token, std::ops::Range<usize>);
+    type Item = (Token, Range<usize>);
     
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next().map(|(token, span)| {
-            (token.unwrap_or(Token::Error), span)
-        })
+        let (token, span) = self.lookahead.pop_front().or_else(|| self.read_token())?;
+        let joined = match (&token, &self.input[span.clone()]) {
+            (Token::Gt, "महत्तर") => Token::Ge,
+            (Token::Lt, "लघुत्तर") => Token::Le,
+            _ => return Some((token, span)),
+        };
+        while self.lookahead.len() < 2 {
+            let Some(next) = self.read_token() else { break };
+            self.lookahead.push_back(next);
+        }
+        match (self.lookahead.front(), self.lookahead.get(1)) {
+            (Some((Token::Ident(va), _)), Some((Token::Eq, eq_span)))
+                if va == "व" && &self.input[eq_span.clone()] == "समान" =>
+            {
+                let end = eq_span.end;
+                self.lookahead.clear();
+                Some((joined, span.start..end))
+            }
+            _ => Some((token, span)),
+        }
     }
 }
 
@@ -217,6 +406,172 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_lex_integer_forms() {
+        let integers = [
+            ("42", 42),
+            ("४२", 42),
+            ("1_000_000", 1_000_000),
+            ("१_०००", 1000),
+            ("0xff", 255),
+            ("0xFF_FF", 0xffff),
+            ("0b1010", 10),
+            ("0o17", 15),
+            ("9223372036854775807", i64::MAX),
+        ];
+        
+        for (input, expected) in integers {
+            let tokens: Vec<Token> = Lexer::new(input).map(|(t, _)| t).collect();
+            assert_eq!(tokens, vec![Token::Number(expected)], "Failed to tokenize: {}", input);
+        }
+    }
+    
+    #[test]
+    fn test_lex_integer_suffixes() {
+        let integers = [
+            ("२५५u8", 255, IntegerWidth::U8),
+            ("0xffi32", 255, IntegerWidth::I32),
+            ("1_000u128", 1000, IntegerWidth::U128),
+            ("0b1i8", 1, IntegerWidth::I8),
+            // Only valid negated, which the parser checks
+            ("128i8", 128, IntegerWidth::I8),
+            ("340282366920938463463374607431768211455u128", u128::MAX, IntegerWidth::U128),
+        ];
+        
+        for (input, value, width) in integers {
+            let tokens: Vec<Token> = Lexer::new(input).map(|(t, _)| t).collect();
+            assert_eq!(tokens, vec![Token::TypedNumber((value, width))], "Failed to tokenize: {}", input);
+        }
+    }
+    
+    #[test]
+    fn test_lex_integer_overflow() {
+        let overflows = [
+            ("9223372036854775809", IntegerWidth::I64),
+            ("९९९९९९९९९९९९९९९९९९९९", IntegerWidth::I64),
+            ("256u8", IntegerWidth::U8),
+            ("129i8", IntegerWidth::I8),
+            ("0x1_0000_0000u32", IntegerWidth::U32),
+            ("340282366920938463463374607431768211456u128", IntegerWidth::U128),
+        ];
+        
+        for (input, width) in overflows {
+            let tokens: Vec<Token> = Lexer::new(input).map(|(t, _)| t).collect();
+            let expected = LexError::IntegerOverflow { literal: input.to_string(), width };
+            assert_eq!(tokens, vec![Token::Error(expected)], "Failed to tokenize: {}", input);
+        }
+    }
+    
+    #[test]
+    fn test_lex_floats() {
+        let floats = [
+            ("१२.२५", 12.25),
+            ("12.25", 12.25),
+            ("२.५e-३", 0.0025),
+            ("1E3", 1000.0),
+            ("०.५", 0.5),
+        ];
+        
+        for (input, expected) in floats {
+            let tokens: Vec<Token> = Lexer::new(input).map(|(t, _)| t).collect();
+            assert_eq!(tokens, vec![Token::Float(expected)], "Failed to tokenize: {}", input);
+        }
+        
+        // A range is not a fraction, and the type keyword is not an identifier
+        let tokens: Vec<Token> = Lexer::new("१..५ दशांश").map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![Token::Number(1), Token::DotDot, Token::Number(5), Token::FloatType]);
+    }
+    
+    #[test]
+    fn test_lex_loop_labels() {
+        let tokens: Vec<Token> = Lexer::new("'बाह्य: प्रत्येक अ मध्ये सूची_१").map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![
+            Token::Label("बाह्य".to_string()),
+            Token::Colon,
+            Token::ForEach,
+            Token::Ident("अ".to_string()),
+            Token::In,
+            Token::Ident("सूची_१".to_string()),
+        ]);
+    }
+    
+    #[test]
+    fn test_lex_return_arrow() {
+        let tokens: Vec<Token> = Lexer::new("() -> सङ्ख्या").map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![Token::LParen, Token::RParen, Token::Arrow, Token::NumberType]);
+    }
+    
+    #[test]
+    fn test_lex_type_punctuation() {
+        let tokens: Vec<Token> = Lexer::new("निधान<शब्द, &[सङ्ख्या]> -> !").map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![
+            Token::MapType,
+            Token::Lt,
+            Token::StringType,
+            Token::Comma,
+            Token::Ampersand,
+            Token::LBracket,
+            Token::NumberType,
+            Token::RBracket,
+            Token::Gt,
+            Token::Arrow,
+            Token::Not,
+        ]);
+    }
+    
+    #[test]
+    fn test_lex_module_paths() {
+        let tokens: Vec<Token> = Lexer::new("यन्त्र गणित; आनय गणित::वर्ग;").map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![
+            Token::Module,
+            Token::Ident("गणित".to_string()),
+            Token::Semicolon,
+            Token::Import,
+            Token::Ident("गणित".to_string()),
+            Token::PathSep,
+            Token::Ident("वर्ग".to_string()),
+            Token::Semicolon,
+        ]);
+    }
+
+    #[test]
+    fn test_lex_lambda() {
+        let tokens: Vec<Token> = Lexer::new("|अ, ब| अ धन ब").map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![
+            Token::Pipe,
+            Token::Ident("अ".to_string()),
+            Token::Comma,
+            Token::Ident("ब".to_string()),
+            Token::Pipe,
+            Token::Ident("अ".to_string()),
+            Token::Plus,
+            Token::Ident("ब".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_lex_match_punctuation() {
+        let tokens: Vec<Token> = Lexer::new("विकल्प अ { बिन्दु { क, .. } => क, _ = ब }").map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![
+            Token::Match,
+            Token::Ident("अ".to_string()),
+            Token::LBrace,
+            Token::Ident("बिन्दु".to_string()),
+            Token::LBrace,
+            Token::Ident("क".to_string()),
+            Token::Comma,
+            Token::DotDot,
+            Token::RBrace,
+            Token::FatArrow,
+            Token::Ident("क".to_string()),
+            Token::Comma,
+            Token::Ident("_".to_string()),
+            Token::Equals,
+            Token::Ident("ब".to_string()),
+            Token::RBrace,
+        ]);
+    }
+
     #[test]
     fn test_lex_string_literals() {
         let input = r#""This is a test string with देवनागरी""#;
@@ -229,4 +584,159 @@ mod tests {
         
         assert!(lexer.next().is_none(), "Expected end of tokens");
     }
+    
+    #[test]
+    fn test_lex_string_escapes() {
+        let tokens: Vec<Token> = Lexer::new(r#""पंक्ति\n\t\"उद्धृत\" \\ \u{0950} \{\}""#).map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![Token::StringLit("पंक्ति\n\t\"उद्धृत\" \\ ॐ {}".to_string())]);
+        
+        let errors = [
+            (r#""\q" सत्य"#, LexError::InvalidEscape('q')),
+            (r#""\u{110000}" सत्य"#, LexError::InvalidUnicodeEscape("110000".to_string())),
+            (r#""{}" सत्य"#, LexError::EmptyInterpolation),
+            (r#""}" सत्य"#, LexError::UnmatchedBrace),
+            // An unclosed hole ends at the string's quote or the line's end
+            (r#""{ब" सत्य"#, LexError::UnterminatedInterpolation),
+            ("\"{ब\nसत्य", LexError::UnterminatedInterpolation),
+        ];
+        for (input, expected) in errors {
+            // The bad literal is consumed whole and lexing carries on
+            let tokens: Vec<Token> = Lexer::new(input).map(|(t, _)| t).collect();
+            assert_eq!(tokens, vec![Token::Error(expected), Token::True], "Input: {}", input);
+        }
+        
+        let tokens: Vec<Token> = Lexer::new(r#""अन्त"#).map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![Token::Error(LexError::UnterminatedString)]);
+    }
+    
+    #[test]
+    fn test_lex_raw_strings() {
+        let tokens: Vec<Token> = Lexer::new(r##"r"\d+\.vk" r#"उक्त "पद" \n"#"##).map(|(t, _)| t).collect();
+        assert_eq!(tokens, vec![
+            Token::StringLit(r"\d+\.vk".to_string()),
+            Token::StringLit(r#"उक्त "पद" \n"#.to_string()),
+        ]);
+    }
+    
+    #[test]
+    fn test_lex_interpolated_string() {
+        let input = r#""नमस्ते {नाम}, {मानचित्र["क"]}!""#;
+        let mut lexer = Lexer::new(input);
+        let Some((Token::InterpolatedString(parts), _)) = lexer.next() else {
+            panic!("Expected interpolated string");
+        };
+        let name_offset = input.find("नाम").unwrap();
+        let map_offset = input.find("मानचित्र").unwrap();
+        let code = |source: &str, offset: usize| StringPart::Code {
+            source: source.to_string(),
+            offset,
+            tokens: Lexer::new(source).map(|(t, r)| (t, r.start + offset..r.end + offset)).collect(),
+        };
+        assert_eq!(parts, vec![
+            StringPart::Text("नमस्ते ".to_string()),
+            code("नाम", name_offset),
+            StringPart::Text(", ".to_string()),
+            code(r#"मानचित्र["क"]"#, map_offset),
+            StringPart::Text("!".to_string()),
+        ]);
+        let StringPart::Code { tokens, .. } = &parts[3] else { unreachable!() };
+        assert_eq!(&input[tokens[0].1.clone()], "मानचित्र");
+        assert!(lexer.next().is_none());
+    }
+
+    #[test]
+    fn test_lex_identifiers_are_normalized() {
+        let precomposed: Vec<_> = Lexer::new("जो\u{095C}ना").map(|(t, _)| t).collect();
+        let decomposed: Vec<_> = Lexer::new("जोड़ना").map(|(t, _)| t).collect();
+        assert_eq!(precomposed, decomposed);
+
+        let mut lexer = Lexer::new("क्\u{200D}ष = १");
+        assert_eq!(lexer.next(), Some((Token::Error(LexError::InvisibleJoiner("क्ष".to_string())), 0..12)));
+        assert_eq!(lexer.next().map(|(t, _)| t), Some(Token::Equals));
+    }
+
+    fn lex_all(input: &str) -> Vec<Token> {
+        Lexer::new(input).map(|(t, _)| t).collect()
+    }
+
+    #[test]
+    fn test_lex_word_boundaries() {
+        let words = [
+            ("धर्म", Token::Class), ("मन्त्र", Token::Fn), ("सूत्र", Token::Let),
+            ("यन्त्र", Token::Module), ("आनय", Token::Import), ("यदि", Token::If),
+            ("अथवा", Token::Else), ("यावत्", Token::While), ("प्रत्येक", Token::ForEach),
+            ("निर्गम", Token::Break), ("अनुवृत्ति", Token::Continue), ("मध्ये", Token::In),
+            ("विकल्प", Token::Match), ("ऋत", Token::Const), ("सङ्ख्या", Token::NumberType),
+            ("दशांश", Token::FloatType), ("सत्यासत्य", Token::BoolType), ("शब्द", Token::StringType),
+            ("सूची", Token::ListType), ("निधान", Token::MapType), ("शून्य", Token::Void),
+            ("सत्य", Token::True), ("असत्य", Token::False), ("समान", Token::Eq),
+            ("असमान", Token::Neq), ("लघुत्तर", Token::Lt), ("समानता", Token::Le),
+            ("महत्तर", Token::Gt), ("धन", Token::Plus), ("ऋण", Token::Minus),
+            ("गुण", Token::Star), ("भाग", Token::Slash), ("शेष", Token::Percent),
+            ("च", Token::And), ("वा", Token::Or), ("न", Token::Not),
+            ("ब्रह्मन्", Token::Object), ("आत्मन्", Token::SelfValue), ("संस्कार", Token::Cast),
+            ("प्रत्याहर", Token::Return), ("चल", Token::Mut), ("सार्वजनिक", Token::Pub),
+            ("असमकाल", Token::Async), ("प्रतीक्षा", Token::Await), ("असुरक्षित", Token::Unsafe),
+            ("गुणधर्म", Token::Trait), ("कार्यान्वय", Token::Impl),
+            ("प्रकार", Token::Reserved("प्रकार".to_string())),
+            ("स्थिर", Token::Reserved("स्थिर".to_string())),
+            ("पाश", Token::Reserved("पाश".to_string())), ("यत्न", Token::Reserved("यत्न".to_string())),
+        ];
+        for (word, token) in words {
+            // Alone, between other tokens, and as the prefix of a longer name
+            assert_eq!(lex_all(word), std::slice::from_ref(&token), "{}", word);
+            assert_eq!(lex_all(&format!("({word})")), [Token::LParen, token.clone(), Token::RParen], "{}", word);
+            assert_eq!(lex_all(&format!("{word}।")), [token.clone(), Token::Semicolon], "{}", word);
+            assert_eq!(lex_all(&format!("{word}॥")), [token.clone(), Token::DoubleDanda], "{}", word);
+            let longer = format!("{word}क");
+            assert_eq!(lex_all(&longer), [Token::Ident(longer.clone())]);
+            let numbered = format!("{word}_१");
+            assert_eq!(lex_all(&numbered), [Token::Ident(numbered.clone())]);
+        }
+        // Names that start like single-syllable operators
+        for name in ["नाम", "चर", "वाक्य", "समानतम", "महत्तरम", "व"] {
+            assert_eq!(lex_all(name), [Token::Ident(name.to_string())]);
+        }
+    }
+
+    #[test]
+    fn test_lex_comparison_words() {
+        let ident = |s: &str| Token::Ident(s.to_string());
+        assert_eq!(lex_all("अ महत्तर ब"), [ident("अ"), Token::Gt, ident("ब")]);
+        assert_eq!(lex_all("अ लघुत्तर ब"), [ident("अ"), Token::Lt, ident("ब")]);
+        assert_eq!(lex_all("अ समानता ब"), [ident("अ"), Token::Le, ident("ब")]);
+        assert_eq!(lex_all("अ महत्तर वा ब"), [ident("अ"), Token::Gt, Token::Or, ident("ब")]);
+
+        let input = "अ महत्तर  व\n  /* ≥ */ समान ब";
+        let lexed: Vec<_> = Lexer::new(input).collect();
+        assert_eq!(lexed[1], (Token::Ge, 4..input.find(" ब").unwrap()));
+        assert_eq!(lex_all("अ लघुत्तर व समान ब"), [ident("अ"), Token::Le, ident("ब")]);
+
+        // Only the word form joins, and a failed join loses nothing
+        assert_eq!(lex_all("> व समान"), [Token::Gt, ident("व"), Token::Eq]);
+        assert_eq!(lex_all("महत्तर व == ब"), [Token::Gt, ident("व"), Token::Eq, ident("ब")]);
+        assert_eq!(lex_all("महत्तर महत्तर व समान"), [Token::Gt, Token::Ge]);
+        assert_eq!(lex_all("महत्तर व"), [Token::Gt, ident("व")]);
+    }
+
+    #[test]
+    fn test_lex_ascii_operators() {
+        assert_eq!(lex_all("+ - * / % == != < <= > >= && || !"), [
+            Token::Plus, Token::Minus, Token::Star, Token::Slash, Token::Percent,
+            Token::Eq, Token::Neq, Token::Lt, Token::Le, Token::Gt, Token::Ge,
+            Token::And, Token::DoublePipe, Token::Not,
+        ]);
+        // Longer punctuation still wins
+        assert_eq!(lex_all("= => -> // टिप्पणी"), [Token::Equals, Token::FatArrow, Token::Arrow]);
+        assert_eq!(lex_all("1-2/3"), [
+            Token::Number(1), Token::Minus, Token::Number(2), Token::Slash, Token::Number(3),
+        ]);
+    }
+
+    #[test]
+    fn test_lex_dandas() {
+        let ident = |s: &str| Token::Ident(s.to_string());
+        assert_eq!(lex_all("अ।ब ॥"), [ident("अ"), Token::Semicolon, ident("ब"), Token::DoubleDanda]);
+        assert_eq!(lex_all("१।"), [Token::Number(1), Token::Semicolon]);
+    }
 }
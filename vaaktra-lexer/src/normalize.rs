@@ -0,0 +1,74 @@
+//! Identifier normalization
+//!
+//! Devanagari has several encodings for text that renders identically:
+//! `ड़` is either U+095C or `ड` followed by the nukta U+093C, and zero-width
+//! joiners change nothing a reader can see in most fonts. Identifiers are
+//! brought to NFC so each spelling names one symbol, and joiners are
+//! rejected outright. [`confusable_skeleton`] goes further for lints,
+//! folding sequences that are distinct after NFC but look the same.
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::LexError;
+
+const ZWNJ: char = '\u{200C}';
+const ZWJ: char = '\u{200D}';
+
+/// NFC form of an identifier as written in the source
+pub(crate) fn identifier(raw: &str) -> Result<String, LexError> {
+    if raw.contains([ZWJ, ZWNJ]) {
+        return Err(LexError::InvisibleJoiner(raw.replace([ZWJ, ZWNJ], "")));
+    }
+    Ok(raw.nfc().collect())
+}
+
+/// Sequences that NFC keeps apart but render like a single letter
+const LOOKALIKES: [(&str, &str); 8] = [
+    ("अा", "आ"),
+    ("अो", "ओ"),
+    ("अौ", "औ"),
+    ("अॉ", "ऑ"),
+    ("अॅ", "ॲ"),
+    ("एे", "ऐ"),
+    ("ओँ", "ॐ"),
+    ("रव", "ख"),
+];
+
+/// A form of an identifier shared by every identifier that looks like it,
+/// for warning about names that a reader cannot tell apart
+pub fn confusable_skeleton(ident: &str) -> String {
+    let mut skeleton: String = ident
+        .nfc()
+        .filter(|&c| c != ZWJ && c != ZWNJ)
+        .map(crate::number::ascii_digit)
+        .collect();
+    for (sequence, letter) in LOOKALIKES {
+        skeleton = skeleton.replace(sequence, letter);
+    }
+    skeleton
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifiers_are_nfc() {
+        // Precomposed ड़ (U+095C) is excluded from composition, so both
+        // spellings normalize to ड + nukta
+        assert_eq!(identifier("जो\u{095C}ना").unwrap(), "जोड़ना");
+        assert_eq!(identifier("जोड़ना").unwrap(), "जोड़ना");
+        assert_eq!(
+            identifier("क\u{094D}\u{200D}ष"),
+            Err(LexError::InvisibleJoiner("क्ष".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_skeletons_fold_lookalikes() {
+        assert_eq!(confusable_skeleton("अाम"), confusable_skeleton("आम"));
+        assert_eq!(confusable_skeleton("रवाना"), confusable_skeleton("खाना"));
+        assert_eq!(confusable_skeleton("सूची_१"), confusable_skeleton("सूची_1"));
+        assert_ne!(confusable_skeleton("बल"), confusable_skeleton("वल"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Sub-lexer for numeric literals
+//!
+//! Integers are written in Devanagari (`४२`) or ASCII (`42`) digits, or in
+//! ASCII with a `0x`, `0b` or `0o` prefix. Any of them may use `_` as a
+//! digit separator and end in a width suffix such as `u8` or `i128`.
+//! Literals that do not fit their type are reported instead of wrapping.
+//! A signed literal may be one past its type's maximum, as that is the
+//! magnitude of its minimum; the parser accepts it only after `ऋण`.
+
+use logos::Lexer;
+
+use crate::{LexError, Token};
+
+/// Integer width for optimal performance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntegerWidth {
+    I8, I16, I32, I64, I128,
+    U8, U16, U32, U64, U128,
+}
+
+impl IntegerWidth {
+    const ALL: [IntegerWidth; 10] = [
+        IntegerWidth::I8, IntegerWidth::I16, IntegerWidth::I32, IntegerWidth::I64, IntegerWidth::I128,
+        IntegerWidth::U8, IntegerWidth::U16, IntegerWidth::U32, IntegerWidth::U64, IntegerWidth::U128,
+    ];
+
+    /// The literal suffix, which is also the type's name
+    pub fn suffix(self) -> &'static str {
+        match self {
+            IntegerWidth::I8 => "i8",
+            IntegerWidth::I16 => "i16",
+            IntegerWidth::I32 => "i32",
+            IntegerWidth::I64 => "i64",
+            IntegerWidth::I128 => "i128",
+            IntegerWidth::U8 => "u8",
+            IntegerWidth::U16 => "u16",
+            IntegerWidth::U32 => "u32",
+            IntegerWidth::U64 => "u64",
+            IntegerWidth::U128 => "u128",
+        }
+    }
+
+    /// Width named by a literal suffix
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|width| width.suffix() == suffix)
+    }
+
+    pub fn bits(self) -> u32 {
+        self.suffix()[1..].parse().expect("suffix ends in the bit count")
+    }
+
+    pub fn is_signed(self) -> bool {
+        self.suffix().starts_with('i')
+    }
+
+    /// Largest value a literal of this width may have. Negative values are
+    /// written with prefix `ऋण`, so literals themselves are never negative.
+    pub fn max_literal(self) -> u128 {
+        let bits = if self.is_signed() { self.bits() - 1 } else { self.bits() };
+        u128::MAX >> (128 - bits)
+    }
+
+    /// Largest value the lexer accepts: for signed widths, the magnitude of
+    /// the minimum, one more than [`IntegerWidth::max_literal`]
+    pub fn max_magnitude(self) -> u128 {
+        self.max_literal() + u128::from(self.is_signed())
+    }
+}
+
+impl std::fmt::Display for IntegerWidth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.suffix())
+    }
+}
+
+/// Split a literal into its digits, radix and optional width suffix
+fn split_literal(slice: &str) -> (String, u32, Option<IntegerWidth>) {
+    let (body, radix) = match slice.get(..2) {
+        Some("0x") => (&slice[2..], 16),
+        Some("0b") => (&slice[2..], 2),
+        Some("0o") => (&slice[2..], 8),
+        _ => (slice, 10),
+    };
+    let (body, width) = match body.find(['i', 'u']) {
+        Some(at) => (&body[..at], IntegerWidth::from_suffix(&body[at..])),
+        None => (body, None),
+    };
+    let digits = body.chars().filter(|&c| c != '_').map(ascii_digit).collect();
+    (digits, radix, width)
+}
+
+/// Read a Devanagari digit as its ASCII counterpart
+pub(crate) fn ascii_digit(c: char) -> char {
+    match c {
+        '०'..='९' => char::from(b'0' + (c as u32 - '०' as u32) as u8),
+        _ => c,
+    }
+}
+
+/// Value of an integer literal, which must fit its width: `सङ्ख्या` (i64)
+/// when there is no suffix
+fn literal_value(lex: &Lexer<Token>) -> Result<(u128, Option<IntegerWidth>), LexError> {
+    let (digits, radix, width) = split_literal(lex.slice());
+    let limit = width.unwrap_or(IntegerWidth::I64);
+    let overflow = || LexError::IntegerOverflow {
+        literal: lex.slice().to_string(),
+        width: limit,
+    };
+    let value = u128::from_str_radix(&digits, radix).map_err(|_| overflow())?;
+    if value > limit.max_magnitude() {
+        return Err(overflow());
+    }
+    Ok((value, width))
+}
+
+/// Callback for literals without a suffix. The magnitude of `i64::MIN`
+/// becomes `i64::MIN` itself, the only negative value a token holds.
+pub(crate) fn lex_integer(lex: &mut Lexer<Token>) -> Result<i64, LexError> {
+    let (value, _) = literal_value(lex)?;
+    Ok(value as i64)
+}
+
+/// Callback for literals with a width suffix
+pub(crate) fn lex_typed_integer(lex: &mut Lexer<Token>) -> Result<(u128, IntegerWidth), LexError> {
+    let (value, width) = literal_value(lex)?;
+    Ok((value, width.expect("typed literal regex requires a suffix")))
+}
+
+/// Value of a fractional literal, reading Devanagari digits as ASCII ones
+pub(crate) fn lex_float(lex: &mut Lexer<Token>) -> Option<f64> {
+    let ascii: String = lex.slice().chars().map(ascii_digit).collect();
+    ascii.parse().ok()
+}
@@ -0,0 +1,177 @@
+//! Sub-lexer for string literals
+//!
+//! Quoted strings support the escapes `\n \t \r \0 \\ \" \' \{ \}` and
+//! `\u{...}` with one to six hex digits, may span lines, and interpolate
+//! expressions written in braces: `"नमस्ते {नाम}"`. An interpolated
+//! expression must close on the line it opens. Raw strings
+//! (`r"..."`, `r#"..."#`) take their contents verbatim, for regular
+//! expressions and paths.
+
+use logos::Lexer;
+
+use crate::{LexError, Token};
+
+/// A piece of a quoted string literal
+#[derive(Debug, Clone, PartialEq)]
+pub enum StringPart {
+    /// Literal text, with escapes already resolved
+    Text(String),
+    /// Source of an interpolated expression, its byte offset in the input
+    /// and its tokens, whose spans are also offsets in the input
+    Code {
+        source: String,
+        offset: usize,
+        tokens: Vec<(Token, std::ops::Range<usize>)>,
+    },
+}
+
+/// Lex the rest of a quoted string after its opening `"`
+///
+/// On a malformed escape or brace the whole literal is still consumed so
+/// lexing resumes after the closing quote, and the first problem is reported.
+pub(crate) fn lex_string(lex: &mut Lexer<Token>) -> Result<Vec<StringPart>, LexError> {
+    let base = lex.span().end;
+    let rest = lex.remainder();
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut error = None;
+    let mut chars = rest.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                lex.bump(i + 1);
+                if !text.is_empty() || parts.is_empty() {
+                    parts.push(StringPart::Text(text));
+                }
+                return match error {
+                    Some(error) => Err(error),
+                    None => Ok(parts),
+                };
+            }
+            '\\' => match escape(&mut chars) {
+                Ok(c) => text.push(c),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            },
+            '{' => {
+                let start = i + 1;
+                let Some(len) = hole_end(&rest[start..]) else {
+                    // Resume after the string's own closing quote, or at the
+                    // end of the line if it has none
+                    let line_end = rest[start..].find('\n').map_or(rest.len(), |n| start + n);
+                    let end = rest[start..line_end].find('"').map_or(line_end, |q| start + q + 1);
+                    lex.bump(end);
+                    return Err(LexError::UnterminatedInterpolation);
+                };
+                if len == 0 {
+                    error.get_or_insert(LexError::EmptyInterpolation);
+                } else {
+                    if !text.is_empty() {
+                        parts.push(StringPart::Text(std::mem::take(&mut text)));
+                    }
+                    let source = &rest[start..start + len];
+                    let offset = base + start;
+                    let tokens = crate::Lexer::new(source)
+                        .map(|(token, range)| (token, range.start + offset..range.end + offset))
+                        .collect();
+                    parts.push(StringPart::Code {
+                        source: source.to_string(),
+                        offset,
+                        tokens,
+                    });
+                }
+                // Skip the expression and its closing brace
+                while chars.next_if(|&(j, _)| j <= start + len).is_some() {}
+            }
+            '}' => {
+                error.get_or_insert(LexError::UnmatchedBrace);
+            }
+            _ => text.push(c),
+        }
+    }
+
+    lex.bump(rest.len());
+    Err(LexError::UnterminatedString)
+}
+
+/// Resolve the escape following a backslash
+fn escape(chars: &mut std::iter::Peekable<std::str::CharIndices>) -> Result<char, LexError> {
+    let Some((_, c)) = chars.next() else {
+        return Err(LexError::UnterminatedString);
+    };
+    Ok(match c {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        '0' => '\0',
+        '\\' | '"' | '\'' | '{' | '}' => c,
+        'u' => {
+            if chars.next_if(|&(_, c)| c == '{').is_none() {
+                return Err(LexError::InvalidUnicodeEscape(String::new()));
+            }
+            let mut digits = String::new();
+            while let Some((_, c)) = chars.next_if(|&(_, c)| c != '}' && c != '"') {
+                digits.push(c);
+            }
+            if chars.next_if(|&(_, c)| c == '}').is_none() || digits.is_empty() || digits.len() > 6 {
+                return Err(LexError::InvalidUnicodeEscape(digits));
+            }
+            u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or(LexError::InvalidUnicodeEscape(digits))?
+        }
+        other => return Err(LexError::InvalidEscape(other)),
+    })
+}
+
+/// Length of an interpolated expression up to its closing `}`, allowing
+/// nested braces and string literals inside it. `None` if the line ends
+/// first.
+fn hole_end(code: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in code.char_indices() {
+        if c == '\n' {
+            return None;
+        }
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(i),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Lex a raw string after its `r`, `#`s and opening `"`
+pub(crate) fn lex_raw_string(lex: &mut Lexer<Token>) -> Result<String, LexError> {
+    let hashes = lex.slice().len() - 2; // r ... "
+    let terminator = format!("\"{}", "#".repeat(hashes));
+    let rest = lex.remainder();
+    match rest.find(&terminator) {
+        Some(end) => {
+            let contents = rest[..end].to_string();
+            lex.bump(end + terminator.len());
+            Ok(contents)
+        }
+        None => {
+            lex.bump(rest.len());
+            Err(LexError::UnterminatedString)
+        }
+    }
+}
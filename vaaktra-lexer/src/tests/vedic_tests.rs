@@ -73,7 +73,7 @@ fn test_vedic_operators() {
     assert_eq!(lexer.next().unwrap().0, Token::Slash);
     assert_eq!(lexer.next().unwrap().0, Token::Percent);
     assert_eq!(lexer.next().unwrap().0, Token::And);
-    assert_eq!(lexer.next().unwrap().0, Token::Or);
+    assert_eq!(lexer.next().unwrap().0, Token::DoublePipe);
     assert_eq!(lexer.next().unwrap().0, Token::Not);
 }
 
@@ -0,0 +1,527 @@
+//! Romanized input for Vāktra source
+//!
+//! Source written in IAST, ITRANS or Harvard-Kyoto is rewritten into
+//! Devanagari before lexing, so `yadi` and `यदि` produce the same `Token`
+//! and `sUtra nAma` declares the same identifier as `सूत्र नाम`. Only
+//! code is rewritten: string contents, comments and numeric literals are
+//! kept as written, while `{}` holes in strings and loop labels are
+//! rewritten like any other code.
+//!
+//! [`from_devanagari`] goes the other way, so tooling can show a program
+//! in whichever script its reader prefers.
+
+use std::ops::Range;
+
+use crate::{Lexer, StringPart, Token};
+
+/// A romanization scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scheme {
+    /// International Alphabet of Sanskrit Transliteration (`sūtra`)
+    Iast,
+    /// ITRANS (`sUtra`, `shabda`)
+    Itrans,
+    /// Harvard-Kyoto (`sUtra`, `zabda`)
+    HarvardKyoto,
+}
+
+/// What a romanized letter group stands for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    /// Independent vowel and its dependent sign (none for अ)
+    Vowel(char, Option<char>),
+    Consonant(char),
+    /// Anusvara, visarga or candrabindu
+    Mark(char),
+}
+
+const VOWELS: [(char, Option<char>); 14] = [
+    ('अ', None), ('आ', Some('ा')), ('इ', Some('ि')), ('ई', Some('ी')),
+    ('उ', Some('ु')), ('ऊ', Some('ू')), ('ऋ', Some('ृ')), ('ॠ', Some('ॄ')),
+    ('ऌ', Some('ॢ')), ('ॡ', Some('ॣ')), ('ए', Some('े')), ('ऐ', Some('ै')),
+    ('ओ', Some('ो')), ('औ', Some('ौ')),
+];
+
+const CONSONANTS: [char; 33] = [
+    'क', 'ख', 'ग', 'घ', 'ङ', 'च', 'छ', 'ज', 'झ', 'ञ', 'ट', 'ठ', 'ड', 'ढ', 'ण',
+    'त', 'थ', 'द', 'ध', 'न', 'प', 'फ', 'ब', 'भ', 'म', 'य', 'र', 'ल', 'व',
+    'श', 'ष', 'स', 'ह',
+];
+
+const MARKS: [char; 3] = ['ं', 'ः', 'ँ'];
+
+const VIRAMA: char = '्';
+
+/// Spellings of `VOWELS`, `CONSONANTS` and `MARKS` in each scheme; the
+/// first spelling of each is the one `from_devanagari` produces
+struct Table {
+    vowels: [&'static [&'static str]; 14],
+    consonants: [&'static [&'static str]; 33],
+    marks: [&'static [&'static str]; 3],
+}
+
+const IAST: Table = Table {
+    vowels: [
+        &["a"], &["ā"], &["i"], &["ī"], &["u"], &["ū"], &["ṛ"], &["ṝ"], &["ḷ"], &["ḹ"],
+        &["e"], &["ai"], &["o"], &["au"],
+    ],
+    consonants: [
+        &["k"], &["kh"], &["g"], &["gh"], &["ṅ"], &["c"], &["ch"], &["j"], &["jh"], &["ñ"],
+        &["ṭ"], &["ṭh"], &["ḍ"], &["ḍh"], &["ṇ"], &["t"], &["th"], &["d"], &["dh"], &["n"],
+        &["p"], &["ph"], &["b"], &["bh"], &["m"], &["y"], &["r"], &["l"], &["v"],
+        &["ś"], &["ṣ"], &["s"], &["h"],
+    ],
+    marks: [&["ṃ"], &["ḥ"], &["m̐"]],
+};
+
+const ITRANS: Table = Table {
+    vowels: [
+        &["a"], &["A", "aa"], &["i"], &["I", "ii"], &["u"], &["U", "uu"], &["RRi", "R^i"],
+        &["RRI", "R^I"], &["LLi", "L^i"], &["LLI", "L^I"], &["e"], &["ai"], &["o"], &["au"],
+    ],
+    consonants: [
+        &["k"], &["kh"], &["g"], &["gh"], &["~N"], &["ch"], &["Ch", "chh"], &["j"], &["jh"], &["~n"],
+        &["T"], &["Th"], &["D"], &["Dh"], &["N"], &["t"], &["th"], &["d"], &["dh"], &["n"],
+        &["p"], &["ph"], &["b"], &["bh"], &["m"], &["y"], &["r"], &["l"], &["v", "w"],
+        &["sh"], &["Sh", "shh"], &["s"], &["h"],
+    ],
+    marks: [&["M"], &["H"], &["M^"]],
+};
+
+const HARVARD_KYOTO: Table = Table {
+    vowels: [
+        &["a"], &["A"], &["i"], &["I"], &["u"], &["U"], &["R"], &["RR"], &["lR"], &["lRR"],
+        &["e"], &["ai"], &["o"], &["au"],
+    ],
+    consonants: [
+        &["k"], &["kh"], &["g"], &["gh"], &["G"], &["c"], &["ch"], &["j"], &["jh"], &["J"],
+        &["T"], &["Th"], &["D"], &["Dh"], &["N"], &["t"], &["th"], &["d"], &["dh"], &["n"],
+        &["p"], &["ph"], &["b"], &["bh"], &["m"], &["y"], &["r"], &["l"], &["v"],
+        &["z"], &["S"], &["s"], &["h"],
+    ],
+    marks: [&["M"], &["H"], &["~"]],
+};
+
+impl Scheme {
+    fn table(self) -> &'static Table {
+        match self {
+            Scheme::Iast => &IAST,
+            Scheme::Itrans => &ITRANS,
+            Scheme::HarvardKyoto => &HARVARD_KYOTO,
+        }
+    }
+
+    /// Whether `c` can be part of a romanized word in this scheme
+    fn is_word_char(self, c: char) -> bool {
+        match self {
+            Scheme::Iast => c.is_alphabetic() && !is_devanagari(c) || c == '\u{310}',
+            Scheme::Itrans => c.is_ascii_alphabetic() || c == '~' || c == '^',
+            Scheme::HarvardKyoto => c.is_ascii_alphabetic() || c == '~',
+        }
+    }
+
+    /// The longest letter group at the start of `text`, and its length
+    fn longest_unit(self, text: &str) -> Option<(Unit, usize)> {
+        let table = self.table();
+        let vowels = table.vowels.iter().zip(VOWELS).map(|(s, (v, sign))| (*s, Unit::Vowel(v, sign)));
+        let consonants = table.consonants.iter().zip(CONSONANTS).map(|(s, c)| (*s, Unit::Consonant(c)));
+        let marks = table.marks.iter().zip(MARKS).map(|(s, m)| (*s, Unit::Mark(m)));
+        vowels
+            .chain(consonants)
+            .chain(marks)
+            .flat_map(|(spellings, unit)| spellings.iter().map(move |s| (*s, unit)))
+            .filter(|(spelling, _)| text.starts_with(spelling))
+            .max_by_key(|(spelling, _)| spelling.len())
+            .map(|(spelling, unit)| (unit, spelling.len()))
+    }
+}
+
+fn is_devanagari(c: char) -> bool {
+    ('\u{900}'..='\u{97F}').contains(&c)
+}
+
+/// Transliterate one romanized word into Devanagari. Letters the scheme
+/// does not know, digits and `_` are kept, with ASCII digits written as
+/// Devanagari ones so `sUcI_1` and `सूची_१` name the same thing.
+pub fn to_devanagari(word: &str, scheme: Scheme) -> String {
+    let mut out = String::with_capacity(word.len() * 3);
+    // Whether the last output was a consonant still waiting for its vowel
+    let mut open_consonant = false;
+    let mut rest = word;
+
+    while let Some(c) = rest.chars().next() {
+        let unit = scheme.longest_unit(rest);
+        if open_consonant && !matches!(unit, Some((Unit::Vowel(..), _))) {
+            out.push(VIRAMA);
+        }
+        open_consonant = false;
+        match unit {
+            Some((Unit::Vowel(vowel, sign), len)) => {
+                let after_consonant = out.chars().last().is_some_and(|c| CONSONANTS.contains(&c));
+                match (after_consonant, sign) {
+                    (true, Some(sign)) => out.push(sign),
+                    (true, None) => {}
+                    (false, _) => out.push(vowel),
+                }
+                rest = &rest[len..];
+            }
+            Some((Unit::Consonant(consonant), len)) => {
+                out.push(consonant);
+                open_consonant = true;
+                rest = &rest[len..];
+            }
+            Some((Unit::Mark(mark), len)) => {
+                out.push(mark);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push(match c {
+                    '0'..='9' => char::from_u32('०' as u32 + (c as u32 - '0' as u32)).unwrap_or(c),
+                    _ => c,
+                });
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if open_consonant {
+        out.push(VIRAMA);
+    }
+    out
+}
+
+/// Romanize Devanagari text; everything else is copied unchanged
+pub fn from_devanagari(text: &str, scheme: Scheme) -> String {
+    let table = scheme.table();
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if let Some(i) = CONSONANTS.iter().position(|&k| k == c) {
+            out.push_str(table.consonants[i][0]);
+            match chars.peek() {
+                Some(&VIRAMA) => {
+                    chars.next();
+                }
+                Some(&next) => match VOWELS.iter().position(|(_, sign)| *sign == Some(next)) {
+                    Some(v) => {
+                        out.push_str(table.vowels[v][0]);
+                        chars.next();
+                    }
+                    None => out.push_str(table.vowels[0][0]),
+                },
+                None => out.push_str(table.vowels[0][0]),
+            }
+        } else if let Some(v) = VOWELS.iter().position(|(vowel, _)| *vowel == c) {
+            out.push_str(table.vowels[v][0]);
+        } else if let Some(m) = MARKS.iter().position(|&mark| mark == c) {
+            out.push_str(table.marks[m][0]);
+        } else if ('०'..='९').contains(&c) {
+            out.push(char::from(b'0' + (c as u32 - '०' as u32) as u8));
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Romanized source rewritten into Devanagari, remembering where every
+/// rewritten word came from
+#[derive(Debug, Clone)]
+pub struct TransliteratedSource {
+    source: String,
+    /// Output range and input range of each rewritten word, in order
+    rewrites: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl TransliteratedSource {
+    /// Rewrite the code in `input` from `scheme` into Devanagari
+    pub fn new(input: &str, scheme: Scheme) -> Self {
+        let mut rewriter = Rewriter {
+            input,
+            scheme,
+            pos: 0,
+            out: TransliteratedSource {
+                source: String::with_capacity(input.len() * 2),
+                rewrites: Vec::new(),
+            },
+        };
+        rewriter.code(false);
+        rewriter.out
+    }
+
+    /// The Devanagari source
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The input range a range of the Devanagari source came from. Spans
+    /// that begin or end inside a rewritten word are widened to the word.
+    pub fn original_range(&self, range: Range<usize>) -> Range<usize> {
+        self.original_offset(range.start, false)..self.original_offset(range.end, true)
+    }
+
+    fn original_offset(&self, offset: usize, is_end: bool) -> usize {
+        let i = self.rewrites.partition_point(|(out, _)| out.start <= offset);
+        let Some((out, input)) = i.checked_sub(1).map(|i| &self.rewrites[i]) else {
+            return offset;
+        };
+        if offset >= out.end {
+            offset - out.end + input.end
+        } else if is_end && offset > out.start {
+            input.end
+        } else {
+            input.start
+        }
+    }
+}
+
+/// Scans romanized source, copying everything but code words verbatim
+struct Rewriter<'a> {
+    input: &'a str,
+    scheme: Scheme,
+    pos: usize,
+    out: TransliteratedSource,
+}
+
+impl Rewriter<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    /// Copy input up to `end` unchanged
+    fn copy_to(&mut self, end: usize) {
+        self.out.source.push_str(&self.input[self.pos..end]);
+        self.pos = end;
+    }
+
+    /// Copy one character unchanged
+    fn copy_char(&mut self) {
+        let len = self.peek().map_or(0, char::len_utf8);
+        self.copy_to(self.pos + len);
+    }
+
+    /// Rewrite code up to the end of input, or up to the `}` closing an
+    /// interpolation hole when `in_hole` is set
+    fn code(&mut self, in_hole: bool) {
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            let rest = self.rest();
+            if rest.starts_with("//") {
+                let end = rest.find('\n').map_or(self.input.len(), |i| self.pos + i);
+                self.copy_to(end);
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                let end = comment.find("*/").map_or(self.input.len(), |i| self.pos + i + 4);
+                self.copy_to(end);
+            } else if c == 'r' && (rest[1..].starts_with('"') || rest[1..].starts_with("#")) {
+                self.raw_string();
+            } else if c == '"' {
+                self.string();
+            } else if c.is_ascii_digit() || ('०'..='९').contains(&c) {
+                // Numbers keep their prefixes and suffixes (0xff, २५५u8)
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || ('०'..='९').contains(&c)))
+                    .unwrap_or(rest.len());
+                self.copy_to(self.pos + len);
+            } else if self.scheme.is_word_char(c) {
+                self.word();
+            } else {
+                match c {
+                    '{' => depth += 1,
+                    '}' if in_hole && depth == 0 => return,
+                    '}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                self.copy_char();
+            }
+        }
+    }
+
+    /// Rewrite a romanized word, including any `_` and digits inside it
+    fn word(&mut self) {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(self.scheme.is_word_char(c) || c == '_' || c.is_ascii_digit() || is_devanagari(c)))
+            .unwrap_or(rest.len());
+        let input = self.pos..self.pos + len;
+        let start = self.out.source.len();
+        self.out.source.push_str(&to_devanagari(&self.input[input.clone()], self.scheme));
+        self.out.rewrites.push((start..self.out.source.len(), input.clone()));
+        self.pos = input.end;
+    }
+
+    /// Copy a quoted string, rewriting the code in its `{}` holes
+    fn string(&mut self) {
+        self.copy_char();
+        while let Some(c) = self.peek() {
+            match c {
+                '\\' => {
+                    self.copy_char();
+                    self.copy_char();
+                }
+                '{' => {
+                    self.copy_char();
+                    self.code(true);
+                    self.copy_char();
+                }
+                '"' => {
+                    self.copy_char();
+                    return;
+                }
+                _ => self.copy_char(),
+            }
+        }
+    }
+
+    /// Copy a raw string unchanged
+    fn raw_string(&mut self) {
+        let rest = self.rest();
+        let hashes = rest[1..].chars().take_while(|&c| c == '#').count();
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let body = 1 + hashes + 1;
+        let end = rest
+            .get(body..)
+            .and_then(|tail| tail.find(&terminator))
+            .map_or(self.input.len(), |i| self.pos + body + i + terminator.len());
+        self.copy_to(end);
+    }
+}
+
+/// Lexes romanized source into the same tokens as its Devanagari form,
+/// with spans pointing into the romanized input. Interpolated code keeps
+/// its Devanagari text, but its offset and the spans of its tokens point
+/// into the input too.
+pub struct TransliteratingLexer {
+    tokens: std::vec::IntoIter<(Token, Range<usize>)>,
+}
+
+impl TransliteratingLexer {
+    pub fn new(input: &str, scheme: Scheme) -> Self {
+        let source = TransliteratedSource::new(input, scheme);
+        let tokens: Vec<_> = Lexer::new(source.source())
+            .map(|token| source.original_token(token))
+            .collect();
+        TransliteratingLexer {
+            tokens: tokens.into_iter(),
+        }
+    }
+}
+
+impl TransliteratedSource {
+    /// A token of the Devanagari source with its span, and those of any
+    /// tokens in its `{}` holes, moved back into the input
+    fn original_token(&self, (mut token, span): (Token, Range<usize>)) -> (Token, Range<usize>) {
+        if let Token::InterpolatedString(parts) = &mut token {
+            for part in parts {
+                if let StringPart::Code { offset, tokens, .. } = part {
+                    *offset = self.original_offset(*offset, false);
+                    *tokens = std::mem::take(tokens).into_iter().map(|token| self.original_token(token)).collect();
+                }
+            }
+        }
+        (token, self.original_range(span))
+    }
+}
+
+impl Iterator for TransliteratingLexer {
+    type Item = (Token, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tokens.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(lexer: impl Iterator<Item = (Token, Range<usize>)>) -> Vec<Token> {
+        lexer.map(|(t, _)| t).collect()
+    }
+
+    #[test]
+    fn test_keywords_in_every_scheme() {
+        let words = [
+            ("mantra", "mantra", "mantra", "मन्त्र"),
+            ("sūtra", "sUtra", "sUtra", "सूत्र"),
+            ("yadi", "yadi", "yadi", "यदि"),
+            ("saṅkhyā", "sa~NkhyA", "saGkhyA", "सङ्ख्या"),
+            ("anuvṛtti", "anuvRRitti", "anuvRtti", "अनुवृत्ति"),
+            ("daśāṃśa", "dashAMsha", "dazAMza", "दशांश"),
+            ("ṛṇa", "RRiNa", "RNa", "ऋण"),
+            ("ātman", "Atman", "Atman", "आत्मन्"),
+            ("saṃskāra", "saMskAra", "saMskAra", "संस्कार"),
+        ];
+        for (iast, itrans, hk, devanagari) in words {
+            assert_eq!(to_devanagari(iast, Scheme::Iast), devanagari);
+            assert_eq!(to_devanagari(itrans, Scheme::Itrans), devanagari);
+            assert_eq!(to_devanagari(hk, Scheme::HarvardKyoto), devanagari);
+            assert_eq!(from_devanagari(devanagari, Scheme::Iast), iast);
+            assert_eq!(from_devanagari(devanagari, Scheme::HarvardKyoto), hk);
+        }
+    }
+
+    #[test]
+    fn test_romanized_source_lexes_like_devanagari() {
+        let devanagari = "मन्त्र योग(अ: सङ्ख्या) -> सङ्ख्या { यदि अ लघुत्तर 0x10 { अ धन १ } }";
+        let romanized = "mantra yoga(a: saGkhyA) -> saGkhyA { yadi a laghuttara 0x10 { a dhana १ } }";
+        assert_eq!(
+            tokens(TransliteratingLexer::new(romanized, Scheme::HarvardKyoto)),
+            tokens(Lexer::new(devanagari))
+        );
+    }
+
+    #[test]
+    fn test_string_text_is_kept_but_holes_are_code() {
+        let input = r#"yadi "a = {a}""#;
+        let lexed: Vec<_> = TransliteratingLexer::new(input, Scheme::HarvardKyoto).collect();
+        let (Token::InterpolatedString(parts), _) = &lexed[1] else {
+            panic!("Expected interpolated string, got {:?}", lexed[1]);
+        };
+        assert_eq!(parts[0], StringPart::Text("a = ".to_string()));
+        let StringPart::Code { source, offset, tokens } = &parts[1] else {
+            panic!("Expected a hole, got {:?}", parts[1]);
+        };
+        assert_eq!(source, "अ");
+        assert_eq!(*offset, input.find("a}").unwrap());
+        assert_eq!(tokens, &[(Token::Ident("अ".into()), *offset..*offset + 1)]);
+    }
+
+    #[test]
+    fn test_hole_spans_stay_inside_the_hole() {
+        let input = r#""{saGkhyA dhana nAma}""#;
+        let lexed: Vec<_> = TransliteratingLexer::new(input, Scheme::HarvardKyoto).collect();
+        let (Token::InterpolatedString(parts), _) = &lexed[0] else {
+            panic!("Expected interpolated string, got {:?}", lexed[0]);
+        };
+        let StringPart::Code { tokens, .. } = &parts[0] else {
+            panic!("Expected a hole, got {:?}", parts[0]);
+        };
+        let spans: Vec<_> = tokens.iter().map(|(_, span)| &input[span.clone()]).collect();
+        assert_eq!(spans, ["saGkhyA", "dhana", "nAma"]);
+    }
+
+    #[test]
+    fn test_identifiers_match_across_scripts() {
+        let romanized = tokens(TransliteratingLexer::new("sUcI_1 'bAhya nAma", Scheme::HarvardKyoto));
+        assert_eq!(romanized, tokens(Lexer::new("सूची_१ 'बाह्य नाम")));
+    }
+
+    #[test]
+    fn test_spans_point_into_romanized_input() {
+        let input = "sUtra phala = 255u8; // ṭippaṇī";
+        let spans: Vec<_> = TransliteratingLexer::new(input, Scheme::Itrans)
+            .map(|(_, span)| &input[span])
+            .collect();
+        assert_eq!(spans, ["sUtra", "phala", "=", "255u8", ";"]);
+    }
+
+    #[test]
+    fn test_raw_strings_and_comments_are_untouched() {
+        let source = TransliteratedSource::new(r##"r#"mantra"# /* yadi */ yadi"##, Scheme::Iast);
+        assert_eq!(source.source(), r##"r#"mantra"# /* yadi */ यदि"##);
+    }
+}
@@ -0,0 +1,162 @@
+//! Lossless lexing
+//!
+//! [`Lexer`] drops whitespace and comments. [`LosslessLexer`] yields them
+//! too, as trivia between the same tokens, so the spans it produces cover
+//! every byte of the input and concatenating their text gives the source
+//! back. Formatters, refactoring tools and documentation extraction build
+//! on this instead of the parser's token stream.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::{Lexer, Token};
+
+/// Text between tokens that carries no meaning for the parser
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trivia {
+    /// Spaces, tabs and line breaks
+    Whitespace,
+    /// `// ...` up to the end of the line
+    LineComment,
+    /// `/* ... */`
+    BlockComment,
+    /// `/// ...` or `/** ... */`, documenting the item after it
+    DocComment,
+    /// `//! ...` or `/*! ... */`, documenting the file or block it is in
+    InnerDocComment,
+}
+
+/// A token or a piece of trivia
+#[derive(Debug, Clone, PartialEq)]
+pub enum LosslessToken {
+    Token(Token),
+    Trivia(Trivia),
+}
+
+impl LosslessToken {
+    /// The token the parser would see, if this is not trivia
+    pub fn into_token(self) -> Option<Token> {
+        match self {
+            LosslessToken::Token(token) => Some(token),
+            LosslessToken::Trivia(_) => None,
+        }
+    }
+
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, LosslessToken::Trivia(_))
+    }
+}
+
+/// Lexes tokens and the trivia between them
+pub struct LosslessLexer<'a> {
+    inner: Lexer<'a>,
+    input: &'a str,
+    /// End of the last token taken from `inner`
+    pos: usize,
+    pending: VecDeque<(LosslessToken, Range<usize>)>,
+}
+
+impl<'a> LosslessLexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        LosslessLexer {
+            inner: Lexer::new(input),
+            input,
+            pos: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Queue the trivia in a gap the token lexer skipped
+    fn split_gap(&mut self, end: usize) {
+        while self.pos < end {
+            let rest = &self.input[self.pos..end];
+            let (kind, len) = if let Some(comment) = rest.strip_prefix("/*") {
+                let len = comment.find("*/").map_or(rest.len(), |i| i + 4);
+                let kind = if rest.starts_with("/*!") {
+                    Trivia::InnerDocComment
+                } else if rest.starts_with("/**") && !rest.starts_with("/**/") {
+                    Trivia::DocComment
+                } else {
+                    Trivia::BlockComment
+                };
+                (kind, len)
+            } else if rest.starts_with("//") {
+                let len = rest.find('\n').unwrap_or(rest.len());
+                let kind = if rest.starts_with("//!") {
+                    Trivia::InnerDocComment
+                } else if rest.starts_with("///") && !rest.starts_with("////") {
+                    Trivia::DocComment
+                } else {
+                    Trivia::LineComment
+                };
+                (kind, len)
+            } else {
+                let len = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+                // Anything else in a gap is a lexer bug; keep it as text
+                // rather than lose it
+                (Trivia::Whitespace, len.max(rest.chars().next().map_or(0, char::len_utf8)))
+            };
+            self.pending.push_back((LosslessToken::Trivia(kind), self.pos..self.pos + len));
+            self.pos += len;
+        }
+    }
+}
+
+impl Iterator for LosslessLexer<'_> {
+    type Item = (LosslessToken, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending.is_empty() {
+            match self.inner.next() {
+                Some((token, span)) => {
+                    self.split_gap(span.start);
+                    self.pos = span.end;
+                    self.pending.push_back((LosslessToken::Token(token), span));
+                }
+                None => self.split_gap(self.input.len()),
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spans_cover_the_input() {
+        let input = "//! गणित\n/// योग\nमन्त्र योग() {\n\t// टिप्पणी\n  १ /* खण्ड */ /*! खण्ड */ }  ";
+        let lexed: Vec<_> = LosslessLexer::new(input).collect();
+        let mut end = 0;
+        for (_, span) in &lexed {
+            assert_eq!(span.start, end);
+            end = span.end;
+        }
+        assert_eq!(end, input.len());
+
+        let trivia: Vec<_> = lexed
+            .iter()
+            .filter_map(|(t, span)| match t {
+                LosslessToken::Trivia(Trivia::Whitespace) => None,
+                LosslessToken::Trivia(kind) => Some((*kind, &input[span.clone()])),
+                LosslessToken::Token(_) => None,
+            })
+            .collect();
+        assert_eq!(trivia, [
+            (Trivia::InnerDocComment, "//! गणित"),
+            (Trivia::DocComment, "/// योग"),
+            (Trivia::LineComment, "// टिप्पणी"),
+            (Trivia::BlockComment, "/* खण्ड */"),
+            (Trivia::InnerDocComment, "/*! खण्ड */"),
+        ]);
+    }
+
+    #[test]
+    fn test_tokens_match_the_plain_lexer() {
+        let input = "सूत्र अ = \"{ब}\"; // अन्त";
+        let tokens: Vec<_> = LosslessLexer::new(input).filter_map(|(t, _)| t.into_token()).collect();
+        let plain: Vec<_> = Lexer::new(input).map(|(t, _)| t).collect();
+        assert_eq!(tokens, plain);
+    }
+}
@@ -5,24 +5,8 @@ use std::fmt;
 use std::sync::Arc;
 use std::hash::{Hash, Hasher};
 
-/// Represents a location in the source code with file information
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Span {
-    pub start: usize,
-    pub end: usize,
-    pub file_id: u32,
-}
-
-impl Span {
-    pub fn new(start: usize, end: usize, file_id: u32) -> Self {
-        debug_assert!(start <= end, "Invalid span: start > end");
-        Span { start, end, file_id }
-    }
-    
-    pub fn dummy() -> Self {
-        Span { start: 0, end: 0, file_id: 0 }
-    }
-}
+pub use crate::span::Span;
+pub use vaaktra_lexer::IntegerWidth;
 
 /// A node in the AST with source location information
 /// Uses Arc for efficient cloning of large AST subtrees
@@ -130,6 +114,12 @@ pub enum Item {
     Sutra(SutraDef),
     /// A यन्त्र (yantra) - module/namespace
     Yantra(YantraDef),
+    /// An आनय (ānaya) - import of an item from another yantra
+    Aanaya(AanayaDef),
+    /// A गुणधर्म (guṇadharma) - interface that dharmas implement
+    Gunadharma(GunadharmaDef),
+    /// A कार्यान्वय (kāryānvaya) - implementation of a गुणधर्म for a dharma
+    Karyanvaya(KaryanvayaDef),
     /// A प्रारब्ध (prārabdha) - initialization block
     Praarabdha(Vec<Statement>),
 }
@@ -145,6 +135,9 @@ pub struct DharmaDef {
     pub span: Span,
 }
 
+/// Name under which the आत्मन् (self) receiver is bound inside methods
+pub const SELF_NAME: &str = "आत्मन्";
+
 /// A मन्त्र (mantra) represents a function or method
 #[derive(Debug, Clone, PartialEq)]
 pub struct MantraDef {
@@ -159,6 +152,13 @@ pub struct MantraDef {
     pub span: Span,
 }
 
+impl MantraDef {
+    /// Whether this mantra is a method taking an आत्मन् receiver
+    pub fn has_receiver(&self) -> bool {
+        self.params.first().is_some_and(|p| p.name.as_str() == SELF_NAME)
+    }
+}
+
 /// A सूत्र (sūtra) represents a constant or variable
 #[derive(Debug, Clone, PartialEq)]
 pub struct SutraDef {
@@ -175,6 +175,50 @@ pub struct SutraDef {
 pub struct YantraDef {
     pub name: RcStr,
     pub items: Vec<Item>,
+    /// Declared as `यन्त्र नाम;` with its items loaded from `नाम.vk`
+    pub external: bool,
+    pub span: Span,
+}
+
+/// An आनय (ānaya) brings an item of another yantra into scope
+#[derive(Debug, Clone, PartialEq)]
+pub struct AanayaDef {
+    pub path: Path,
+    pub span: Span,
+}
+
+/// A गुणधर्म (guṇadharma) names methods that dharmas may implement. Used
+/// as a type, it holds a value of any dharma implementing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GunadharmaDef {
+    pub name: RcStr,
+    pub methods: Vec<MethodSig>,
+    pub visibility: Visibility,
+    pub span: Span,
+}
+
+/// A method of a गुणधर्म, declared without a body
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodSig {
+    pub name: RcStr,
+    pub params: Vec<Param>,
+    pub return_type: Type,
+    pub span: Span,
+}
+
+impl MethodSig {
+    /// Whether this method takes an आत्मन् receiver
+    pub fn has_receiver(&self) -> bool {
+        self.params.first().is_some_and(|p| p.name.as_str() == SELF_NAME)
+    }
+}
+
+/// A कार्यान्वय (kāryānvaya) defines the methods of a गुणधर्म for a dharma
+#[derive(Debug, Clone, PartialEq)]
+pub struct KaryanvayaDef {
+    pub dharma: Path,
+    pub gunadharma: Path,
+    pub methods: Vec<MantraDef>,
     pub span: Span,
 }
 
@@ -199,19 +243,24 @@ pub enum Statement {
     Yaavat {
         condition: Box<Expr>,
         body: Box<Statement>,
+        label: Option<LoopLabel>,
     },
     /// For-each loop (प्रत्येक)
     Pratyeka {
         pattern: Pattern,
         iterable: Box<Expr>,
         body: Box<Statement>,
+        label: Option<LoopLabel>,
     },
     /// Return statement (प्रत्याहर)
     Pratyahara(Option<Box<Expr>>),
-    /// Break statement (निर्गम)
-    Nirgama(Option<Box<Expr>>),
-    /// Continue statement (अनुवृत्ति)
-    Anuvrtti,
+    /// Break statement (निर्गम 'label value)
+    Nirgama {
+        label: Option<LoopLabel>,
+        value: Option<Box<Expr>>,
+    },
+    /// Continue statement (अनुवृत्ति 'label)
+    Anuvrtti(Option<LoopLabel>),
     /// Empty statement (शून्य)
     Shunya,
 }
@@ -417,6 +466,40 @@ pub enum Expr {
     Error(Span),
 }
 
+impl Expr {
+    /// Source span covered by this expression
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Literal(_, span)
+            | Expr::Variable(_, span)
+            | Expr::FieldAccess(_, _, span)
+            | Expr::MethodCall(_, _, _, _, span)
+            | Expr::Call(_, _, span)
+            | Expr::Binary(_, _, _, span)
+            | Expr::Unary(_, _, span)
+            | Expr::Assign(_, _, span)
+            | Expr::Block(_, span)
+            | Expr::If(_, _, _, span)
+            | Expr::Loop(_, _, span)
+            | Expr::While(_, _, _, span)
+            | Expr::For(_, _, _, _, span)
+            | Expr::Match(_, _, span)
+            | Expr::Return(_, span)
+            | Expr::Break(_, _, span)
+            | Expr::Continue(_, span)
+            | Expr::Lambda(_, _, span)
+            | Expr::Array(_, span)
+            | Expr::Tuple(_, span)
+            | Expr::Struct(_, _, span)
+            | Expr::Range(_, _, _, span)
+            | Expr::Async(_, span)
+            | Expr::Await(_, span)
+            | Expr::Try(_, span)
+            | Expr::Error(span) => *span,
+        }
+    }
+}
+
 /// Field value in a struct literal
 #[derive(Debug, Clone, PartialEq)]
 pub struct FieldValue {
@@ -487,6 +570,13 @@ pub enum UnaryOp {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
     Int(i64),
+    /// An integer with an explicit width suffix (२५५u8)
+    TypedInt(u128, IntegerWidth),
+    /// A negated sized integer in a pattern (ऋण १२८i8), kept as its
+    /// magnitude; expressions negate with [`UnaryOp::Negate`] instead
+    NegativeTypedInt(u128, IntegerWidth),
+    /// A दशांश (daśāṃśa) fractional number
+    Float(f64),
     Bool(bool),
     String(String),
 }
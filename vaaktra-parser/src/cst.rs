@@ -0,0 +1,398 @@
+//! Concrete syntax tree
+//!
+//! A lossless tree over the tokens and trivia of a source file: printing it
+//! gives back the exact input, comments and spacing included. It only
+//! knows the coarse structure a formatter or refactoring tool needs:
+//! statements (items are statements too), and bracketed groups nested in
+//! them. Each statement owns the trivia before it, so its doc comments
+//! travel with it, and any comment after it on the same line. Inner doc
+//! comments (`//!`) instead stay with the file or brace group they are in.
+//!
+//! The [`ast`](crate::ast) remains the tree for analysis; nothing here
+//! checks that the source is valid Vāktra.
+
+use std::fmt;
+use std::ops::Range;
+
+use vaaktra_lexer::{LosslessLexer, LosslessToken, Token, Trivia};
+
+use crate::span::Span;
+
+/// What a node of the tree holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A whole file
+    Root,
+    /// A statement or item, up to its `;`, `।` or `॥`, or its closing `}`
+    /// and any `॥` after it
+    Statement,
+    /// A bracketed group, delimiters included
+    Group(Delimiter),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Delimiter {
+    Paren,
+    Brace,
+    Bracket,
+}
+
+impl Delimiter {
+    fn opened_by(token: &Token) -> Option<Self> {
+        match token {
+            Token::LParen => Some(Delimiter::Paren),
+            Token::LBrace => Some(Delimiter::Brace),
+            Token::LBracket => Some(Delimiter::Bracket),
+            _ => None,
+        }
+    }
+
+    fn closed_by(token: &Token) -> Option<Self> {
+        match token {
+            Token::RParen => Some(Delimiter::Paren),
+            Token::RBrace => Some(Delimiter::Brace),
+            Token::RBracket => Some(Delimiter::Bracket),
+            _ => None,
+        }
+    }
+}
+
+/// A token or piece of trivia with its source text
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxToken {
+    pub kind: LosslessToken,
+    pub text: String,
+    pub span: Span,
+}
+
+impl SyntaxToken {
+    pub fn is_trivia(&self) -> bool {
+        self.kind.is_trivia()
+    }
+
+    /// A doc comment's text without its markers
+    fn doc_text(&self) -> &str {
+        match self.text.get(..3) {
+            Some("/**" | "/*!") => self.text[3..].trim_end_matches("*/").trim(),
+            _ => self.text[3..].trim(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl SyntaxElement {
+    fn span(&self) -> Span {
+        match self {
+            SyntaxElement::Node(node) => node.span,
+            SyntaxElement::Token(token) => token.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxNode {
+    pub kind: NodeKind,
+    pub children: Vec<SyntaxElement>,
+    pub span: Span,
+}
+
+impl SyntaxNode {
+    /// Build the tree of a source file
+    pub fn parse(source: &str, file_id: u32) -> SyntaxNode {
+        let mut builder = Builder {
+            source,
+            file_id,
+            tokens: LosslessLexer::new(source).collect(),
+            pos: 0,
+        };
+        let children = builder.statements(false);
+        SyntaxNode::new(NodeKind::Root, children, Span::new(0, 0, file_id))
+    }
+
+    fn new(kind: NodeKind, children: Vec<SyntaxElement>, empty: Span) -> Self {
+        let span = match (children.first(), children.last()) {
+            (Some(first), Some(last)) => first.span().to(last.span()),
+            _ => empty,
+        };
+        SyntaxNode { kind, children, span }
+    }
+
+    /// Child nodes of this node
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Every token and piece of trivia under this node, in source order
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken>) {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+
+    /// Text of the doc comments before this node's first token, without
+    /// their `///` or `/** */` markers
+    pub fn doc_comments(&self) -> Vec<&str> {
+        self.tokens()
+            .into_iter()
+            .take_while(|token| token.is_trivia())
+            .filter(|token| token.kind == LosslessToken::Trivia(Trivia::DocComment))
+            .map(SyntaxToken::doc_text)
+            .collect()
+    }
+
+    /// Text of the inner doc comments directly in this node, documenting
+    /// the file for the root and the enclosing item for a brace group
+    pub fn inner_doc_comments(&self) -> Vec<&str> {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                SyntaxElement::Token(token) if token.kind == LosslessToken::Trivia(Trivia::InnerDocComment) => {
+                    Some(token.doc_text())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for token in self.tokens() {
+            f.write_str(&token.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Groups lexed tokens into nodes
+struct Builder<'a> {
+    source: &'a str,
+    file_id: u32,
+    tokens: Vec<(LosslessToken, Range<usize>)>,
+    pos: usize,
+}
+
+impl Builder<'_> {
+    /// The next token that is not trivia
+    fn peek_token(&self) -> Option<&Token> {
+        self.tokens[self.pos..].iter().find_map(|(token, _)| match token {
+            LosslessToken::Token(token) => Some(token),
+            LosslessToken::Trivia(_) => None,
+        })
+    }
+
+    fn peek_is_trivia(&self) -> bool {
+        self.tokens.get(self.pos).is_some_and(|(token, _)| token.is_trivia())
+    }
+
+    fn bump(&mut self) -> SyntaxElement {
+        let (kind, range) = self.tokens[self.pos].clone();
+        self.pos += 1;
+        SyntaxElement::Token(SyntaxToken {
+            kind,
+            text: self.source[range.clone()].to_string(),
+            span: Span::new(range.start, range.end, self.file_id),
+        })
+    }
+
+    /// Span of an empty node at the current position
+    fn here(&self) -> Span {
+        let offset = self.tokens.get(self.pos).map_or(self.source.len(), |(_, range)| range.start);
+        Span::new(offset, offset, self.file_id)
+    }
+
+    /// Statements up to the closing `}` of a brace group, or to the end
+    /// of input at the top level, where stray closers are kept as tokens
+    fn statements(&mut self, in_braces: bool) -> Vec<SyntaxElement> {
+        let mut children = Vec::new();
+        loop {
+            match self.peek_token() {
+                None => break,
+                Some(token) if Delimiter::closed_by(token).is_some() => {
+                    if in_braces {
+                        break;
+                    }
+                    children.push(self.bump());
+                }
+                Some(_) => {
+                    // Inner doc comments belong to the enclosing node
+                    let trivia = self.tokens[self.pos..].iter().take_while(|(token, _)| token.is_trivia());
+                    let inner = trivia
+                        .enumerate()
+                        .filter(|(_, (token, _))| *token == LosslessToken::Trivia(Trivia::InnerDocComment))
+                        .last();
+                    if let Some((last, _)) = inner {
+                        for _ in 0..=last {
+                            children.push(self.bump());
+                        }
+                    }
+                    let empty = self.here();
+                    let statement = self.statement();
+                    children.push(SyntaxElement::Node(SyntaxNode::new(NodeKind::Statement, statement, empty)));
+                }
+            }
+        }
+        // Trivia before the closer or the end of input
+        while self.peek_is_trivia() {
+            children.push(self.bump());
+        }
+        children
+    }
+
+    /// One statement with its leading trivia and same-line trailing comments
+    fn statement(&mut self) -> Vec<SyntaxElement> {
+        let mut children = Vec::new();
+        while self.peek_is_trivia() {
+            children.push(self.bump());
+        }
+        // Items and control flow end with their block rather than a `;`
+        let block_bodied = matches!(
+            self.peek_token(),
+            Some(Token::Class | Token::Fn | Token::Module | Token::Trait | Token::Impl | Token::If | Token::While | Token::ForEach
+                | Token::Pub | Token::Async | Token::Unsafe
+                | Token::Label(_) | Token::LBrace | Token::Match)
+        );
+
+        while let Some((token, _)) = self.tokens.get(self.pos) {
+            let LosslessToken::Token(token) = token else {
+                children.push(self.bump());
+                continue;
+            };
+            if Delimiter::closed_by(token).is_some() {
+                break;
+            }
+            if let Some(delimiter) = Delimiter::opened_by(token) {
+                let empty = self.here();
+                let group = self.group(delimiter);
+                children.push(SyntaxElement::Node(SyntaxNode::new(NodeKind::Group(delimiter), group, empty)));
+                let continues = matches!(self.peek_token(), Some(Token::Else | Token::DoubleDanda));
+                if delimiter == Delimiter::Brace && block_bodied && !continues {
+                    break;
+                }
+                continue;
+            }
+            let end = matches!(token, Token::Semicolon | Token::DoubleDanda);
+            children.push(self.bump());
+            if end {
+                break;
+            }
+        }
+
+        // A trailing comment on the same line belongs to this statement
+        while let Some((LosslessToken::Trivia(kind), range)) = self.tokens.get(self.pos) {
+            let same_line = match kind {
+                Trivia::Whitespace => !self.source[range.clone()].contains('\n'),
+                Trivia::LineComment | Trivia::BlockComment => true,
+                Trivia::DocComment | Trivia::InnerDocComment => false,
+            };
+            if !same_line {
+                break;
+            }
+            children.push(self.bump());
+        }
+        // Keep whitespace between tokens inside the statement, not after it
+        while let Some(SyntaxElement::Token(last)) = children.last() {
+            if last.kind != LosslessToken::Trivia(Trivia::Whitespace) {
+                break;
+            }
+            self.pos -= 1;
+            children.pop();
+        }
+        children
+    }
+
+    /// A bracketed group, from its opener to its closer
+    fn group(&mut self, delimiter: Delimiter) -> Vec<SyntaxElement> {
+        let mut children = vec![self.bump()];
+        if delimiter == Delimiter::Brace {
+            children.extend(self.statements(true));
+        } else {
+            while let Some((token, _)) = self.tokens.get(self.pos) {
+                match token {
+                    LosslessToken::Token(token) if Delimiter::closed_by(token).is_some() => break,
+                    LosslessToken::Token(token) => match Delimiter::opened_by(token) {
+                        Some(inner) => {
+                            let empty = self.here();
+                            let group = self.group(inner);
+                            children.push(SyntaxElement::Node(SyntaxNode::new(NodeKind::Group(inner), group, empty)));
+                        }
+                        None => children.push(self.bump()),
+                    },
+                    LosslessToken::Trivia(_) => children.push(self.bump()),
+                }
+            }
+        }
+        // Any closer ends the group, so a mismatched one is not lost
+        if self.peek_token().is_some_and(|token| Delimiter::closed_by(token).is_some()) {
+            children.push(self.bump());
+        }
+        children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "//! गणित\n\n/// दो सङ्ख्याओं का योग\n/** शुद्ध */\nमन्त्र योग(अ: सङ्ख्या,  ब: सङ्ख्या) -> सङ्ख्या {\n    अ धन ब   // योग\n}\n\nयदि सत्य { १; } अथवा { २; }\nसूत्र क = [१, (२)] ;  /* अन्त */\n";
+
+    #[test]
+    fn test_round_trip_is_exact() {
+        let tree = SyntaxNode::parse(SOURCE, 0);
+        assert_eq!(tree.to_string(), SOURCE);
+        assert_eq!(tree.span.range(), 0..SOURCE.len());
+        for weird in ["", "   ", "}", "(( ", "मन्त्र फ() { अ; ", "\"{अ}\" ) ] ;;"] {
+            assert_eq!(SyntaxNode::parse(weird, 0).to_string(), weird);
+        }
+    }
+
+    #[test]
+    fn test_statements_and_groups() {
+        let tree = SyntaxNode::parse(SOURCE, 0);
+        let statements: Vec<_> = tree.nodes().collect();
+        assert_eq!(statements.len(), 3);
+        assert!(statements.iter().all(|s| s.kind == NodeKind::Statement));
+
+        // The else branch stays with its यदि, and the trailing comment with
+        // its सूत्र
+        assert_eq!(SOURCE[statements[1].span.range()].trim(), "यदि सत्य { १; } अथवा { २; }");
+        assert!(SOURCE[statements[2].span.range()].ends_with("/* अन्त */"));
+
+        let body = statements[0].nodes().find(|n| n.kind == NodeKind::Group(Delimiter::Brace)).unwrap();
+        let inner: Vec<_> = body.nodes().collect();
+        assert_eq!(inner.len(), 1);
+        assert!(inner[0].to_string().ends_with("अ धन ब   // योग"));
+    }
+
+    #[test]
+    fn test_doc_comments_follow_their_item() {
+        let tree = SyntaxNode::parse(SOURCE, 0);
+        let mantra = tree.nodes().next().unwrap();
+        assert_eq!(mantra.doc_comments(), ["दो सङ्ख्याओं का योग", "शुद्ध"]);
+        assert!(tree.nodes().nth(1).unwrap().doc_comments().is_empty());
+
+        // `//!` documents the file, and inside braces the enclosing item
+        assert_eq!(tree.inner_doc_comments(), ["गणित"]);
+        let tree = SyntaxNode::parse("मन्त्र फ() {\n    //! फल\n    १\n}", 0);
+        let body = tree.nodes().next().unwrap().nodes().find(|n| n.kind == NodeKind::Group(Delimiter::Brace)).unwrap();
+        assert_eq!(body.inner_doc_comments(), ["फल"]);
+        assert!(body.nodes().next().unwrap().doc_comments().is_empty());
+    }
+}
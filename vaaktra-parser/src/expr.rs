@@ -0,0 +1,422 @@
+//! Expression parsing for Vāktra
+//!
+//! Binary expressions are parsed by precedence climbing. Operators bind
+//! from loosest to tightest as follows:
+//!
+//! | Level | Operators                                   | Associativity |
+//! |-------|---------------------------------------------|---------------|
+//! | 1     | `=` (assignment)                            | right         |
+//! | 2     | `वा` (or)                                   | left          |
+//! | 3     | `च` (and)                                   | left          |
+//! | 4     | `समान` `असमान`                              | left          |
+//! | 5     | `लघुत्तर` `समानता` `महत्तर` `महत्तर व समान` | left          |
+//! | 6     | `धन` `ऋण`                                   | left          |
+//! | 7     | `गुण` `भाग` `शेष`                           | left          |
+//! | 8     | prefix `ऋण` `न`                             | right         |
+//! | 9     | call `f(..)`, field `x.y`, method `x.f(..)` | left          |
+//! |       | await `x.प्रतीक्षा`                          |               |
+//!
+//! An interpolated string `"नमस्ते {नाम}!"` desugars to the concatenation
+//! `"नमस्ते " धन नाम धन "!"`. The chain always starts with a string literal,
+//! so it concatenates even when the first hole holds a number.
+
+use vaaktra_lexer::{IntegerWidth, LexError, StringPart, Token};
+
+use crate::ast::{self, BinaryOp, Expr, Literal, Span, UnaryOp};
+use crate::types::single_segment_path;
+use crate::{ParseError, ParseResult, Parser};
+
+/// Binding power of assignment, the loosest binary level
+const ASSIGN_PREC: u8 = 1;
+
+/// Binding power of the prefix operators
+const PREFIX_PREC: u8 = 8;
+
+/// Map a token to its binary operator and precedence. Every operator in
+/// this table is left-associative; assignment is handled separately.
+fn binary_op(token: &Token) -> Option<(BinaryOp, u8)> {
+    let info = match token {
+        Token::Or | Token::DoublePipe => (BinaryOp::Or, 2),
+        Token::And => (BinaryOp::And, 3),
+        Token::Eq => (BinaryOp::Equal, 4),
+        Token::Neq => (BinaryOp::NotEqual, 4),
+        Token::Lt => (BinaryOp::Less, 5),
+        Token::Le => (BinaryOp::LessEqual, 5),
+        Token::Gt => (BinaryOp::Greater, 5),
+        Token::Ge => (BinaryOp::GreaterEqual, 5),
+        Token::Plus => (BinaryOp::Add, 6),
+        Token::Minus => (BinaryOp::Subtract, 6),
+        Token::Star => (BinaryOp::Multiply, 7),
+        Token::Slash => (BinaryOp::Divide, 7),
+        Token::Percent => (BinaryOp::Modulo, 7),
+        _ => return None,
+    };
+    Some(info)
+}
+
+/// The error for a lexed integer one past the largest value of its type.
+/// Such a literal is only valid right after `ऋण`, giving the type's
+/// minimum: `ऋण 128i8`.
+pub(crate) fn negative_only(token: &Token) -> Option<LexError> {
+    match token {
+        Token::Number(n) if *n < 0 => Some(LexError::IntegerOverflow {
+            literal: n.unsigned_abs().to_string(),
+            width: IntegerWidth::I64,
+        }),
+        Token::TypedNumber((value, width)) if *value > width.max_literal() => Some(LexError::IntegerOverflow {
+            literal: format!("{}{}", value, width),
+            width: *width,
+        }),
+        _ => None,
+    }
+}
+
+/// Whether an expression ends in a `}` and so needs no `;` as a statement
+pub(crate) fn is_block_like(expr: &Expr) -> bool {
+    matches!(expr, Expr::Match(..) | Expr::Block(..) | Expr::Async(..))
+}
+
+/// Whether a token can begin a primary expression
+fn starts_primary(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Number(_)
+            | Token::TypedNumber(_)
+            | Token::Float(_)
+            | Token::True
+            | Token::False
+            | Token::StringLit(_)
+            | Token::InterpolatedString(_)
+            | Token::Ident(_)
+            | Token::SelfValue
+            | Token::LParen
+            | Token::LBracket
+            | Token::Match
+            | Token::Async
+            | Token::Pipe
+            | Token::DoublePipe
+    )
+}
+
+impl<I> Parser<I>
+where
+    I: Iterator<Item = (Token, std::ops::Range<usize>)>,
+{
+    /// Parse a complete expression, including assignment
+    pub(crate) fn parse_expression(&mut self) -> ParseResult<Expr> {
+        self.parse_expr_prec(ASSIGN_PREC)
+    }
+
+    /// Precedence climbing: parse an expression whose binary operators all
+    /// bind at least as tightly as `min_prec`
+    fn parse_expr_prec(&mut self, min_prec: u8) -> ParseResult<Expr> {
+        let mut lhs = self.parse_unary()?;
+
+        loop {
+            if min_prec <= ASSIGN_PREC && self.peek() == Some(&Token::Equals) {
+                self.next();
+                // Assignment is right-associative: `अ = ब = ३` is `अ = (ब = ३)`
+                let rhs = self.parse_expr_prec(ASSIGN_PREC)?;
+                let span = lhs.span().to(rhs.span());
+                lhs = Expr::Assign(Box::new(lhs), Box::new(rhs), span);
+                continue;
+            }
+
+            let (op, prec) = match self.peek().and_then(binary_op) {
+                Some(info) if info.1 >= min_prec => info,
+                _ => break,
+            };
+            self.next();
+
+            // Left-associative: the right operand must bind strictly tighter
+            let rhs = self.parse_expr_prec(prec + 1)?;
+            let span = lhs.span().to(rhs.span());
+            lhs = Expr::Binary(Box::new(lhs), op, Box::new(rhs), span);
+        }
+
+        Ok(lhs)
+    }
+
+    /// Parse prefix `ऋण` (negation) and `न` (logical not)
+    fn parse_unary(&mut self) -> ParseResult<Expr> {
+        let op = match self.peek() {
+            Some(Token::Minus) => UnaryOp::Negate,
+            Some(Token::Not) => UnaryOp::Not,
+            _ => return self.parse_postfix(),
+        };
+        let start = self.next_start();
+        self.next();
+
+        // A signed minimum is negated as written. A सङ्ख्या folds into one
+        // literal; a typed literal has no sign, so it stays negated.
+        if matches!(op, UnaryOp::Negate) && self.peek().and_then(negative_only).is_some() {
+            let literal_start = self.next_start();
+            let literal = match self.next() {
+                Some(Token::Number(n)) => return Ok(Expr::Literal(Literal::Int(n), self.span_from(start))),
+                Some(Token::TypedNumber((value, width))) => Literal::TypedInt(value, width),
+                _ => unreachable!("negative_only accepts only integers"),
+            };
+            let operand = Expr::Literal(literal, self.span_from(literal_start));
+            return Ok(Expr::Unary(op, Box::new(operand), self.span_from(start)));
+        }
+
+        let operand = self.parse_expr_prec(PREFIX_PREC)?;
+        let span = self.span_from(start);
+        Ok(Expr::Unary(op, Box::new(operand), span))
+    }
+
+    /// Parse calls, field accesses, method calls and awaits following a
+    /// primary
+    fn parse_postfix(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.parse_primary()?;
+        let start = expr.span().start;
+
+        loop {
+            match self.peek() {
+                Some(Token::LParen) => {
+                    self.next();
+                    let args = self.parse_call_args()?;
+                    expr = Expr::Call(Box::new(expr), args, self.span_from(start));
+                }
+                Some(Token::Dot) => {
+                    self.next();
+                    if self.matches(Token::Await) {
+                        expr = Expr::Await(Box::new(expr), self.span_from(start));
+                        continue;
+                    }
+                    let name = self.parse_identifier()?;
+                    if self.matches(Token::LParen) {
+                        let args = self.parse_call_args()?;
+                        expr = Expr::MethodCall(
+                            Box::new(expr),
+                            name,
+                            Vec::new(),
+                            args,
+                            self.span_from(start),
+                        );
+                    } else {
+                        expr = Expr::FieldAccess(Box::new(expr), name, self.span_from(start));
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        Ok(expr)
+    }
+
+    /// Parse a comma-separated argument list; the `(` is already consumed
+    fn parse_call_args(&mut self) -> ParseResult<Vec<Expr>> {
+        self.parse_expr_list(Token::RParen)
+    }
+
+    /// Parse comma-separated expressions up to and including `close`,
+    /// allowing a trailing comma
+    fn parse_expr_list(&mut self, close: Token) -> ParseResult<Vec<Expr>> {
+        let mut exprs = Vec::new();
+        while !self.matches(close.clone()) {
+            exprs.push(self.parse_expression()?);
+            if !self.matches(Token::Comma) {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(exprs)
+    }
+
+    /// Parse literals, variables, `आत्मन्`, parenthesized expressions,
+    /// tuples, lists, `विकल्प` expressions, `असमकाल` blocks and lambdas
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
+        let start = self.next_start();
+        if !self.peek().is_some_and(starts_primary) {
+            // Leave the token for error recovery to synchronize on
+            return Err(self.unexpected("expression"));
+        }
+        if let Some(error) = self.peek().and_then(negative_only) {
+            self.next();
+            return Err(ParseError::InvalidToken { error, span: self.token_span() });
+        }
+
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Literal(Literal::Int(n), self.span_from(start))),
+            Some(Token::TypedNumber((value, width))) => {
+                Ok(Expr::Literal(Literal::TypedInt(value, width), self.span_from(start)))
+            }
+            Some(Token::Float(x)) => Ok(Expr::Literal(Literal::Float(x), self.span_from(start))),
+            Some(Token::True) => Ok(Expr::Literal(Literal::Bool(true), self.span_from(start))),
+            Some(Token::False) => Ok(Expr::Literal(Literal::Bool(false), self.span_from(start))),
+            Some(Token::StringLit(s)) => Ok(Expr::Literal(Literal::String(s), self.span_from(start))),
+            Some(Token::InterpolatedString(parts)) => self.desugar_interpolation(parts, start),
+            Some(Token::Ident(name)) => {
+                let path = self.parse_path_rest(&name, start)?;
+                Ok(Expr::Variable(path, self.span_from(start)))
+            }
+            Some(Token::SelfValue) => {
+                let span = self.span_from(start);
+                Ok(Expr::Variable(single_segment_path(ast::SELF_NAME, span), span))
+            }
+            Some(Token::LParen) => {
+                if self.matches(Token::RParen) {
+                    return Ok(Expr::Tuple(Vec::new(), self.span_from(start)));
+                }
+                let first = self.parse_expression()?;
+                if self.matches(Token::RParen) {
+                    // Plain grouping keeps the inner expression's node
+                    return Ok(first);
+                }
+                self.expect(Token::Comma)?;
+                let mut elems = vec![first];
+                elems.extend(self.parse_expr_list(Token::RParen)?);
+                Ok(Expr::Tuple(elems, self.span_from(start)))
+            }
+            Some(Token::LBracket) => {
+                let elems = self.parse_expr_list(Token::RBracket)?;
+                Ok(Expr::Array(elems, self.span_from(start)))
+            }
+            Some(Token::Match) => self.parse_match_rest(start),
+            Some(Token::Async) => {
+                let body = self.parse_block()?;
+                Ok(Expr::Async(Box::new(body), self.span_from(start)))
+            }
+            Some(Token::Pipe) => self.parse_lambda_rest(start),
+            // `||` is also the Or operator, but `वा` never opens a lambda
+            Some(Token::DoublePipe) => self.parse_lambda_body(start, Vec::new()),
+            found => {
+                let span = self.token_span();
+                Err(ParseError::UnexpectedToken {
+                    expected: "expression".to_string(),
+                    found,
+                    span,
+                })
+            }
+        }
+    }
+
+    /// Turn the parts of an interpolated string into a `धन` chain
+    fn desugar_interpolation(&mut self, parts: Vec<StringPart>, start: usize) -> ParseResult<Expr> {
+        let span = self.span_from(start);
+        // Text runs between the opening quote, the holes' braces and the
+        // closing quote; `cursor` is where the next run starts
+        let mut cursor = span.start + 1;
+        let mut pieces = Vec::with_capacity(parts.len() + 1);
+        if !matches!(parts.first(), Some(StringPart::Text(_))) {
+            pieces.push(Expr::Literal(Literal::String(String::new()), Span::new(cursor, cursor, self.file_id)));
+        }
+        let mut parts = parts.into_iter().peekable();
+        while let Some(part) = parts.next() {
+            pieces.push(match part {
+                StringPart::Text(text) => {
+                    let end = match parts.peek() {
+                        Some(StringPart::Code { offset, .. }) => offset - 1,
+                        _ => span.end - 1,
+                    };
+                    Expr::Literal(Literal::String(text), Span::new(cursor, end, self.file_id))
+                }
+                StringPart::Code { source, offset, tokens } => {
+                    cursor = offset + source.len() + 1;
+                    self.parse_interpolated_expr(tokens)?
+                }
+            });
+        }
+
+        let mut pieces = pieces.into_iter();
+        let first = pieces.next().expect("interpolation has at least one piece");
+        Ok(pieces.fold(first, |acc, piece| {
+            let span = acc.span().to(piece.span());
+            Expr::Binary(Box::new(acc), BinaryOp::Add, Box::new(piece), span)
+        }))
+    }
+
+    /// Parse the expression in a `{}` hole from its tokens, already lexed
+    /// with spans into the source
+    fn parse_interpolated_expr(&self, tokens: Vec<(Token, std::ops::Range<usize>)>) -> ParseResult<Expr> {
+        let mut parser = Parser::with_file_id(tokens.into_iter(), self.file_id);
+        let expr = parser.parse_expression()?;
+        if parser.peek().is_some() {
+            return Err(parser.unexpected("end of interpolated expression"));
+        }
+        Ok(expr)
+    }
+
+    /// Parse `scrutinee { pattern [यदि guard] => body, ... }`; the `विकल्प`
+    /// keyword is already consumed. A comma may be omitted after a block
+    /// body and after the last arm.
+    fn parse_match_rest(&mut self, start: usize) -> ParseResult<Expr> {
+        let scrutinee = self.parse_expression()?;
+        self.expect(Token::LBrace)?;
+
+        let mut arms = Vec::new();
+        while !self.matches(Token::RBrace) {
+            let arm_start = self.next_start();
+            let pattern = self.parse_pattern()?;
+            let guard = if self.matches(Token::If) {
+                Some(ast::Guard::If(Box::new(self.parse_expression()?)))
+            } else {
+                None
+            };
+            self.expect(Token::FatArrow)?;
+
+            let block_body = self.peek() == Some(&Token::LBrace);
+            let body = if block_body {
+                let block = self.parse_block()?;
+                let span = block.span;
+                Expr::Block(Box::new(block), span)
+            } else {
+                self.parse_expression()?
+            };
+            arms.push(ast::Arm {
+                pattern,
+                guard,
+                body,
+                span: self.span_from(arm_start),
+            });
+
+            if !self.matches(Token::Comma) && !block_body {
+                self.expect(Token::RBrace)?;
+                break;
+            }
+        }
+
+        Ok(Expr::Match(Box::new(scrutinee), arms, self.span_from(start)))
+    }
+
+    /// Parse `अ, ब: सङ्ख्या| body`; the opening `|` is already consumed.
+    /// Parameters without a type annotation get `Type::Infer`, and the body
+    /// is either a block or extends as far right as an expression can.
+    fn parse_lambda_rest(&mut self, start: usize) -> ParseResult<Expr> {
+        let mut params = Vec::new();
+        while !self.matches(Token::Pipe) {
+            let param_start = self.next_start();
+            let name = self.parse_identifier()?;
+            let ty = if self.matches(Token::Colon) {
+                self.parse_type()?
+            } else {
+                ast::Type::Infer(self.span_from(param_start))
+            };
+            params.push(ast::Param {
+                name,
+                ty,
+                default_value: None,
+                span: self.span_from(param_start),
+            });
+            if !self.matches(Token::Comma) {
+                self.expect(Token::Pipe)?;
+                break;
+            }
+        }
+        self.parse_lambda_body(start, params)
+    }
+
+    /// Parse a lambda body after its parameter list
+    fn parse_lambda_body(&mut self, start: usize, params: Vec<ast::Param>) -> ParseResult<Expr> {
+        let body = if self.peek() == Some(&Token::LBrace) {
+            let block = self.parse_block()?;
+            let span = block.span;
+            Expr::Block(Box::new(block), span)
+        } else {
+            self.parse_expression()?
+        };
+
+        Ok(Expr::Lambda(params, Box::new(body), self.span_from(start)))
+    }
+}
@@ -0,0 +1,128 @@
+//! Source formatting
+//!
+//! Rewrites source through its [concrete syntax tree](crate::cst), so
+//! everything a setting does not touch is kept byte for byte.
+
+use vaaktra_lexer::{LosslessToken, Token, Trivia};
+
+use crate::cst::{Delimiter, NodeKind, SyntaxElement, SyntaxNode, SyntaxToken};
+
+/// How statements are terminated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TerminatorStyle {
+    /// Leave `;`, `।` and `॥` as written
+    #[default]
+    Preserve,
+    /// `;` everywhere; a `॥` after another terminator or a block is dropped
+    Semicolon,
+    /// `।` for every `;`; `॥` is kept
+    Danda,
+}
+
+/// Formatter settings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub terminators: TerminatorStyle,
+}
+
+/// Format `source` according to `options`
+pub fn format_source(source: &str, options: &FormatOptions) -> String {
+    let tree = SyntaxNode::parse(source, 0);
+    let mut edits = Vec::new();
+    collect_terminator_edits(&tree, options.terminators, &mut edits);
+
+    let mut formatted = String::with_capacity(source.len());
+    let mut pos = 0;
+    for (range, replacement) in edits {
+        formatted.push_str(&source[pos..range.start]);
+        formatted.push_str(replacement);
+        pos = range.end;
+    }
+    formatted.push_str(&source[pos..]);
+    formatted
+}
+
+/// Replacements for the terminators of every statement under `node`, in
+/// source order
+fn collect_terminator_edits(
+    node: &SyntaxNode,
+    style: TerminatorStyle,
+    edits: &mut Vec<(std::ops::Range<usize>, &'static str)>,
+) {
+    let is_statement = node.kind == NodeKind::Statement;
+    // Whether the last significant child so far ends the statement
+    let mut terminated = false;
+    let mut pending_space: Option<&SyntaxToken> = None;
+
+    for child in &node.children {
+        let token = match child {
+            SyntaxElement::Node(inner) => {
+                collect_terminator_edits(inner, style, edits);
+                terminated = inner.kind == NodeKind::Group(Delimiter::Brace);
+                pending_space = None;
+                continue;
+            }
+            SyntaxElement::Token(token) => token,
+        };
+        match &token.kind {
+            LosslessToken::Trivia(Trivia::Whitespace) if !token.text.contains('\n') => {
+                pending_space = Some(token);
+                continue;
+            }
+            LosslessToken::Trivia(_) => {}
+            LosslessToken::Token(Token::Semicolon) if is_statement => {
+                match (style, token.text.as_str()) {
+                    (TerminatorStyle::Semicolon, "।") => edits.push((token.span.range(), ";")),
+                    (TerminatorStyle::Danda, ";") => edits.push((token.span.range(), "।")),
+                    _ => {}
+                }
+                terminated = true;
+            }
+            LosslessToken::Token(Token::DoubleDanda) if is_statement && style == TerminatorStyle::Semicolon => {
+                // `;` hugs its statement, and a `॥` after a `;` or a block
+                // is redundant; either way the space before it goes
+                let start = pending_space.map_or(token.span.start, |space| space.span.start);
+                edits.push((start..token.span.end, if terminated { "" } else { ";" }));
+                terminated = true;
+            }
+            LosslessToken::Token(_) => terminated = false,
+        }
+        pending_space = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str, terminators: TerminatorStyle) -> String {
+        format_source(source, &FormatOptions { terminators })
+    }
+
+    const SOURCE: &str = "सूत्र अ: [सङ्ख्या; ३] = क।\nमन्त्र फ() {\n    अ;  // अन्त\n} ॥\nसूत्र ब = १ ॥\n";
+
+    #[test]
+    fn test_semicolon_style() {
+        assert_eq!(
+            format(SOURCE, TerminatorStyle::Semicolon),
+            "सूत्र अ: [सङ्ख्या; ३] = क;\nमन्त्र फ() {\n    अ;  // अन्त\n}\nसूत्र ब = १;\n"
+        );
+    }
+
+    #[test]
+    fn test_danda_style() {
+        assert_eq!(
+            format(SOURCE, TerminatorStyle::Danda),
+            "सूत्र अ: [सङ्ख्या; ३] = क।\nमन्त्र फ() {\n    अ।  // अन्त\n} ॥\nसूत्र ब = १ ॥\n"
+        );
+    }
+
+    #[test]
+    fn test_preserve_and_idempotence() {
+        assert_eq!(format(SOURCE, TerminatorStyle::Preserve), SOURCE);
+        for style in [TerminatorStyle::Semicolon, TerminatorStyle::Danda] {
+            let once = format(SOURCE, style);
+            assert_eq!(format(&once, style), once);
+        }
+    }
+}
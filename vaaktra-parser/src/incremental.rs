@@ -0,0 +1,667 @@
+//! Incremental parsing for editors
+//!
+//! [`IncrementalParse`] keeps a file's tokens and program together with
+//! the tokens each top-level item was parsed from. An edit re-lexes only
+//! around the change (see [`vaaktra_lexer::relex`]) and re-parses from the
+//! first item whose tokens changed until the parser reaches the start of
+//! an old item past the change again. Items on either side are kept, those
+//! after the edit with their spans moved. The program and errors are the
+//! same as parsing the new text from scratch.
+
+use std::ops::Range;
+
+use vaaktra_lexer::{relex, Lexer, TextEdit, Token};
+
+use crate::ast::{
+    AanayaDef, Arm, Block, DharmaDef, Expr, FieldDef, FieldPattern, FieldValue, GenericArg, GenericArgs,
+    GunadharmaDef, Guard, Item, KaryanvayaDef, LoopLabel, MantraDef, MethodSig, Param, Path, Pattern, Program,
+    Statement, SutraDef, Type, TypeBound, TypeParam, YantraDef,
+};
+use crate::{ParseError, Parser, Span};
+
+/// How many tokens past the end of an item the parser may look at while
+/// parsing it
+const LOOKAHEAD: usize = 2;
+
+/// A parsed source file that can be updated by text edits
+pub struct IncrementalParse {
+    source: String,
+    file_id: u32,
+    tokens: Vec<(Token, Range<usize>)>,
+    program: Program,
+    /// For each item of `program`, the indices in `tokens` it was parsed
+    /// from and the errors found in it
+    parsed: Vec<ParsedItem>,
+}
+
+struct ParsedItem {
+    tokens: Range<usize>,
+    errors: Vec<ParseError>,
+}
+
+impl IncrementalParse {
+    /// Lex and parse `source` from scratch
+    pub fn new(source: impl Into<String>, file_id: u32) -> Self {
+        let source = source.into();
+        let tokens: Vec<_> = Lexer::new(&source).collect();
+        let (items, parsed) = parse_items(&tokens, 0, file_id, |_| false);
+        let mut parse = IncrementalParse {
+            source,
+            file_id,
+            tokens,
+            program: Program { items, span: Span::new(0, 0, file_id) },
+            parsed,
+        };
+        parse.update_program_span();
+        parse
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn tokens(&self) -> &[(Token, Range<usize>)] {
+        &self.tokens
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Every error in the file, in source order
+    pub fn errors(&self) -> impl Iterator<Item = &ParseError> {
+        self.parsed.iter().flat_map(|parsed| &parsed.errors)
+    }
+
+    /// Apply `edit` to the source and bring the tokens and program up to
+    /// date. Returns the indices in `program().items` of the items that
+    /// were parsed again; all others were reused.
+    pub fn edit(&mut self, edit: &TextEdit) -> Range<usize> {
+        self.source = edit.apply(&self.source);
+        let relexed = relex(&self.tokens, &self.source, edit);
+        let moved_by = relexed.tokens.len() as isize - self.tokens.len() as isize;
+        self.tokens = relexed.tokens;
+
+        // Items before the first one whose tokens, or the tokens just after
+        // it, were replaced parse as before
+        let first = self
+            .parsed
+            .iter()
+            .position(|parsed| parsed.tokens.end + LOOKAHEAD > relexed.replaced.start)
+            .unwrap_or(self.parsed.len());
+        // Items cover the tokens without gaps, so this is only past the
+        // end when there are none
+        let restart = self.parsed.get(first).map_or(0, |parsed| parsed.tokens.start);
+
+        // Parse until reaching the start of an old item after the change
+        let mut candidate = first;
+        let mut resumed = None;
+        let (items, parsed) = parse_items(&self.tokens, restart, self.file_id, |at| {
+            if at < relexed.relexed.end {
+                return false;
+            }
+            while let Some(old) = self.parsed.get(candidate) {
+                let moved = old.tokens.start.checked_add_signed(moved_by);
+                if old.tokens.start >= relexed.replaced.end && moved >= Some(at) {
+                    if moved == Some(at) {
+                        resumed = Some(candidate);
+                    }
+                    return resumed.is_some();
+                }
+                candidate += 1;
+            }
+            false
+        });
+
+        // Keep the items after the change, moved to their new place
+        let reparsed = first..first + items.len();
+        let delta = edit.delta();
+        let old_items = self.program.items.split_off(first).into_iter().zip(self.parsed.split_off(first));
+        let (mut kept_items, mut kept_parsed): (Vec<_>, Vec<_>) = old_items
+            .skip(resumed.map_or(usize::MAX, |resumed| resumed - first))
+            .map(|(mut item, mut parsed)| {
+                item.shift_spans(delta);
+                parsed.errors.shift_spans(delta);
+                parsed.tokens = move_range(parsed.tokens, moved_by);
+                (item, parsed)
+            })
+            .unzip();
+        self.program.items.extend(items);
+        self.program.items.append(&mut kept_items);
+        self.parsed.extend(parsed);
+        self.parsed.append(&mut kept_parsed);
+        self.update_program_span();
+        reparsed
+    }
+
+    /// The program spans its tokens, as it does when parsed from scratch
+    fn update_program_span(&mut self) {
+        let start = self.tokens.first().map_or(0, |(_, span)| span.start);
+        let end = self.tokens.last().map_or(start, |(_, span)| span.end);
+        self.program.span = Span::new(start, end, self.file_id);
+    }
+}
+
+/// Parse items from token `start` on, stopping early before a token index
+/// for which `resume` holds
+fn parse_items(
+    tokens: &[(Token, Range<usize>)],
+    start: usize,
+    file_id: u32,
+    mut resume: impl FnMut(usize) -> bool,
+) -> (Vec<Item>, Vec<ParsedItem>) {
+    let mut parser = Parser::with_file_id(tokens[start..].iter().cloned(), file_id);
+    let mut items = Vec::new();
+    let mut parsed = Vec::new();
+    loop {
+        let at = start + parser.consumed;
+        if parser.peek().is_none() || resume(at) {
+            break;
+        }
+        items.push(parser.parse_item());
+        parsed.push(ParsedItem {
+            tokens: at..start + parser.consumed,
+            errors: std::mem::take(&mut parser.errors),
+        });
+    }
+    (items, parsed)
+}
+
+fn move_range(range: Range<usize>, by: isize) -> Range<usize> {
+    let move_by = |index: usize| index.checked_add_signed(by).expect("range moved before the start");
+    move_by(range.start)..move_by(range.end)
+}
+
+/// Moving every span in a tree by the same number of bytes
+trait ShiftSpans {
+    fn shift_spans(&mut self, delta: isize);
+}
+
+impl ShiftSpans for Span {
+    fn shift_spans(&mut self, delta: isize) {
+        let moved = move_range(self.range(), delta);
+        *self = Span::new(moved.start, moved.end, self.file_id);
+    }
+}
+
+impl<T: ShiftSpans> ShiftSpans for Box<T> {
+    fn shift_spans(&mut self, delta: isize) {
+        (**self).shift_spans(delta);
+    }
+}
+
+impl<T: ShiftSpans> ShiftSpans for Option<T> {
+    fn shift_spans(&mut self, delta: isize) {
+        if let Some(inner) = self {
+            inner.shift_spans(delta);
+        }
+    }
+}
+
+impl<T: ShiftSpans> ShiftSpans for Vec<T> {
+    fn shift_spans(&mut self, delta: isize) {
+        self.iter_mut().for_each(|inner| inner.shift_spans(delta));
+    }
+}
+
+impl ShiftSpans for Item {
+    fn shift_spans(&mut self, delta: isize) {
+        match self {
+            Item::Dharma(dharma) => dharma.shift_spans(delta),
+            Item::Mantra(mantra) => mantra.shift_spans(delta),
+            Item::Sutra(sutra) => sutra.shift_spans(delta),
+            Item::Yantra(yantra) => yantra.shift_spans(delta),
+            Item::Aanaya(aanaya) => aanaya.shift_spans(delta),
+            Item::Gunadharma(gunadharma) => gunadharma.shift_spans(delta),
+            Item::Karyanvaya(karyanvaya) => karyanvaya.shift_spans(delta),
+            Item::Praarabdha(stmts) => stmts.shift_spans(delta),
+        }
+    }
+}
+
+impl ShiftSpans for DharmaDef {
+    fn shift_spans(&mut self, delta: isize) {
+        self.type_params.shift_spans(delta);
+        self.fields.shift_spans(delta);
+        self.methods.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for MantraDef {
+    fn shift_spans(&mut self, delta: isize) {
+        self.type_params.shift_spans(delta);
+        self.params.shift_spans(delta);
+        self.return_type.shift_spans(delta);
+        self.body.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for SutraDef {
+    fn shift_spans(&mut self, delta: isize) {
+        self.pattern.shift_spans(delta);
+        self.type_annotation.shift_spans(delta);
+        self.value.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for YantraDef {
+    fn shift_spans(&mut self, delta: isize) {
+        self.items.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for AanayaDef {
+    fn shift_spans(&mut self, delta: isize) {
+        self.path.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for GunadharmaDef {
+    fn shift_spans(&mut self, delta: isize) {
+        self.methods.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for MethodSig {
+    fn shift_spans(&mut self, delta: isize) {
+        self.params.shift_spans(delta);
+        self.return_type.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for KaryanvayaDef {
+    fn shift_spans(&mut self, delta: isize) {
+        self.dharma.shift_spans(delta);
+        self.gunadharma.shift_spans(delta);
+        self.methods.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for FieldDef {
+    fn shift_spans(&mut self, delta: isize) {
+        self.ty.shift_spans(delta);
+        self.default_value.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for Param {
+    fn shift_spans(&mut self, delta: isize) {
+        self.ty.shift_spans(delta);
+        self.default_value.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for TypeParam {
+    fn shift_spans(&mut self, delta: isize) {
+        self.bounds.shift_spans(delta);
+        self.default.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for TypeBound {
+    fn shift_spans(&mut self, delta: isize) {
+        self.bound.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for Statement {
+    fn shift_spans(&mut self, delta: isize) {
+        match self {
+            Statement::Item(item) => item.shift_spans(delta),
+            Statement::Sutra(sutra) => sutra.shift_spans(delta),
+            Statement::Expr(expr) => expr.shift_spans(delta),
+            Statement::Block(block) => block.shift_spans(delta),
+            Statement::Yadi { condition, then_branch, else_branch } => {
+                condition.shift_spans(delta);
+                then_branch.shift_spans(delta);
+                else_branch.shift_spans(delta);
+            }
+            Statement::Yaavat { condition, body, label } => {
+                condition.shift_spans(delta);
+                body.shift_spans(delta);
+                label.shift_spans(delta);
+            }
+            Statement::Pratyeka { pattern, iterable, body, label } => {
+                pattern.shift_spans(delta);
+                iterable.shift_spans(delta);
+                body.shift_spans(delta);
+                label.shift_spans(delta);
+            }
+            Statement::Pratyahara(value) => value.shift_spans(delta),
+            Statement::Nirgama { label, value } => {
+                label.shift_spans(delta);
+                value.shift_spans(delta);
+            }
+            Statement::Anuvrtti(label) => label.shift_spans(delta),
+            Statement::Shunya => {}
+        }
+    }
+}
+
+impl ShiftSpans for Block {
+    fn shift_spans(&mut self, delta: isize) {
+        self.stmts.shift_spans(delta);
+        self.expr.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for LoopLabel {
+    fn shift_spans(&mut self, delta: isize) {
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for Pattern {
+    fn shift_spans(&mut self, delta: isize) {
+        match self {
+            Pattern::Any(span) => span.shift_spans(delta),
+            Pattern::Literal(_) => {}
+            Pattern::Bind { subpattern, span, .. } => {
+                subpattern.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Pattern::Tuple(elems, span) => {
+                elems.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Pattern::Struct { path, fields, span, .. } => {
+                path.shift_spans(delta);
+                fields.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+        }
+    }
+}
+
+impl ShiftSpans for FieldPattern {
+    fn shift_spans(&mut self, delta: isize) {
+        self.pattern.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for Type {
+    fn shift_spans(&mut self, delta: isize) {
+        match self {
+            Type::Named(path, args) => {
+                path.shift_spans(delta);
+                args.shift_spans(delta);
+            }
+            Type::Tuple(elems, span) => {
+                elems.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Type::Function(params, ret, span) => {
+                params.shift_spans(delta);
+                ret.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Type::Reference(inner, _, span) | Type::Slice(inner, span) => {
+                inner.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Type::Array(elem, len, span) => {
+                elem.shift_spans(delta);
+                len.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Type::Never(span) | Type::Infer(span) => span.shift_spans(delta),
+            Type::Error => {}
+        }
+    }
+}
+
+impl ShiftSpans for Path {
+    fn shift_spans(&mut self, delta: isize) {
+        for segment in &mut self.segments {
+            segment.args.shift_spans(delta);
+        }
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for GenericArgs {
+    fn shift_spans(&mut self, delta: isize) {
+        self.args.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for GenericArg {
+    fn shift_spans(&mut self, delta: isize) {
+        match self {
+            GenericArg::Type(ty) => ty.shift_spans(delta),
+            GenericArg::Lifetime(_, span) => span.shift_spans(delta),
+            GenericArg::Const(expr) => expr.shift_spans(delta),
+        }
+    }
+}
+
+impl ShiftSpans for Expr {
+    fn shift_spans(&mut self, delta: isize) {
+        match self {
+            Expr::Literal(_, span) | Expr::Error(span) => span.shift_spans(delta),
+            Expr::Variable(path, span) => {
+                path.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::FieldAccess(object, _, span) | Expr::Unary(_, object, span) | Expr::Await(object, span) => {
+                object.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::MethodCall(receiver, _, type_args, args, span) => {
+                receiver.shift_spans(delta);
+                type_args.shift_spans(delta);
+                args.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Call(callee, args, span) => {
+                callee.shift_spans(delta);
+                args.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Binary(left, _, right, span) | Expr::Assign(left, right, span) => {
+                left.shift_spans(delta);
+                right.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Block(block, span) | Expr::Async(block, span) | Expr::Try(block, span) => {
+                block.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::If(condition, then_block, else_expr, span) => {
+                condition.shift_spans(delta);
+                then_block.shift_spans(delta);
+                else_expr.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Loop(body, label, span) => {
+                body.shift_spans(delta);
+                label.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::While(condition, body, label, span) => {
+                condition.shift_spans(delta);
+                body.shift_spans(delta);
+                label.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::For(pattern, iterable, body, label, span) => {
+                pattern.shift_spans(delta);
+                iterable.shift_spans(delta);
+                body.shift_spans(delta);
+                label.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Match(scrutinee, arms, span) => {
+                scrutinee.shift_spans(delta);
+                arms.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Return(value, span) => {
+                value.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Break(label, value, span) => {
+                label.shift_spans(delta);
+                value.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Continue(label, span) => {
+                label.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Lambda(params, body, span) => {
+                params.shift_spans(delta);
+                body.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Array(elems, span) | Expr::Tuple(elems, span) => {
+                elems.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Struct(path, fields, span) => {
+                path.shift_spans(delta);
+                fields.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+            Expr::Range(start, end, _, span) => {
+                start.shift_spans(delta);
+                end.shift_spans(delta);
+                span.shift_spans(delta);
+            }
+        }
+    }
+}
+
+impl ShiftSpans for FieldValue {
+    fn shift_spans(&mut self, delta: isize) {
+        self.value.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for Arm {
+    fn shift_spans(&mut self, delta: isize) {
+        self.pattern.shift_spans(delta);
+        self.guard.shift_spans(delta);
+        self.body.shift_spans(delta);
+        self.span.shift_spans(delta);
+    }
+}
+
+impl ShiftSpans for Guard {
+    fn shift_spans(&mut self, delta: isize) {
+        match self {
+            Guard::If(condition) => condition.shift_spans(delta),
+            Guard::IfLet(pattern, value) => {
+                pattern.shift_spans(delta);
+                value.shift_spans(delta);
+            }
+        }
+    }
+}
+
+impl ShiftSpans for ParseError {
+    fn shift_spans(&mut self, delta: isize) {
+        match self {
+            ParseError::UnexpectedToken { span, .. }
+            | ParseError::InvalidToken { span, .. }
+            | ParseError::UnexpectedEof(span)
+            | ParseError::ReservedKeyword { span, .. }
+            | ParseError::InvalidModifier { span, .. } => span.shift_spans(delta),
+            ParseError::InvalidNumber(_) | ParseError::SyntaxError(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "सूत्र अ = १;\n\nमन्त्र योग(क: सङ्ख्या, ख: सङ्ख्या) -> सङ्ख्या {\n    सूत्र ग = \"{क} धन {ख}\";\n    यदि क महत्तर ख { प्रत्याहर क; }\n    क धन ख\n}\n\nधर्म बिन्दु { सूत्र य: सङ्ख्या; }\n\nयदि अ समान १ { योग(अ, २); }\nसूत्र ब = [अ, २];\n";
+
+    /// Apply `edit` incrementally and check the result against a parse of
+    /// the new text from scratch
+    fn check(parse: &mut IncrementalParse, edit: &TextEdit) -> Range<usize> {
+        let reparsed = parse.edit(edit);
+        let mut parser = Parser::new(Lexer::new(parse.source()));
+        let (program, errors) = parser.parse_program_recovering();
+        assert_eq!(parse.program(), &program, "after {:?}", edit);
+        let incremental: Vec<_> = parse.errors().map(|e| format!("{:?}", e)).collect();
+        let scratch: Vec<_> = errors.iter().map(|e| format!("{:?}", e)).collect();
+        assert_eq!(incremental, scratch, "after {:?}", edit);
+        assert_eq!(parse.tokens(), Lexer::new(parse.source()).collect::<Vec<_>>().as_slice());
+        reparsed
+    }
+
+    /// An edit replacing the first `old` after `after` with `new`
+    fn replace(source: &str, after: &str, old: &str, new: &str) -> TextEdit {
+        let from = source.find(after).unwrap();
+        let at = from + source[from..].find(old).unwrap();
+        TextEdit::new(at..at + old.len(), new)
+    }
+
+    #[test]
+    fn test_incremental_matches_parsing_from_scratch() {
+        let mut parse = IncrementalParse::new(SOURCE, 0);
+        let edits: [(&str, &str, &str); 12] = [
+            // Inside one item, without and with a change in length
+            ("सूत्र अ", "१", "२"),
+            ("प्रत्याहर", "क;", "क गुण क;"),
+            // Breaking an item so that errors and recovery change, then
+            // mending it again
+            ("धर्म", "{", ""),
+            ("धर्म", "बिन्दु", "बिन्दु {"),
+            // An unclosed brace swallows the rest of the file
+            ("यदि अ", "२); }", "२);"),
+            ("यदि अ", "२);", "२); }"),
+            // Joining and splitting items
+            ("सूत्र अ = २", ";", " धन"),
+            ("सूत्र अ = २ धन", " धन", ";"),
+            ("यदि अ", "}", "} अथवा { ०; }"),
+            // New items, and a comment over an item
+            ("\n\nमन्त्र", "\n\n", "\nमन्त्र शून्यफल() {}\n"),
+            ("धर्म", "धर्म", "/* धर्म"),
+            ("/* धर्म", "/* ", "// \n"),
+        ];
+        for (after, old, new) in edits {
+            let edit = replace(parse.source(), after, old, new);
+            check(&mut parse, &edit);
+        }
+    }
+
+    #[test]
+    fn test_every_character_deleted_or_doubled() {
+        for (at, c) in SOURCE.char_indices() {
+            let range = at..at + c.len_utf8();
+            check(&mut IncrementalParse::new(SOURCE, 0), &TextEdit::new(range.clone(), ""));
+            check(&mut IncrementalParse::new(SOURCE, 0), &TextEdit::new(range.end..range.end, c));
+        }
+    }
+
+    #[test]
+    fn test_untouched_items_are_reused() {
+        let mut parse = IncrementalParse::new(SOURCE, 0);
+        let items = parse.program().items.len();
+        let edit = replace(SOURCE, "प्रत्याहर", "क;", "ख;");
+        assert_eq!(check(&mut parse, &edit), 1..2);
+
+        let edit = replace(parse.source(), "धर्म", "य", "र");
+        assert_eq!(check(&mut parse, &edit), 2..3);
+        assert_eq!(parse.program().items.len(), items);
+    }
+}
@@ -2,12 +2,22 @@
 //! Converts tokens into an Abstract Syntax Tree (AST)
 
 pub mod ast;
+pub mod cst;
+pub mod format;
+pub mod incremental;
+pub mod loader;
+pub mod span;
+mod expr;
+mod pattern;
+mod recovery;
+mod stmt;
+mod types;
 
-use vaaktra_lexer::Token;
-use std::iter::Peekable;
+use vaaktra_lexer::{LexError, Token};
+use std::collections::VecDeque;
 use thiserror::Error;
 
-use crate::ast::Span;
+pub use crate::span::{Location, SourceFile, SourceMap, Span};
 
 /// Represents a parsing error
 #[derive(Debug, Error)]
@@ -16,11 +26,30 @@ pub enum ParseError {
     UnexpectedToken {
         expected: String,
         found: Option<Token>,
-        span: (usize, usize),
+        span: Span,
+    },
+    
+    #[error("Invalid token: {error}")]
+    InvalidToken {
+        error: LexError,
+        span: Span,
     },
     
     #[error("Unexpected end of input")]
-    UnexpectedEof,
+    UnexpectedEof(Span),
+    
+    #[error("`{word}` is reserved for future use")]
+    ReservedKeyword {
+        word: String,
+        span: Span,
+    },
+    
+    #[error("`{modifier}` cannot be applied to {target}")]
+    InvalidModifier {
+        modifier: &'static str,
+        target: &'static str,
+        span: Span,
+    },
     
     #[error("Invalid number: {0}")]
     InvalidNumber(#[from] std::num::ParseIntError),
@@ -29,16 +58,78 @@ pub enum ParseError {
     SyntaxError(String),
 }
 
+impl ParseError {
+    /// Where in the source the error was found, if known
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ParseError::UnexpectedToken { span, .. }
+            | ParseError::InvalidToken { span, .. }
+            | ParseError::UnexpectedEof(span)
+            | ParseError::ReservedKeyword { span, .. }
+            | ParseError::InvalidModifier { span, .. } => Some(*span),
+            ParseError::InvalidNumber(_) | ParseError::SyntaxError(_) => None,
+        }
+    }
+}
+
 /// Result type for parsing operations
 pub type ParseResult<T> = Result<T, ParseError>;
 
+/// Modifiers written before an item or dharma member, with where each
+/// was written
+#[derive(Debug, Default)]
+struct Modifiers {
+    public: Option<Span>,
+    is_async: Option<Span>,
+    is_unsafe: Option<Span>,
+}
+
+impl Modifiers {
+    fn visibility(&self) -> ast::Visibility {
+        if self.public.is_some() {
+            ast::Visibility::Public
+        } else {
+            ast::Visibility::Private
+        }
+    }
+    
+    fn apply_to_mantra(&self, mantra: &mut ast::MantraDef) {
+        mantra.visibility = self.visibility();
+        mantra.is_async = self.is_async.is_some();
+        mantra.is_unsafe = self.is_unsafe.is_some();
+    }
+    
+    /// Reject `असमकाल` and `असुरक्षित` on something that is not a mantra
+    fn only_public(&self, target: &'static str) -> ParseResult<()> {
+        let invalid = [("असमकाल", self.is_async), ("असुरक्षित", self.is_unsafe)];
+        match invalid.into_iter().find_map(|(modifier, span)| Some((modifier, span?))) {
+            Some((modifier, span)) => Err(ParseError::InvalidModifier { modifier, target, span }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A member of a dharma or कार्यान्वय body
+enum Member {
+    Field(ast::FieldDef),
+    Method(ast::MantraDef),
+}
+
 /// The main parser struct
 pub struct Parser<I>
 where
     I: Iterator<Item = (Token, std::ops::Range<usize>)>,
 {
-    tokens: Peekable<I>,
+    tokens: I,
+    /// Tokens taken from `tokens` but not yet consumed
+    lookahead: VecDeque<(Token, std::ops::Range<usize>)>,
     current_span: Option<std::ops::Range<usize>>,
+    /// Source file every span produced by this parser belongs to
+    file_id: u32,
+    /// Errors recovered from so far
+    errors: Vec<ParseError>,
+    /// Number of tokens consumed, used to ensure recovery makes progress
+    consumed: usize,
 }
 
 impl<I> Parser<I>
@@ -47,34 +138,83 @@ where
 {
     /// Create a new parser from an iterator of tokens
     pub fn new(tokens: I) -> Self {
+        Self::with_file_id(tokens, 0)
+    }
+    
+    /// Create a parser whose spans refer to the given source file
+    pub fn with_file_id(tokens: I, file_id: u32) -> Self {
         Parser {
-            tokens: tokens.peekable(),
+            tokens,
+            lookahead: VecDeque::new(),
             current_span: None,
+            file_id,
+            errors: Vec::new(),
+            consumed: 0,
         }
     }
     
-    /// Parse a complete program
+    /// Parse a complete program, failing with the first error encountered
     pub fn parse_program(&mut self) -> ParseResult<ast::Program> {
+        let (program, mut errors) = self.parse_program_recovering();
+        if errors.is_empty() {
+            Ok(program)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+    
+    /// Parse a complete program, recovering from errors at statement and
+    /// item boundaries. Returns the partial program, with `Expr::Error`
+    /// statements where input was skipped, and every error in source order.
+    pub fn parse_program_recovering(&mut self) -> (ast::Program, Vec<ParseError>) {
+        let start = self.next_start();
+        let items = match self.parse_items(None) {
+            Ok(items) => items,
+            Err(error) => {
+                self.record_error(error);
+                Vec::new()
+            }
+        };
+        let program = ast::Program { items, span: self.span_from(start) };
+        (program, std::mem::take(&mut self.errors))
+    }
+    
+    /// Parse items up to and including `close`, or to the end of input
+    fn parse_items(&mut self, close: Option<Token>) -> ParseResult<Vec<ast::Item>> {
         let mut items = Vec::new();
         
-        while self.peek().is_some() {
-            let stmt = self.parse_statement()?;
-            if let ast::Statement::Item(item) = stmt {
-                items.push(item);
-            } else {
-                // For now, wrap non-item statements in a Praarabdha block
-                items.push(ast::Item::Praarabdha(vec![stmt]));
+        loop {
+            match (self.peek(), close.as_ref()) {
+                (None, None) => break,
+                (None, Some(_)) => return Err(self.eof_error()),
+                (Some(token), Some(close)) if token == close => {
+                    self.next();
+                    break;
+                }
+                _ => {}
             }
+            
+            items.push(self.parse_item());
         }
         
-        Ok(ast::Program { items, span: Span::dummy() })
+        Ok(items)
+    }
+    
+    /// Parse one item, or a statement as a Praarabdha item
+    pub(crate) fn parse_item(&mut self) -> ast::Item {
+        match self.parse_statement_or_recover() {
+            ast::Statement::Item(item) => item,
+            // For now, wrap non-item statements in a Praarabdha block
+            stmt => ast::Item::Praarabdha(vec![stmt]),
+        }
     }
     
     /// Parse a statement
     fn parse_statement(&mut self) -> ParseResult<ast::Statement> {
-        let _start_pos = self.current_span().map(|s| s.start).unwrap_or(0);
-        
+        let modified_item = self.at_modified_item();
         let stmt = match self.peek() {
+            _ if modified_item => self.parse_modified_item()?,
+            
             // Vedic-inspired keywords
             Some(Token::Class) => {
                 // Class declarations are top-level items, wrap in an Item statement
@@ -96,128 +236,335 @@ where
                 // Variable declarations are statements
                 self.parse_sutra_decl()?
             },
+            Some(Token::Module) => self.parse_yantra_decl()?,
+            Some(Token::Import) => self.parse_import_decl()?,
+            Some(Token::Trait) => self.parse_gunadharma_decl()?,
+            Some(Token::Impl) => self.parse_karyanvaya_decl()?,
+            
+            // Control flow
+            Some(Token::If) => self.parse_if_statement()?,
+            Some(Token::While) => self.parse_while_statement(None)?,
+            Some(Token::ForEach) => self.parse_foreach_statement(None)?,
+            Some(Token::Label(_)) => self.parse_labeled_loop()?,
+            Some(Token::Break) => self.parse_break_statement()?,
+            Some(Token::Continue) => self.parse_continue_statement()?,
+            Some(Token::Return) => self.parse_return_statement()?,
             
-            // Control flow - for now, return placeholder statements
-            Some(Token::If) => ast::Statement::Shunya, // TODO: implement parse_if_statement
-            Some(Token::While) => ast::Statement::Shunya, // TODO: implement parse_while_statement  
+            // Blocks
+            Some(Token::LBrace) => ast::Statement::Block(self.parse_block()?),
             
-            // Blocks and expressions - for now, return placeholder statements
-            Some(Token::LBrace) => ast::Statement::Shunya, // TODO: implement parse_block
-            _ => ast::Statement::Shunya, // TODO: implement parse_expression_statement
+            // Empty statement
+            Some(Token::Semicolon | Token::DoubleDanda) => {
+                self.next();
+                ast::Statement::Shunya
+            },
+            _ => self.parse_expression_statement()?,
         };
         
-        let _end_pos = self.current_span().map(|s| s.end).unwrap_or(0);
-        
-        // Return the statement as-is for now
+        // `॥` may close any statement or item, after its `}` or `;`
+        self.matches(Token::DoubleDanda);
         Ok(stmt)
     }
     
+    /// Whether the next tokens are modifiers of an item. `असमकाल {`
+    /// begins an async block expression instead.
+    pub(crate) fn at_modified_item(&mut self) -> bool {
+        match self.peek() {
+            Some(Token::Pub | Token::Unsafe) => true,
+            Some(Token::Async) => self.peek_nth(1) != Some(&Token::LBrace),
+            _ => false,
+        }
+    }
+    
+    /// Parse `सार्वजनिक`, `असमकाल` and `असुरक्षित`, in any order
+    fn parse_modifiers(&mut self) -> ParseResult<Modifiers> {
+        let mut modifiers = Modifiers::default();
+        loop {
+            let slot = match self.peek() {
+                Some(Token::Pub) => &mut modifiers.public,
+                Some(Token::Async) => &mut modifiers.is_async,
+                Some(Token::Unsafe) => &mut modifiers.is_unsafe,
+                _ => return Ok(modifiers),
+            };
+            if slot.is_some() {
+                // Each modifier may be written once
+                return Err(self.unexpected("मन्त्र, धर्म or गुणधर्म after modifiers"));
+            }
+            self.next();
+            *slot = Some(self.token_span());
+        }
+    }
+    
+    /// Parse a मन्त्र, धर्म or गुणधर्म preceded by modifiers
+    fn parse_modified_item(&mut self) -> ParseResult<ast::Statement> {
+        let start_pos = self.next_start();
+        let modifiers = self.parse_modifiers()?;
+        
+        // Spans cover the modifiers too
+        let item = match self.peek() {
+            Some(Token::Fn) => {
+                let mut mantra = self.parse_mantra_def(None)?;
+                modifiers.apply_to_mantra(&mut mantra);
+                mantra.span = self.span_from(start_pos);
+                ast::Item::Mantra(mantra)
+            }
+            Some(Token::Class) => {
+                modifiers.only_public("a धर्म")?;
+                let ast::Statement::Item(ast::Item::Dharma(mut dharma)) = self.parse_dharma_decl()? else {
+                    return Err(ParseError::SyntaxError("Expected dharma declaration".to_string()));
+                };
+                dharma.visibility = modifiers.visibility();
+                dharma.span = self.span_from(start_pos);
+                ast::Item::Dharma(dharma)
+            }
+            Some(Token::Trait) => {
+                modifiers.only_public("a गुणधर्म")?;
+                let ast::Statement::Item(ast::Item::Gunadharma(mut gunadharma)) = self.parse_gunadharma_decl()? else {
+                    return Err(ParseError::SyntaxError("Expected gunadharma declaration".to_string()));
+                };
+                gunadharma.visibility = modifiers.visibility();
+                gunadharma.span = self.span_from(start_pos);
+                ast::Item::Gunadharma(gunadharma)
+            }
+            _ => return Err(self.unexpected("मन्त्र, धर्म or गुणधर्म after modifiers")),
+        };
+        Ok(ast::Statement::Item(item))
+    }
+    
+    /// Parse a field or method of a dharma, with its modifiers
+    fn parse_member(&mut self, self_type: &ast::Path) -> ParseResult<Member> {
+        let start_pos = self.next_start();
+        let modifiers = self.parse_modifiers()?;
+        
+        match self.peek() {
+            Some(Token::Let) => {
+                modifiers.only_public("a field")?;
+                let field = self.parse_field_def()?;
+                Ok(Member::Field(ast::FieldDef {
+                    visibility: modifiers.visibility(),
+                    span: self.span_from(start_pos),
+                    ..field
+                }))
+            }
+            Some(Token::Fn) => {
+                let mut method = self.parse_mantra_def(Some(self_type))?;
+                modifiers.apply_to_mantra(&mut method);
+                method.span = self.span_from(start_pos);
+                Ok(Member::Method(method))
+            }
+            _ => Err(self.unexpected("सूत्र field or मन्त्र method")),
+        }
+    }
+    
     /// Parse a धर्म (dharma) declaration - class/type definition
+    ///
+    /// The body holds `सूत्र नाम: प्रकार [= मूल्य];` fields and `मन्त्र`
+    /// methods, which may take `आत्मन्` as their first parameter.
     fn parse_dharma_decl(&mut self) -> ParseResult<ast::Statement> {
-        let start_pos = self.current_span().map(|s| s.start).unwrap_or(0);
+        let start_pos = self.next_start();
         self.expect(Token::Class)?;
         
         let name = self.parse_identifier()?;
+        let self_type = types::single_segment_path(&name, self.token_span());
         
-        // Parse type parameters if any - for now, skip
-        let type_params = Vec::new(); // TODO: implement parse_type_parameters
+        let type_params = self.parse_type_params()?;
         
-        // Parse fields - for now, create empty fields
         self.expect(Token::LBrace)?;
-        let fields = Vec::new(); // TODO: implement field parsing
-        
-        // Skip to closing brace for now
-        while !self.matches(Token::RBrace) {
-            self.next(); // Skip tokens until we find closing brace
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+        for member in self.parse_members(&self_type)? {
+            match member {
+                Member::Field(field) => fields.push(field),
+                Member::Method(method) => methods.push(method),
+            }
         }
         
-        let end_pos = self.current_span().map(|s| s.end).unwrap_or(0);
-        
         Ok(ast::Statement::Item(ast::Item::Dharma(ast::DharmaDef {
             name,
             type_params,
             fields,
-            methods: Vec::new(), // Will be populated later
-            visibility: ast::Visibility::Public, // Default to public for now
-            span: ast::Span::new(start_pos, end_pos, 0), // 0 for main file
+            methods,
+            visibility: ast::Visibility::Private,
+            span: self.span_from(start_pos),
         })))
     }
     
+    /// Parse the members of a dharma or कार्यान्वय body up to and including
+    /// its `}`, recovering from errors in each
+    fn parse_members(&mut self, self_type: &ast::Path) -> ParseResult<Vec<Member>> {
+        let mut members = Vec::new();
+        while !self.matches(Token::RBrace) {
+            match self.peek() {
+                None => return Err(self.eof_error()),
+                Some(Token::Let | Token::Fn | Token::Pub | Token::Async | Token::Unsafe) => {
+                    members.extend(self.recover(|p| p.parse_member(self_type)));
+                }
+                Some(Token::DoubleDanda) => {
+                    self.next();
+                }
+                _ => {
+                    let found = self.next();
+                    let span = self.token_span();
+                    self.record_error(ParseError::UnexpectedToken {
+                        expected: "सूत्र field or मन्त्र method".to_string(),
+                        found,
+                        span,
+                    });
+                }
+            }
+        }
+        Ok(members)
+    }
+    
+    /// Parse a `सूत्र नाम: प्रकार [= मूल्य];` field inside a dharma body
+    fn parse_field_def(&mut self) -> ParseResult<ast::FieldDef> {
+        let start_pos = self.next_start();
+        self.expect(Token::Let)?;
+        
+        let name = self.parse_identifier()?;
+        self.expect(Token::Colon)?;
+        let ty = self.parse_type_or_recover()?;
+        
+        let default_value = if self.matches(Token::Equals) {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+        self.expect_terminator()?;
+        
+        Ok(ast::FieldDef {
+            name,
+            ty,
+            default_value,
+            visibility: ast::Visibility::Private,
+            span: self.span_from(start_pos),
+        })
+    }
+    
     /// Parse a मन्त्र (mantra) declaration - function/method
     fn parse_mantra_decl(&mut self) -> ParseResult<ast::Statement> {
-        let start_pos = self.current_span().map(|s| s.start).unwrap_or(0);
-        let visibility = ast::Visibility::Public; // TODO: implement parse_visibility
-        
+        Ok(ast::Statement::Item(ast::Item::Mantra(self.parse_mantra_def(None)?)))
+    }
+    
+    /// Parse `मन्त्र नाम(अ: सङ्ख्या, ब: सङ्ख्या = १) -> सङ्ख्या { ... }`
+    ///
+    /// Inside a dharma, `self_type` names the enclosing type and the first
+    /// parameter may be the `आत्मन्` receiver.
+    fn parse_mantra_def(&mut self, self_type: Option<&ast::Path>) -> ParseResult<ast::MantraDef> {
+        let start_pos = self.next_start();
         self.expect(Token::Fn)?;
         let name = self.parse_identifier()?;
         
-        // Parse type parameters if any - for now, skip
-        let type_params = Vec::new(); // TODO: implement parse_type_parameters
+        let type_params = self.parse_type_params()?;
         
-        // Parse parameters - for now, create empty params
         self.expect(Token::LParen)?;
-        let params = Vec::new(); // TODO: implement parameter parsing
+        let params = self.parse_params(self_type)?;
+        let return_type = self.parse_return_type(start_pos)?;
+        
+        let body = self.parse_block()?;
+        
+        Ok(ast::MantraDef {
+            name,
+            type_params,
+            params,
+            return_type,
+            body,
+            is_async: false,
+            is_unsafe: false,
+            visibility: ast::Visibility::Private,
+            span: self.span_from(start_pos),
+        })
+    }
+    
+    /// Parse the `-> प्रकार` of a mantra. Without `->` the mantra returns
+    /// शून्य (void).
+    fn parse_return_type(&mut self, start_pos: usize) -> ParseResult<ast::Type> {
+        if self.matches(Token::Arrow) {
+            return self.parse_type_or_recover();
+        }
+        let at = self.current_span().map(|s| s.end).unwrap_or(start_pos);
+        Ok(types::named_type("शून्य", Span::new(at, at, self.file_id)))
+    }
+    
+    /// Parse a parameter list up to and including `)`
+    fn parse_params(&mut self, self_type: Option<&ast::Path>) -> ParseResult<Vec<ast::Param>> {
+        let mut params = Vec::new();
         
-        // Skip to closing paren for now
         while !self.matches(Token::RParen) {
-            self.next(); // Skip tokens until we find closing paren
+            let param = match (self.peek(), self_type) {
+                (Some(Token::SelfValue), Some(self_type)) if params.is_empty() => {
+                    self.parse_receiver(self_type)?
+                }
+                _ => self.parse_param()?,
+            };
+            params.push(param);
+            if !self.matches(Token::Comma) {
+                self.expect(Token::RParen)?;
+                break;
+            }
         }
         
-        // Parse return type - for now, use a placeholder
-        let return_type = ast::Type::Infer(ast::Span::dummy()); // TODO: implement return type parsing
+        Ok(params)
+    }
+    
+    /// Parse the `आत्मन्` receiver of a method; its type is the enclosing
+    /// dharma, or the गुणधर्म declaring the method
+    fn parse_receiver(&mut self, self_type: &ast::Path) -> ParseResult<ast::Param> {
+        let start_pos = self.next_start();
+        self.expect(Token::SelfValue)?;
+        let span = self.span_from(start_pos);
         
-        // Parse function body - for now, create empty block
-        let body = ast::Block {
-            stmts: Vec::new(),
-            expr: None,
-            span: ast::Span::dummy(),
-        }; // TODO: implement parse_block
+        Ok(ast::Param {
+            name: ast::RcStr::new(ast::SELF_NAME),
+            ty: ast::Type::Named(ast::Path { span, ..self_type.clone() }, Vec::new()),
+            default_value: None,
+            span,
+        })
+    }
+    
+    /// Parse a single `नाम: प्रकार [= मूल्य]` parameter
+    fn parse_param(&mut self) -> ParseResult<ast::Param> {
+        let start_pos = self.next_start();
+        let name = self.parse_identifier()?;
+        self.expect(Token::Colon)?;
+        let ty = self.parse_type_or_recover()?;
         
-        let end_pos = self.current_span().map(|s| s.end).unwrap_or(0);
+        let default_value = if self.matches(Token::Equals) {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
         
-        Ok(ast::Statement::Item(ast::Item::Mantra(ast::MantraDef {
+        Ok(ast::Param {
             name,
-            type_params,
-            params,
-            return_type,
-            body,
-            is_async: false, // Will be handled with async keyword later
-            is_unsafe: false, // Will be handled with unsafe keyword later
-            visibility,
-            span: ast::Span::new(start_pos, end_pos, 0), // 0 for main file
-        })))
+            ty,
+            default_value,
+            span: self.span_from(start_pos),
+        })
     }
     
     /// Parse a सूत्र (sutra) declaration - constant/variable
     fn parse_sutra_decl(&mut self) -> ParseResult<ast::Statement> {
-        let start_pos = self.current_span().map(|s| s.start).unwrap_or(0);
+        let start_pos = self.next_start();
         
         self.expect(Token::Let)?;
-        let is_mutable = false; // TODO: Add mutable token support
         
-        // Parse pattern (for now, just a simple identifier pattern)
-        let pattern = ast::Pattern::Bind {
-            name: self.parse_identifier()?,
-            mutable: is_mutable,
-            by_ref: false,
-            subpattern: None,
-            span: ast::Span::dummy(),
-        }; // TODO: implement parse_pattern
+        // `सूत्र चल नाम` declares a variable; a सूत्र is immutable otherwise
+        let pattern = self.parse_pattern()?;
+        let is_mutable = matches!(pattern, ast::Pattern::Bind { mutable: true, .. });
         
         // Parse type annotation if present
         let type_annotation = if self.matches(Token::Colon) {
-            Some(ast::Type::Infer(ast::Span::dummy())) // TODO: implement parse_type
+            Some(self.parse_type_or_recover()?)
         } else {
             None
         };
         
         // Parse initializer (required for sutra)
         self.expect(Token::Equals)?;
-        let value = ast::Expr::Error(ast::Span::dummy()); // TODO: implement parse_expression
-        
-        self.expect(Token::Semicolon)?;
+        let value = self.parse_expression()?;
         
-        let end_pos = self.current_span().map(|s| s.end).unwrap_or(0);
+        self.expect_terminator()?;
         
         Ok(ast::Statement::Sutra(ast::SutraDef {
             pattern,
@@ -225,27 +572,195 @@ where
             value,
             is_mutable,
             is_static: false, // Will be handled with static keyword if needed
-            span: ast::Span::new(start_pos, end_pos, 0), // 0 for main file
+            span: self.span_from(start_pos),
         }))
     }
     
-    // TODO: Implement yantra (module) parsing when needed
+    /// Parse an expression followed by `;`
+    fn parse_expression_statement(&mut self) -> ParseResult<ast::Statement> {
+        let expr = self.parse_expression()?;
+        if expr::is_block_like(&expr) {
+            self.matches_terminator();
+        } else {
+            self.expect_terminator()?;
+        }
+        Ok(ast::Statement::Expr(Box::new(expr)))
+    }
+    
+    /// Parse a यन्त्र (yantra) declaration - module/namespace
+    ///
+    /// `यन्त्र नाम { ... }` declares the module inline, while `यन्त्र नाम;`
+    /// refers to `नाम.vk`, whose items are filled in by the module loader.
+    fn parse_yantra_decl(&mut self) -> ParseResult<ast::Statement> {
+        let start_pos = self.next_start();
+        self.expect(Token::Module)?;
+        let name = self.parse_identifier()?;
+        
+        let (items, external) = if self.matches_terminator() {
+            (Vec::new(), true)
+        } else {
+            self.expect(Token::LBrace)?;
+            (self.parse_items(Some(Token::RBrace))?, false)
+        };
+        
+        Ok(ast::Statement::Item(ast::Item::Yantra(ast::YantraDef {
+            name,
+            items,
+            external,
+            span: self.span_from(start_pos),
+        })))
+    }
+    
+    /// Parse a गुणधर्म (guṇadharma) declaration - interface
+    ///
+    /// The body lists `मन्त्र नाम(आत्मन्, ...) -> प्रकार;` methods without
+    /// bodies, which each dharma implementing it defines.
+    fn parse_gunadharma_decl(&mut self) -> ParseResult<ast::Statement> {
+        let start_pos = self.next_start();
+        self.expect(Token::Trait)?;
+        let name = self.parse_identifier()?;
+        let self_type = types::single_segment_path(&name, self.token_span());
+        
+        self.expect(Token::LBrace)?;
+        let mut methods = Vec::new();
+        while !self.matches(Token::RBrace) {
+            match self.peek() {
+                None => return Err(self.eof_error()),
+                Some(Token::Fn) => methods.extend(self.recover(|p| p.parse_method_sig(&self_type))),
+                Some(Token::DoubleDanda) => {
+                    self.next();
+                }
+                _ => {
+                    let found = self.next();
+                    let span = self.token_span();
+                    self.record_error(ParseError::UnexpectedToken {
+                        expected: "मन्त्र method".to_string(),
+                        found,
+                        span,
+                    });
+                }
+            }
+        }
+        
+        Ok(ast::Statement::Item(ast::Item::Gunadharma(ast::GunadharmaDef {
+            name,
+            methods,
+            visibility: ast::Visibility::Private,
+            span: self.span_from(start_pos),
+        })))
+    }
+    
+    /// Parse `मन्त्र नाम(आत्मन्, ...) -> प्रकार;` inside a गुणधर्म, whose
+    /// `आत्मन्` has the type of the गुणधर्म
+    fn parse_method_sig(&mut self, self_type: &ast::Path) -> ParseResult<ast::MethodSig> {
+        let start_pos = self.next_start();
+        self.expect(Token::Fn)?;
+        let name = self.parse_identifier()?;
+        
+        self.expect(Token::LParen)?;
+        let params = self.parse_params(Some(self_type))?;
+        let return_type = self.parse_return_type(start_pos)?;
+        self.expect_terminator()?;
+        
+        Ok(ast::MethodSig {
+            name,
+            params,
+            return_type,
+            span: self.span_from(start_pos),
+        })
+    }
+    
+    /// Parse a कार्यान्वय (kāryānvaya) - implementation of a गुणधर्म
+    ///
+    /// `कार्यान्वय वृत्त: आकार { ... }` defines the methods of आकार for the
+    /// dharma वृत्त, written as in the dharma's own body.
+    fn parse_karyanvaya_decl(&mut self) -> ParseResult<ast::Statement> {
+        let start_pos = self.next_start();
+        self.expect(Token::Impl)?;
+        let dharma = self.parse_path()?;
+        self.expect(Token::Colon)?;
+        let gunadharma = self.parse_path()?;
+        
+        self.expect(Token::LBrace)?;
+        let mut methods = Vec::new();
+        for member in self.parse_members(&dharma)? {
+            match member {
+                Member::Method(method) => methods.push(method),
+                Member::Field(field) => self.record_error(ParseError::UnexpectedToken {
+                    expected: "मन्त्र method; fields belong in the धर्म".to_string(),
+                    found: Some(Token::Let),
+                    span: field.span,
+                }),
+            }
+        }
+        
+        Ok(ast::Statement::Item(ast::Item::Karyanvaya(ast::KaryanvayaDef {
+            dharma,
+            gunadharma,
+            methods,
+            span: self.span_from(start_pos),
+        })))
+    }
+    
+    /// Parse an `आनय यन्त्र::नाम;` import
+    fn parse_import_decl(&mut self) -> ParseResult<ast::Statement> {
+        let start_pos = self.next_start();
+        self.expect(Token::Import)?;
+        let path = self.parse_path()?;
+        self.expect_terminator()?;
+        
+        Ok(ast::Statement::Item(ast::Item::Aanaya(ast::AanayaDef {
+            path,
+            span: self.span_from(start_pos),
+        })))
+    }
+    
     
     /// Get the current span
     fn current_span(&self) -> Option<std::ops::Range<usize>> {
         self.current_span.clone()
     }
     
+    /// Start offset of the next token, or the end of the input
+    fn next_start(&mut self) -> usize {
+        match self.peek_spanned(0) {
+            Some((_, span)) => span.start,
+            None => self.current_span.as_ref().map(|s| s.end).unwrap_or(0),
+        }
+    }
+    
+    /// Span from `start` to the end of the last consumed token
+    fn span_from(&self, start: usize) -> Span {
+        let end = self.current_span.as_ref().map(|s| s.end).unwrap_or(start);
+        Span::new(start, end.max(start), self.file_id)
+    }
+    
+    /// Span of the last consumed token
+    fn token_span(&self) -> Span {
+        let range = self.current_span.clone().unwrap_or(0..0);
+        Span::new(range.start, range.end, self.file_id)
+    }
+    
+    /// Error for input ending where more was expected
+    fn eof_error(&self) -> ParseError {
+        let end = self.current_span.as_ref().map_or(0, |s| s.end);
+        ParseError::UnexpectedEof(Span::new(end, end, self.file_id))
+    }
+    
     /// Parse an identifier
     fn parse_identifier(&mut self) -> ParseResult<ast::RcStr> {
         match self.next() {
             Some(Token::Ident(name)) => Ok(ast::RcStr::new(&name)),
+            Some(Token::Reserved(word)) => Err(ParseError::ReservedKeyword {
+                word,
+                span: self.token_span(),
+            }),
             found => {
-                let span = self.current_span.clone().unwrap_or(0..0);
+                let span = self.token_span();
                 Err(ParseError::UnexpectedToken {
                     expected: "identifier".to_string(),
                     found,
-                    span: (span.start, span.end),
+                    span,
                 })
             }
         }
@@ -256,8 +771,9 @@ where
     
     /// Get the next token
     fn next(&mut self) -> Option<Token> {
-        if let Some((token, span)) = self.tokens.next() {
+        if let Some((token, span)) = self.lookahead.pop_front().or_else(|| self.tokens.next()) {
             self.current_span = Some(span);
+            self.consumed += 1;
             Some(token)
         } else {
             None
@@ -266,21 +782,61 @@ where
     
     /// Peek at the next token without consuming it
     fn peek(&mut self) -> Option<&Token> {
-        self.tokens.peek().map(|(token, _)| token)
+        self.peek_nth(0)
+    }
+    
+    /// Peek `n` tokens past the next one without consuming anything
+    fn peek_nth(&mut self, n: usize) -> Option<&Token> {
+        self.peek_spanned(n).map(|(token, _)| token)
+    }
+    
+    fn peek_spanned(&mut self, n: usize) -> Option<&(Token, std::ops::Range<usize>)> {
+        while self.lookahead.len() <= n {
+            let token = self.tokens.next()?;
+            self.lookahead.push_back(token);
+        }
+        self.lookahead.get(n)
     }
     
-    /// Expect a specific token
+    /// Expect a specific token. A mismatched token is left in place so
+    /// error recovery can resynchronize on it.
     fn expect(&mut self, expected: Token) -> ParseResult<()> {
-        match self.next() {
-            Some(token) if token == expected => Ok(()),
-            found => {
-                let span = self.current_span.clone().unwrap_or(0..0);
-                Err(ParseError::UnexpectedToken {
-                    expected: format!("{:?}", expected),
-                    found,
-                    span: (span.start, span.end),
-                })
+        if self.matches(expected.clone()) {
+            return Ok(());
+        }
+        Err(self.unexpected(format!("{:?}", expected)))
+    }
+    
+    /// Error for the next token, which is left unconsumed. A token the
+    /// lexer rejected is reported with the lexer's reason instead.
+    fn unexpected(&mut self, expected: impl Into<String>) -> ParseError {
+        let file_id = self.file_id;
+        match self.peek_spanned(0) {
+            Some((Token::Error(error), span)) => {
+                return ParseError::InvalidToken {
+                    error: error.clone(),
+                    span: Span::new(span.start, span.end, file_id),
+                };
+            }
+            Some((Token::Reserved(word), span)) => {
+                return ParseError::ReservedKeyword {
+                    word: word.clone(),
+                    span: Span::new(span.start, span.end, file_id),
+                };
+            }
+            _ => {}
+        }
+        let (found, span) = match self.peek_spanned(0) {
+            Some((found, span)) => (Some(found.clone()), Span::new(span.start, span.end, file_id)),
+            None => {
+                let end = self.current_span.as_ref().map_or(0, |s| s.end);
+                (None, Span::new(end, end, file_id))
             }
+        };
+        ParseError::UnexpectedToken {
+            expected: expected.into(),
+            found,
+            span,
         }
     }
     
@@ -294,6 +850,20 @@ where
         }
         false
     }
+
+    /// Consume a statement terminator: `;` (also written `।`) or `॥`
+    fn matches_terminator(&mut self) -> bool {
+        self.matches(Token::Semicolon) || self.matches(Token::DoubleDanda)
+    }
+
+    /// Expect a statement terminator, reporting a missing `;`
+    fn expect_terminator(&mut self) -> ParseResult<()> {
+        if self.matches(Token::DoubleDanda) {
+            Ok(())
+        } else {
+            self.expect(Token::Semicolon)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -310,5 +880,985 @@ mod tests {
         assert!(program.items.is_empty());  
     }
     
+    /// Parse `सूत्र फल = <src>;` and return the initializer
+    fn parse_init(src: &str) -> ast::Expr {
+        let input = format!("सूत्र फल = {};", src);
+        let mut parser = Parser::new(Lexer::new(&input));
+        let program = parser.parse_program().unwrap();
+        match &program.items[0] {
+            ast::Item::Praarabdha(stmts) => match &stmts[0] {
+                ast::Statement::Sutra(sutra) => sutra.value.clone(),
+                other => panic!("Expected sutra, got {:?}", other),
+            },
+            other => panic!("Expected statement block, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_binary_precedence() {
+        // अ धन ब गुण ३ == अ धन (ब गुण ३)
+        match parse_init("अ धन ब गुण ३") {
+            ast::Expr::Binary(lhs, ast::BinaryOp::Add, rhs, _) => {
+                assert!(matches!(*lhs, ast::Expr::Variable(..)));
+                assert!(matches!(*rhs, ast::Expr::Binary(_, ast::BinaryOp::Multiply, _, _)));
+            }
+            other => panic!("Expected addition, got {:?}", other),
+        }
+        
+        // च binds tighter than वा, comparisons tighter than both
+        match parse_init("अ लघुत्तर ब वा स च न द") {
+            ast::Expr::Binary(lhs, ast::BinaryOp::Or, rhs, _) => {
+                assert!(matches!(*lhs, ast::Expr::Binary(_, ast::BinaryOp::Less, _, _)));
+                match *rhs {
+                    ast::Expr::Binary(_, ast::BinaryOp::And, not, _) => {
+                        assert!(matches!(*not, ast::Expr::Unary(ast::UnaryOp::Not, _, _)));
+                    }
+                    other => panic!("Expected conjunction, got {:?}", other),
+                }
+            }
+            other => panic!("Expected disjunction, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_left_associativity_and_grouping() {
+        // अ ऋण ब ऋण स == (अ ऋण ब) ऋण स
+        match parse_init("अ ऋण ब ऋण स") {
+            ast::Expr::Binary(lhs, ast::BinaryOp::Subtract, rhs, _) => {
+                assert!(matches!(*lhs, ast::Expr::Binary(_, ast::BinaryOp::Subtract, _, _)));
+                assert!(matches!(*rhs, ast::Expr::Variable(..)));
+            }
+            other => panic!("Expected subtraction, got {:?}", other),
+        }
+        
+        match parse_init("(अ धन ब) गुण ऋण २") {
+            ast::Expr::Binary(lhs, ast::BinaryOp::Multiply, rhs, _) => {
+                assert!(matches!(*lhs, ast::Expr::Binary(_, ast::BinaryOp::Add, _, _)));
+                assert!(matches!(*rhs, ast::Expr::Unary(ast::UnaryOp::Negate, _, _)));
+            }
+            other => panic!("Expected multiplication, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_postfix_expressions() {
+        match parse_init("गणक.योग(१, २).फल") {
+            ast::Expr::FieldAccess(inner, field, _) => {
+                assert_eq!(field.as_str(), "फल");
+                match *inner {
+                    ast::Expr::MethodCall(recv, method, _, args, _) => {
+                        assert!(matches!(*recv, ast::Expr::Variable(..)));
+                        assert_eq!(method.as_str(), "योग");
+                        assert_eq!(args.len(), 2);
+                    }
+                    other => panic!("Expected method call, got {:?}", other),
+                }
+            }
+            other => panic!("Expected field access, got {:?}", other),
+        }
+        
+        match parse_init("जोड़ना(अ, ब धन १)") {
+            ast::Expr::Call(callee, args, _) => {
+                assert!(matches!(*callee, ast::Expr::Variable(..)));
+                assert!(matches!(args[1], ast::Expr::Binary(_, ast::BinaryOp::Add, _, _)));
+            }
+            other => panic!("Expected call, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_expression_spans() {
+        let input = "सूत्र फल = अ धन ब;";
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program().unwrap();
+        let ast::Item::Praarabdha(stmts) = &program.items[0] else {
+            panic!("Expected statement block");
+        };
+        let ast::Statement::Sutra(sutra) = &stmts[0] else {
+            panic!("Expected sutra");
+        };
+        let span = sutra.value.span();
+        assert_eq!(&input[span.start..span.end], "अ धन ब");
+    }
+    
+    #[test]
+    fn test_parse_assignment_statement() {
+        let mut parser = Parser::new(Lexer::new("अ = ब = ३;"));
+        let program = parser.parse_program().unwrap();
+        match &program.items[0] {
+            ast::Item::Praarabdha(stmts) => match &stmts[0] {
+                ast::Statement::Expr(expr) => match expr.as_ref() {
+                    ast::Expr::Assign(_, rhs, _) => {
+                        assert!(matches!(rhs.as_ref(), ast::Expr::Assign(..)));
+                    }
+                    other => panic!("Expected assignment, got {:?}", other),
+                },
+                other => panic!("Expected expression statement, got {:?}", other),
+            },
+            other => panic!("Expected statement block, got {:?}", other),
+        }
+    }
+    
+    /// Parse a program consisting of a single top-level statement
+    fn parse_single_statement(input: &str) -> ast::Statement {
+        let mut parser = Parser::new(Lexer::new(input));
+        let mut program = parser.parse_program().unwrap();
+        assert_eq!(program.items.len(), 1, "Expected exactly one item");
+        match program.items.remove(0) {
+            ast::Item::Praarabdha(mut stmts) => stmts.remove(0),
+            other => panic!("Expected statement block, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_if_else_chain() {
+        let stmt = parse_single_statement(
+            "यदि (० लघुत्तर अ) { ब = १; } अथवा यदि अ लघुत्तर ० { ब = ऋण १; } अथवा { ब = ०; }",
+        );
+        let ast::Statement::Yadi { condition, then_branch, else_branch } = stmt else {
+            panic!("Expected यदि statement");
+        };
+        assert!(matches!(*condition, ast::Expr::Binary(_, ast::BinaryOp::Less, _, _)));
+        assert!(matches!(*then_branch, ast::Statement::Block(ref b) if b.stmts.len() == 1));
+        
+        match else_branch.map(|b| *b) {
+            Some(ast::Statement::Yadi { else_branch: Some(last), .. }) => {
+                assert!(matches!(*last, ast::Statement::Block(_)));
+            }
+            other => panic!("Expected अथवा यदि chain, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_loops_with_labels() {
+        let stmt = parse_single_statement(
+            "'बाह्य: प्रत्येक पङ्क्ति मध्ये सारणी { यावत् सत्य { निर्गम 'बाह्य; } अनुवृत्ति; }",
+        );
+        let ast::Statement::Pratyeka { pattern, body, label, .. } = stmt else {
+            panic!("Expected प्रत्येक loop");
+        };
+        assert_eq!(label.unwrap().name.as_str(), "बाह्य");
+        assert!(matches!(pattern, ast::Pattern::Bind { ref name, .. } if name.as_str() == "पङ्क्ति"));
+        
+        let ast::Statement::Block(body) = *body else {
+            panic!("Expected loop body block");
+        };
+        match &body.stmts[0] {
+            ast::Statement::Yaavat { body, label: None, .. } => match body.as_ref() {
+                ast::Statement::Block(inner) => {
+                    assert!(matches!(
+                        &inner.stmts[0],
+                        ast::Statement::Nirgama { label: Some(l), value: None } if l.name.as_str() == "बाह्य"
+                    ));
+                }
+                other => panic!("Expected block, got {:?}", other),
+            },
+            other => panic!("Expected यावत् loop, got {:?}", other),
+        }
+        assert_eq!(body.stmts[1], ast::Statement::Anuvrtti(None));
+    }
+    
+    #[test]
+    fn test_parse_break_with_value() {
+        let stmt = parse_single_statement("निर्गम अ धन १;");
+        assert!(matches!(
+            stmt,
+            ast::Statement::Nirgama { label: None, value: Some(_) }
+        ));
+    }
+    
+    /// Parse a program and return its first item as a mantra
+    fn parse_mantra(input: &str) -> ast::MantraDef {
+        let mut parser = Parser::new(Lexer::new(input));
+        let mut program = parser.parse_program().unwrap();
+        match program.items.remove(0) {
+            ast::Item::Mantra(mantra) => mantra,
+            other => panic!("Expected mantra, got {:?}", other),
+        }
+    }
+    
+    /// Name of a single-segment named type
+    fn type_name(ty: &ast::Type) -> &str {
+        match ty {
+            ast::Type::Named(path, _) => path.segments[0].ident.as_str(),
+            other => panic!("Expected named type, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_mantra_signature_and_body() {
+        let mantra = parse_mantra(
+            "मन्त्र जोड़ना(अ: सङ्ख्या, ब: सङ्ख्या = १) -> सङ्ख्या { सूत्र फल = अ धन ब; फल }",
+        );
+        assert_eq!(mantra.name.as_str(), "जोड़ना");
+        assert_eq!(mantra.params.len(), 2);
+        assert_eq!(mantra.params[0].name.as_str(), "अ");
+        assert_eq!(type_name(&mantra.params[0].ty), "सङ्ख्या");
+        assert!(mantra.params[0].default_value.is_none());
+        assert!(matches!(
+            mantra.params[1].default_value,
+            Some(ast::Expr::Literal(ast::Literal::Int(1), _))
+        ));
+        assert_eq!(type_name(&mantra.return_type), "सङ्ख्या");
+        
+        assert_eq!(mantra.body.stmts.len(), 1);
+        assert!(matches!(mantra.body.stmts[0], ast::Statement::Sutra(_)));
+        assert!(matches!(mantra.body.expr.as_deref(), Some(ast::Expr::Variable(..))));
+    }
+    
+    #[test]
+    fn test_parse_mantra_defaults_to_void() {
+        let mantra = parse_mantra("मन्त्र मुद्रण() { लेखन(\"नमस्ते\"); }");
+        assert!(mantra.params.is_empty());
+        assert_eq!(type_name(&mantra.return_type), "शून्य");
+        assert_eq!(mantra.body.stmts.len(), 1);
+        assert!(mantra.body.expr.is_none());
+    }
+    
+    #[test]
+    fn test_parse_mantra_span_covers_body() {
+        let input = "  मन्त्र शून्यक() -> सङ्ख्या { ० }  ";
+        let mantra = parse_mantra(input);
+        assert_eq!(&input[mantra.span.start..mantra.span.end], "मन्त्र शून्यक() -> सङ्ख्या { ० }");
+    }
+    
+    /// Parse `सूत्र अ: <ty> = ०;` and return the annotation
+    fn parse_annotation(ty: &str) -> ast::Type {
+        let input = format!("सूत्र अ: {} = ०;", ty);
+        match parse_single_statement(&input) {
+            ast::Statement::Sutra(sutra) => sutra.type_annotation.expect("Expected annotation"),
+            other => panic!("Expected sutra, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_generic_types() {
+        match parse_annotation("निधान<शब्द, सूची<सङ्ख्या>>") {
+            ast::Type::Named(path, args) => {
+                assert_eq!(path.segments[0].ident.as_str(), "निधान");
+                assert_eq!(args.len(), 2);
+                assert_eq!(type_name(&args[0]), "शब्द");
+                match &args[1] {
+                    ast::Type::Named(_, inner) => assert_eq!(type_name(&inner[0]), "सङ्ख्या"),
+                    other => panic!("Expected सूची<सङ्ख्या>, got {:?}", other),
+                }
+            }
+            other => panic!("Expected named type, got {:?}", other),
+        }
+        assert_eq!(type_name(&parse_annotation("गणक")), "गणक");
+
+        // `>=` after generic arguments is a closing `>` and then `=`
+        let input = "सूत्र अ: सूची<सङ्ख्या>= [];";
+        match parse_single_statement(input) {
+            ast::Statement::Sutra(sutra) => {
+                assert!(matches!(sutra.type_annotation, Some(ast::Type::Named(_, ref args)) if args.len() == 1));
+                assert!(matches!(sutra.value, ast::Expr::Array(ref elems, _) if elems.is_empty()));
+            }
+            other => panic!("Expected sutra, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_parse_compound_types() {
+        assert!(matches!(parse_annotation("(सङ्ख्या, शब्द)"), ast::Type::Tuple(ref t, _) if t.len() == 2));
+        assert!(matches!(parse_annotation("(सङ्ख्या,)"), ast::Type::Tuple(ref t, _) if t.len() == 1));
+        assert!(matches!(parse_annotation("()"), ast::Type::Tuple(ref t, _) if t.is_empty()));
+        assert_eq!(type_name(&parse_annotation("(सत्यासत्य)")), "सत्यासत्य");
+        
+        match parse_annotation("(सङ्ख्या, सङ्ख्या) -> सत्यासत्य") {
+            ast::Type::Function(params, ret, _) => {
+                assert_eq!(params.len(), 2);
+                assert_eq!(type_name(&ret), "सत्यासत्य");
+            }
+            other => panic!("Expected function type, got {:?}", other),
+        }
+        
+        assert!(matches!(parse_annotation("&शब्द"), ast::Type::Reference(_, false, _)));
+        match parse_annotation("&चल सूची<सङ्ख्या>") {
+            ast::Type::Reference(inner, true, _) => assert_eq!(type_name(&inner), "सूची"),
+            other => panic!("Expected mutable reference, got {:?}", other),
+        }
+        assert!(matches!(parse_annotation("[सङ्ख्या; ४]"), ast::Type::Array(_, Some(_), _)));
+        assert!(matches!(parse_annotation("[सङ्ख्या]"), ast::Type::Slice(..)));
+        assert!(matches!(parse_annotation("!"), ast::Type::Never(_)));
+        assert!(matches!(parse_annotation("_"), ast::Type::Infer(_)));
+    }
+    
+    #[test]
+    fn test_parse_dharma_fields_and_methods() {
+        let input = r#"
+            धर्म गणक {
+                सूत्र परिणाम: सङ्ख्या = ०;
+                सूत्र नाम: शब्द;
+                
+                मन्त्र योजय(आत्मन्, अ: सङ्ख्या) -> सङ्ख्या {
+                    आत्मन्.परिणाम = आत्मन्.परिणाम धन अ;
+                    आत्मन्.परिणाम
+                }
+                
+                मन्त्र नूतन() -> गणक { गणक() }
+            }
+        "#;
+        let mut parser = Parser::new(Lexer::new(input));
+        let mut program = parser.parse_program().unwrap();
+        let ast::Item::Dharma(dharma) = program.items.remove(0) else {
+            panic!("Expected dharma");
+        };
+        
+        assert_eq!(dharma.name.as_str(), "गणक");
+        assert_eq!(dharma.fields.len(), 2);
+        assert_eq!(dharma.fields[0].name.as_str(), "परिणाम");
+        assert_eq!(type_name(&dharma.fields[0].ty), "सङ्ख्या");
+        assert!(dharma.fields[0].default_value.is_some());
+        assert!(dharma.fields[1].default_value.is_none());
+        
+        assert_eq!(dharma.methods.len(), 2);
+        let method = &dharma.methods[0];
+        assert!(method.has_receiver());
+        assert_eq!(type_name(&method.params[0].ty), "गणक");
+        assert_eq!(method.params.len(), 2);
+        match method.body.expr.as_deref() {
+            Some(ast::Expr::FieldAccess(recv, field, _)) => {
+                assert_eq!(field.as_str(), "परिणाम");
+                assert!(matches!(
+                    recv.as_ref(),
+                    ast::Expr::Variable(path, _) if path.segments[0].ident.as_str() == ast::SELF_NAME
+                ));
+            }
+            other => panic!("Expected field access on आत्मन्, got {:?}", other),
+        }
+        assert!(!dharma.methods[1].has_receiver());
+    }
+    
+    #[test]
+    fn test_parse_type_parameters() {
+        let input = "धर्म युग्म<क, ख = शब्द> { सूत्र प्रथम: क; सूत्र द्वितीय: ख; }\n\
+                     मन्त्र महत्तम<त: तुलनीय + गणित::सांख्यिक>(अ: त, ब: त) -> त { अ }";
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program().unwrap();
+        
+        let ast::Item::Dharma(dharma) = &program.items[0] else {
+            panic!("Expected dharma");
+        };
+        let names: Vec<_> = dharma.type_params.iter().map(|param| param.name.as_str()).collect();
+        assert_eq!(names, ["क", "ख"]);
+        assert!(dharma.type_params[0].default.is_none());
+        assert_eq!(type_name(dharma.type_params[1].default.as_ref().unwrap()), "शब्द");
+        assert_eq!(&input[dharma.type_params[1].span.range()], "ख = शब्द");
+        
+        let ast::Item::Mantra(mantra) = &program.items[1] else {
+            panic!("Expected mantra");
+        };
+        let bounds = &mantra.type_params[0].bounds;
+        assert_eq!(bounds.len(), 2);
+        assert_eq!(bounds[0].bound.segments[0].ident.as_str(), "तुलनीय");
+        assert_eq!(&input[bounds[1].span.range()], "गणित::सांख्यिक");
+        assert_eq!(type_name(&mantra.params[0].ty), "त");
+        
+        let mut parser = Parser::new(Lexer::new("मन्त्र फ<त:>() {}"));
+        assert!(parser.parse_program().is_err());
+    }
+    
+    #[test]
+    fn test_parse_gunadharma_and_karyanvaya() {
+        let input = "सार्वजनिक गुणधर्म आकार {\n    मन्त्र क्षेत्रफल(आत्मन्) -> दशांश;\n    मन्त्र नाम(आत्मन्, उपसर्ग: शब्द)।\n}\n\
+                     कार्यान्वय ज्यामिति::वृत्त: आकार {\n    मन्त्र क्षेत्रफल(आत्मन्) -> दशांश { ३.१४ }\n}";
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        
+        let ast::Item::Gunadharma(gunadharma) = &program.items[0] else {
+            panic!("Expected gunadharma");
+        };
+        assert_eq!(gunadharma.visibility, ast::Visibility::Public);
+        let names: Vec<_> = gunadharma.methods.iter().map(|method| method.name.as_str()).collect();
+        assert_eq!(names, ["क्षेत्रफल", "नाम"]);
+        assert!(gunadharma.methods.iter().all(ast::MethodSig::has_receiver));
+        // The receiver has the type of the गुणधर्म
+        assert_eq!(type_name(&gunadharma.methods[0].params[0].ty), "आकार");
+        assert_eq!(type_name(&gunadharma.methods[1].return_type), "शून्य");
+        
+        let ast::Item::Karyanvaya(karyanvaya) = &program.items[1] else {
+            panic!("Expected karyanvaya");
+        };
+        assert_eq!(&input[karyanvaya.dharma.span.range()], "ज्यामिति::वृत्त");
+        assert_eq!(karyanvaya.gunadharma.segments[0].ident.as_str(), "आकार");
+        // The receiver has the dharma's type, written as in the header
+        let ast::Type::Named(receiver, _) = &karyanvaya.methods[0].params[0].ty else {
+            panic!("Expected named type");
+        };
+        assert_eq!(receiver.segments, karyanvaya.dharma.segments);
+        
+        for input in ["गुणधर्म आकार { मन्त्र क() {} }", "कार्यान्वय वृत्त: आकार { सूत्र क: सङ्ख्या; }"] {
+            assert!(Parser::new(Lexer::new(input)).parse_program().is_err(), "{}", input);
+        }
+    }
+    
+    #[test]
+    fn test_receiver_outside_dharma_is_rejected() {
+        let mut parser = Parser::new(Lexer::new("मन्त्र मुक्त(आत्मन्) { }"));
+        assert!(parser.parse_program().is_err());
+    }
+
+    #[test]
+    fn test_parse_inline_and_external_yantra() {
+        let input = "यन्त्र गणित { मन्त्र वर्ग(अ: सङ्ख्या) -> सङ्ख्या { अ गुण अ } } यन्त्र सदिश;";
+        let mut parser = Parser::with_file_id(Lexer::new(input), 3);
+        let program = parser.parse_program().unwrap();
+        assert_eq!(program.items.len(), 2);
+
+        let ast::Item::Yantra(inline) = &program.items[0] else {
+            panic!("Expected yantra");
+        };
+        assert_eq!(inline.name.as_str(), "गणित");
+        assert!(!inline.external);
+        assert!(matches!(&inline.items[0], ast::Item::Mantra(m) if m.name.as_str() == "वर्ग"));
+        assert_eq!(inline.span.file_id, 3);
+
+        let ast::Item::Yantra(external) = &program.items[1] else {
+            panic!("Expected yantra");
+        };
+        assert!(external.external);
+        assert!(external.items.is_empty());
+    }
+
+    #[test]
+    fn test_parse_import_and_qualified_paths() {
+        let mut parser = Parser::new(Lexer::new("आनय गणित::सदिश;"));
+        let program = parser.parse_program().unwrap();
+        let ast::Item::Aanaya(import) = &program.items[0] else {
+            panic!("Expected import");
+        };
+        let segments: Vec<_> = import.path.segments.iter().map(|s| s.ident.as_str()).collect();
+        assert_eq!(segments, ["गणित", "सदिश"]);
+
+        match parse_init("गणित::वर्ग(२)") {
+            ast::Expr::Call(callee, _, _) => match callee.as_ref() {
+                ast::Expr::Variable(path, span) => {
+                    assert_eq!(path.segments.len(), 2);
+                    assert_eq!(path.segments[1].ident.as_str(), "वर्ग");
+                    assert_eq!(path.span, *span);
+                }
+                other => panic!("Expected path, got {:?}", other),
+            },
+            other => panic!("Expected call, got {:?}", other),
+        }
+
+        match parse_annotation("गणित::सदिश<सङ्ख्या>") {
+            ast::Type::Named(path, args) => {
+                assert_eq!(path.segments.len(), 2);
+                assert_eq!(args.len(), 1);
+            }
+            other => panic!("Expected named type, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unterminated_yantra_is_an_error() {
+        let mut parser = Parser::new(Lexer::new("यन्त्र गणित { मन्त्र अ() {}"));
+        assert!(matches!(parser.parse_program(), Err(ParseError::UnexpectedEof(_))));
+    }
+
+    fn parse_recovering(input: &str) -> (ast::Program, Vec<ParseError>) {
+        Parser::new(Lexer::new(input)).parse_program_recovering()
+    }
+
+    #[test]
+    fn test_recover_multiple_statement_errors() {
+        let (program, errors) = parse_recovering("सूत्र = १; सूत्र अ = २; मन्त्र (; सूत्र ब = ३;");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(program.items.len(), 4);
+
+        let is_error = |item: &ast::Item| {
+            matches!(item, ast::Item::Praarabdha(stmts)
+                if matches!(stmts.as_slice(), [ast::Statement::Expr(e)] if matches!(**e, ast::Expr::Error(_))))
+        };
+        assert!(is_error(&program.items[0]));
+        assert!(!is_error(&program.items[1]));
+        assert!(is_error(&program.items[2]));
+        assert!(!is_error(&program.items[3]));
+    }
+
+    #[test]
+    fn test_recover_inside_block_keeps_mantra() {
+        let input = r#"
+            मन्त्र क() -> सङ्ख्या {
+                सूत्र अ = ;
+                अ धन );
+                १
+            }
+            मन्त्र ख() {}
+        "#;
+        let (program, errors) = parse_recovering(input);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(program.items.len(), 2);
+
+        let ast::Item::Mantra(mantra) = &program.items[0] else {
+            panic!("Expected mantra");
+        };
+        assert_eq!(mantra.body.stmts.len(), 2);
+        assert!(mantra.body.stmts.iter().all(|stmt| matches!(
+            stmt,
+            ast::Statement::Expr(e) if matches!(**e, ast::Expr::Error(_))
+        )));
+        assert!(mantra.body.expr.is_some());
+    }
+
+    #[test]
+    fn test_recover_missing_type_and_bad_member() {
+        let input = "धर्म बिन्दु { सूत्र क: ; ; सूत्र ख: सङ्ख्या; } मन्त्र ग(अ: ) {}";
+        let (program, errors) = parse_recovering(input);
+        assert_eq!(errors.len(), 3);
+
+        let ast::Item::Dharma(dharma) = &program.items[0] else {
+            panic!("Expected dharma");
+        };
+        assert_eq!(dharma.fields.len(), 2);
+        assert_eq!(dharma.fields[0].ty, ast::Type::Error);
+
+        let ast::Item::Mantra(mantra) = &program.items[1] else {
+            panic!("Expected mantra");
+        };
+        assert_eq!(mantra.params[0].ty, ast::Type::Error);
+    }
+
+    #[test]
+    fn test_unterminated_input_reports_eof_once() {
+        let (_, errors) = parse_recovering("मन्त्र क() { यदि सत्य { सूत्र अ = १;");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ParseError::UnexpectedEof(_)));
+    }
+
+    #[test]
+    fn test_parse_match_arms_and_guards() {
+        let input = r#"
+            विकल्प बिन्दु {
+                रेखा::बिन्दु { क: ०, .. } => "मूल",
+                (अ, _) यदि अ लघुत्तर ० => { "ऋणात्मक" }
+                ऋण १ => "एक",
+                शेषः => "अन्य"
+            }
+        "#;
+        let ast::Statement::Expr(expr) = parse_single_statement(input) else {
+            panic!("Expected expression statement");
+        };
+        let ast::Expr::Match(scrutinee, arms, _) = *expr else {
+            panic!("Expected match");
+        };
+        assert!(matches!(*scrutinee, ast::Expr::Variable(..)));
+        assert_eq!(arms.len(), 4);
+
+        match &arms[0].pattern {
+            ast::Pattern::Struct { path, fields, rest, .. } => {
+                assert_eq!(path.segments.len(), 2);
+                assert_eq!(fields[0].name.as_str(), "क");
+                assert!(matches!(fields[0].pattern, ast::Pattern::Literal(ast::Literal::Int(0))));
+                assert!(*rest);
+            }
+            other => panic!("Expected struct pattern, got {:?}", other),
+        }
+
+        match &arms[1].pattern {
+            ast::Pattern::Tuple(elems, _) => {
+                assert!(matches!(&elems[0], ast::Pattern::Bind { name, .. } if name.as_str() == "अ"));
+                assert!(matches!(elems[1], ast::Pattern::Any(_)));
+            }
+            other => panic!("Expected tuple pattern, got {:?}", other),
+        }
+        assert!(matches!(arms[1].guard, Some(ast::Guard::If(_))));
+        assert!(matches!(arms[1].body, ast::Expr::Block(..)));
+
+        assert!(matches!(arms[2].pattern, ast::Pattern::Literal(ast::Literal::Int(-1))));
+        assert!(arms[0].guard.is_none());
+        assert!(matches!(&arms[3].pattern, ast::Pattern::Bind { name, .. } if name.as_str() == "शेषः"));
+    }
+
+    #[test]
+    fn test_parse_destructuring_declarations() {
+        match parse_single_statement("सूत्र (अ, (ब, स)) = युग्म;") {
+            ast::Statement::Sutra(sutra) => match sutra.pattern {
+                ast::Pattern::Tuple(elems, _) => {
+                    assert_eq!(elems.len(), 2);
+                    assert!(matches!(&elems[1], ast::Pattern::Tuple(inner, _) if inner.len() == 2));
+                }
+                other => panic!("Expected tuple pattern, got {:?}", other),
+            },
+            other => panic!("Expected sutra, got {:?}", other),
+        }
+
+        match parse_single_statement("प्रत्येक बिन्दु { क, ख: य } मध्ये बिन्दवः { }") {
+            ast::Statement::Pratyeka { pattern: ast::Pattern::Struct { fields, rest, .. }, .. } => {
+                assert_eq!(fields.len(), 2);
+                assert!(matches!(&fields[0].pattern, ast::Pattern::Bind { name, .. } if name.as_str() == "क"));
+                assert!(matches!(&fields[1].pattern, ast::Pattern::Bind { name, .. } if name.as_str() == "य"));
+                assert!(!rest);
+            }
+            other => panic!("Expected destructuring loop, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_match_statement_needs_no_semicolon() {
+        let input = "मन्त्र क(अ: सङ्ख्या) { विकल्प अ { _ => अ } सूत्र ब = अ; ब }";
+        let mantra = parse_mantra(input);
+        assert_eq!(mantra.body.stmts.len(), 2);
+        assert!(mantra.body.expr.is_some());
+    }
+
+    #[test]
+    fn test_parse_lambdas() {
+        match parse_init("अङ्काः.मानचित्र(|अ| अ गुण अ)") {
+            ast::Expr::MethodCall(_, _, _, args, _) => match &args[0] {
+                ast::Expr::Lambda(params, body, _) => {
+                    assert_eq!(params.len(), 1);
+                    assert!(matches!(params[0].ty, ast::Type::Infer(_)));
+                    assert!(matches!(**body, ast::Expr::Binary(_, ast::BinaryOp::Multiply, _, _)));
+                }
+                other => panic!("Expected lambda, got {:?}", other),
+            },
+            other => panic!("Expected method call, got {:?}", other),
+        }
+
+        match parse_init("|अ: सङ्ख्या, ब| { सूत्र स = अ; स धन ब }") {
+            ast::Expr::Lambda(params, body, _) => {
+                assert_eq!(type_name(&params[0].ty), "सङ्ख्या");
+                assert!(matches!(*body, ast::Expr::Block(..)));
+            }
+            other => panic!("Expected lambda, got {:?}", other),
+        }
+
+        assert!(matches!(parse_init("|| ५"), ast::Expr::Lambda(params, _, _) if params.is_empty()));
+        assert!(matches!(parse_init("अ || ब"), ast::Expr::Binary(_, ast::BinaryOp::Or, _, _)));
+        // The word `वा` is only ever the operator
+        assert!(Parser::new(Lexer::new("सूत्र अ = वा ५;")).parse_program().is_err());
+    }
+
+    #[test]
+    fn test_parse_function_typed_parameter() {
+        let mantra = parse_mantra("मन्त्र प्रयोज्य(फ: (सङ्ख्या) -> सङ्ख्या, अ: सङ्ख्या) -> सङ्ख्या { फ(अ) }");
+        assert!(matches!(mantra.params[0].ty, ast::Type::Function(..)));
+        assert!(matches!(mantra.body.expr.as_deref(), Some(ast::Expr::Call(..))));
+    }
+
+    #[test]
+    fn test_error_spans_point_at_offending_token() {
+        let input = "सूत्र अ = १;\nसूत्र ब = ;";
+        let mut parser = Parser::with_file_id(Lexer::new(input), 4);
+        let err = parser.parse_program().unwrap_err();
+        let span = err.span().expect("token errors carry a span");
+        assert_eq!(span.file_id, 4);
+        assert_eq!(&input[span.range()], ";");
+        assert_eq!(span.start, input.rfind(';').unwrap());
+
+        let err = Parser::new(Lexer::new("मन्त्र फ() {")).parse_program().unwrap_err();
+        assert_eq!(err.span(), Some(Span::new(26, 26, 0)));
+    }
+
+    #[test]
+    fn test_statement_spans_cover_source() {
+        let input = "सूत्र अ = १;  सूत्र ब = अ;";
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let ast::Item::Praarabdha(stmts) = &program.items[1] else {
+            panic!("Expected statement block");
+        };
+        let ast::Statement::Sutra(sutra) = &stmts[0] else {
+            panic!("Expected sutra");
+        };
+        assert_eq!(&input[sutra.span.range()], "सूत्र ब = अ;");
+    }
+
+    #[test]
+    fn test_parse_float_literals_and_type() {
+        assert!(matches!(parse_init("१२.२५"), ast::Expr::Literal(ast::Literal::Float(x), _) if x == 12.25));
+        assert!(matches!(parse_init("2.5e3"), ast::Expr::Literal(ast::Literal::Float(x), _) if x == 2500.0));
+
+        let input = "सूत्र पाई: दशांश = ३.१४; विकल्प पाई { ऋण १.५ => सत्य, _ => असत्य };";
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let ast::Item::Praarabdha(stmts) = &program.items[0] else {
+            panic!("Expected statement block");
+        };
+        let ast::Statement::Sutra(sutra) = &stmts[0] else {
+            panic!("Expected sutra");
+        };
+        assert!(matches!(&sutra.type_annotation, Some(ast::Type::Named(path, _)) if path.segments[0].ident.as_str() == "दशांश"));
+        let ast::Item::Praarabdha(stmts) = &program.items[1] else {
+            panic!("Expected statement block");
+        };
+        let ast::Statement::Expr(expr) = &stmts[0] else {
+            panic!("Expected match statement");
+        };
+        let ast::Expr::Match(_, arms, _) = expr.as_ref() else {
+            panic!("Expected match");
+        };
+        assert!(matches!(arms[0].pattern, ast::Pattern::Literal(ast::Literal::Float(x)) if x == -1.5));
+    }
+
+    #[test]
+    fn test_interpolated_string_desugars_to_concatenation() {
+        let input = r#"सूत्र फल = "{गणना} अङ्काः, {अ धन १}!";"#;
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let ast::Item::Praarabdha(stmts) = &program.items[0] else {
+            panic!("Expected statement block");
+        };
+        let ast::Statement::Sutra(sutra) = &stmts[0] else {
+            panic!("Expected sutra");
+        };
+
+        // (((("" धन गणना) धन " अङ्काः, ") धन (अ धन १)) धन "!")
+        let ast::Expr::Binary(rest, ast::BinaryOp::Add, last, _) = &sutra.value else {
+            panic!("Expected concatenation");
+        };
+        assert!(matches!(last.as_ref(), ast::Expr::Literal(ast::Literal::String(s), _) if s == "!"));
+        assert_eq!(&input[last.span().range()], "!");
+        assert_eq!(&input[sutra.value.span().range()], "{गणना} अङ्काः, {अ धन १}!");
+        let ast::Expr::Binary(rest, ast::BinaryOp::Add, sum, _) = rest.as_ref() else {
+            panic!("Expected concatenation");
+        };
+        assert_eq!(&input[sum.span().range()], "अ धन १");
+        let ast::Expr::Binary(rest, ast::BinaryOp::Add, text, span) = rest.as_ref() else {
+            panic!("Expected concatenation");
+        };
+        assert_eq!(&input[text.span().range()], " अङ्काः, ");
+        assert_eq!(&input[span.range()], "{गणना} अङ्काः, ");
+        let ast::Expr::Binary(empty, ast::BinaryOp::Add, count, _) = rest.as_ref() else {
+            panic!("Expected concatenation");
+        };
+        assert!(matches!(empty.as_ref(), ast::Expr::Literal(ast::Literal::String(s), _) if s.is_empty()));
+        assert!(empty.span().range().is_empty());
+        assert_eq!(&input[count.span().range()], "गणना");
+    }
+
+    #[test]
+    fn test_lexer_errors_are_reported() {
+        let input = r#"सूत्र अ = "\q";"#;
+        let err = Parser::new(Lexer::new(input)).parse_program().unwrap_err();
+        match err {
+            ParseError::InvalidToken { error, span } => {
+                assert_eq!(error, LexError::InvalidEscape('q'));
+                assert_eq!(&input[span.range()], r#""\q""#);
+            }
+            other => panic!("Expected InvalidToken, got {:?}", other),
+        }
+
+        let err = Parser::new(Lexer::new(r#"सूत्र अ = "{क ख}";"#)).parse_program().unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedToken { expected, .. } if expected == "end of interpolated expression"));
+    }
+
+    #[test]
+    fn test_parse_ascii_and_typed_integers() {
+        assert!(matches!(parse_init("0x2A धन 42"), ast::Expr::Binary(l, _, r, _)
+            if matches!((l.as_ref(), r.as_ref()), (ast::Expr::Literal(ast::Literal::Int(42), _), ast::Expr::Literal(ast::Literal::Int(42), _)))));
+        assert!(matches!(parse_init("२५५u8"), ast::Expr::Literal(ast::Literal::TypedInt(255, ast::IntegerWidth::U8), _)));
+        
+        // Signed minimums are written negated
+        assert!(matches!(parse_init("ऋण 9223372036854775808"), ast::Expr::Literal(ast::Literal::Int(i64::MIN), _)));
+        assert!(matches!(parse_init("ऋण 128i8"), ast::Expr::Unary(ast::UnaryOp::Negate, operand, _)
+            if matches!(operand.as_ref(), ast::Expr::Literal(ast::Literal::TypedInt(128, ast::IntegerWidth::I8), _))));
+        let program = Parser::new(Lexer::new("विकल्प अ { ऋण 9223372036854775808 => १, _ => २ }")).parse_program();
+        assert!(program.is_ok(), "{:?}", program);
+        for (input, literal) in [("सूत्र अ = 128i8;", "128i8"), ("सूत्र अ = 9223372036854775808 धन १;", "9223372036854775808")] {
+            match Parser::new(Lexer::new(input)).parse_program().unwrap_err() {
+                ParseError::InvalidToken { error, span } => {
+                    assert!(matches!(error, LexError::IntegerOverflow { .. }), "{:?}", error);
+                    assert_eq!(&input[span.range()], literal);
+                }
+                other => panic!("Expected InvalidToken, got {:?}", other),
+            }
+        }
+
+        let input = "सूत्र अ = 300u8;";
+        match Parser::new(Lexer::new(input)).parse_program().unwrap_err() {
+            ParseError::InvalidToken { error, span } => {
+                assert_eq!(error.to_string(), "integer literal 300u8 does not fit in u8");
+                assert_eq!(&input[span.range()], "300u8");
+            }
+            other => panic!("Expected InvalidToken, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_negated_typed_integer_patterns() {
+        let input = "विकल्प अ { ऋण १२८i8 => सत्य, ऋण ५i8 => सत्य, १२७i8 => असत्य, _ => असत्य };";
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let ast::Item::Praarabdha(stmts) = &program.items[0] else {
+            panic!("Expected statement block");
+        };
+        let ast::Statement::Expr(expr) = &stmts[0] else {
+            panic!("Expected match statement");
+        };
+        let ast::Expr::Match(_, arms, _) = expr.as_ref() else {
+            panic!("Expected match");
+        };
+        assert_eq!(arms[0].pattern, ast::Pattern::Literal(ast::Literal::NegativeTypedInt(128, ast::IntegerWidth::I8)));
+        assert_eq!(arms[1].pattern, ast::Pattern::Literal(ast::Literal::NegativeTypedInt(5, ast::IntegerWidth::I8)));
+        assert_eq!(arms[2].pattern, ast::Pattern::Literal(ast::Literal::TypedInt(127, ast::IntegerWidth::I8)));
+
+        // The minimum's magnitude is still only valid after `ऋण`
+        let input = "विकल्प अ { १२८i8 => सत्य, _ => असत्य };";
+        match Parser::new(Lexer::new(input)).parse_program().unwrap_err() {
+            ParseError::InvalidToken { error, span } => {
+                assert_eq!(error.to_string(), "integer literal 128i8 does not fit in i8");
+                assert_eq!(&input[span.range()], "१२८i8");
+            }
+            other => panic!("Expected InvalidToken, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_romanized_and_devanagari_names_agree() {
+        use vaaktra_lexer::{Scheme, TransliteratingLexer};
+
+        let input = "sUtra phala = 1; phala धन फल;";
+        let program = Parser::new(TransliteratingLexer::new(input, Scheme::HarvardKyoto))
+            .parse_program()
+            .unwrap();
+        let ast::Item::Praarabdha(stmts) = &program.items[1] else {
+            panic!("Expected statement block");
+        };
+        let ast::Statement::Expr(expr) = &stmts[0] else {
+            panic!("Expected expression statement");
+        };
+        let ast::Expr::Binary(l, _, r, _) = expr.as_ref() else {
+            panic!("Expected binary expression, got {:?}", expr);
+        };
+        for side in [l, r] {
+            assert!(matches!(side.as_ref(), ast::Expr::Variable(path, _) if &*path.segments[0].ident == "फल"));
+        }
+        let ast::Item::Praarabdha(stmts) = &program.items[0] else {
+            panic!("Expected statement block");
+        };
+        let ast::Statement::Sutra(sutra) = &stmts[0] else {
+            panic!("Expected sutra");
+        };
+        assert_eq!(&input[sutra.span.range()], "sUtra phala = 1;");
+    }
+
+    #[test]
+    fn test_parse_ascii_and_word_operators() {
+        use ast::BinaryOp::*;
+        let op = |expr: &ast::Expr| match expr {
+            ast::Expr::Binary(_, op, _, _) => *op,
+            other => panic!("Expected binary expression, got {:?}", other),
+        };
+        let sides = |expr: ast::Expr| match expr {
+            ast::Expr::Binary(l, _, r, _) => (*l, *r),
+            other => panic!("Expected binary expression, got {:?}", other),
+        };
+
+        let (l, r) = sides(parse_init("अ + ब * ग"));
+        assert!(matches!(l, ast::Expr::Variable(..)));
+        assert_eq!(op(&r), Multiply);
+
+        for src in ["अ >= ब || ग && !घ", "अ महत्तर व समान ब वा ग च न घ"] {
+            let expr = parse_init(src);
+            assert_eq!(op(&expr), Or);
+            let (l, r) = sides(expr);
+            assert_eq!(op(&l), GreaterEqual);
+            assert_eq!(op(&r), And);
+        }
+        assert_eq!(op(&parse_init("अ <= ब")), LessEqual);
+        assert_eq!(op(&parse_init("अ लघुत्तर व समान ब")), LessEqual);
+        assert_eq!(op(&parse_init("अ != ब % २")), NotEqual);
+    }
+
+    #[test]
+    fn test_parse_danda_terminators() {
+        let input = "सूत्र अ = १।\nमन्त्र फ(ब: सङ्ख्या) -> सङ्ख्या {\n    सूत्र ग = ब॥\n    यदि ग { निर्गम। } ॥\n    ग\n} ॥\nधर्म द { सूत्र क: सङ्ख्या। मन्त्र ख() {} ॥ }\nआनय गणित::सदिश॥";
+        let danda = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let ascii = input.replace('।', ";").replace("॥", ";").replace("} ;", "}");
+        let plain = Parser::new(Lexer::new(&ascii)).parse_program().unwrap();
+        assert_eq!(danda.items.len(), 4);
+        assert_eq!(danda.items.len(), plain.items.len());
+        let ast::Item::Mantra(mantra) = &danda.items[1] else {
+            panic!("Expected mantra");
+        };
+        assert_eq!(mantra.body.stmts.len(), 2);
+        assert!(mantra.body.expr.is_some());
+
+        // `॥` on its own line is an empty statement, like `;`
+        let program = Parser::new(Lexer::new("॥ सूत्र अ = १ ॥ ॥")).parse_program().unwrap();
+        assert_eq!(program.items.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_modifiers() {
+        let input = "सार्वजनिक असमकाल मन्त्र फ() { प्रत्याहर भ.प्रतीक्षा; }\nअसुरक्षित मन्त्र ग() { प्रत्याहर; }\nमन्त्र घ() {}\nसार्वजनिक धर्म द {\n    सार्वजनिक सूत्र क: सङ्ख्या;\n    सूत्र ख: सङ्ख्या;\n    असमकाल सार्वजनिक मन्त्र म(आत्मन्) {}\n}";
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let mantras: Vec<_> = program.items.iter().filter_map(|item| match item {
+            ast::Item::Mantra(mantra) => Some(mantra),
+            _ => None,
+        }).collect();
+        let flags: Vec<_> = mantras.iter().map(|m| (m.visibility.clone(), m.is_async, m.is_unsafe)).collect();
+        assert_eq!(flags, [
+            (ast::Visibility::Public, true, false),
+            (ast::Visibility::Private, false, true),
+            (ast::Visibility::Private, false, false),
+        ]);
+        // The span includes the modifiers
+        assert_eq!(mantras[0].span.start, 0);
+
+        match mantras[0].body.stmts.as_slice() {
+            [ast::Statement::Pratyahara(Some(value))] => assert!(matches!(**value, ast::Expr::Await(..))),
+            other => panic!("Expected return of an await, got {:?}", other),
+        }
+        assert_eq!(mantras[1].body.stmts, [ast::Statement::Pratyahara(None)]);
+
+        let ast::Item::Dharma(dharma) = &program.items[3] else {
+            panic!("Expected dharma");
+        };
+        assert_eq!(dharma.visibility, ast::Visibility::Public);
+        let fields: Vec<_> = dharma.fields.iter().map(|f| f.visibility.clone()).collect();
+        assert_eq!(fields, [ast::Visibility::Public, ast::Visibility::Private]);
+        assert_eq!(dharma.methods[0].visibility, ast::Visibility::Public);
+        assert!(dharma.methods[0].is_async && dharma.methods[0].has_receiver());
+    }
+
+    #[test]
+    fn test_parse_mutable_bindings() {
+        let program = Parser::new(Lexer::new("सूत्र चल अ = ०; सूत्र ब = १; सूत्र (चल ग, घ) = (१, २);"))
+            .parse_program()
+            .unwrap();
+        let sutras: Vec<_> = program.items.iter().map(|item| match item {
+            ast::Item::Praarabdha(stmts) => match &stmts[0] {
+                ast::Statement::Sutra(sutra) => sutra,
+                other => panic!("Expected sutra, got {:?}", other),
+            },
+            other => panic!("Expected statement, got {:?}", other),
+        }).collect();
+        let mutable: Vec<_> = sutras.iter().map(|s| s.is_mutable).collect();
+        assert_eq!(mutable, [true, false, false]);
+        let ast::Pattern::Tuple(elems, _) = &sutras[2].pattern else {
+            panic!("Expected tuple pattern");
+        };
+        assert!(matches!(elems[0], ast::Pattern::Bind { mutable: true, .. }));
+        assert!(matches!(elems[1], ast::Pattern::Bind { mutable: false, .. }));
+    }
+
+    #[test]
+    fn test_parse_async_blocks() {
+        assert!(matches!(parse_init("असमकाल { १ }.प्रतीक्षा"), ast::Expr::Await(..)));
+        let program = Parser::new(Lexer::new("मन्त्र फ() { असमकाल { १ } }")).parse_program().unwrap();
+        let ast::Item::Mantra(mantra) = &program.items[0] else {
+            panic!("Expected mantra");
+        };
+        assert!(matches!(mantra.body.expr.as_deref(), Some(ast::Expr::Async(..))));
+    }
+
+    #[test]
+    fn test_parse_reserved_and_misplaced_keywords() {
+        for input in ["सूत्र प्रकार = १;", "पाश {}", "सूत्र अ = यत्न;"] {
+            match Parser::new(Lexer::new(input)).parse_program() {
+                Err(ParseError::ReservedKeyword { word, .. }) => assert!(input.contains(&word)),
+                other => panic!("Expected reserved keyword error for {}, got {:?}", input, other),
+            }
+        }
+        match Parser::new(Lexer::new("असमकाल धर्म द {}")).parse_program() {
+            Err(ParseError::InvalidModifier { modifier, span, .. }) => {
+                assert_eq!(modifier, "असमकाल");
+                assert_eq!(span.start, 0);
+            }
+            other => panic!("Expected invalid modifier error, got {:?}", other),
+        }
+        assert!(Parser::new(Lexer::new("सार्वजनिक सार्वजनिक मन्त्र फ() {}")).parse_program().is_err());
+    }
+
     // More tests will be added as we implement more parsing functionality
 }
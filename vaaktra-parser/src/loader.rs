@@ -0,0 +1,282 @@
+//! Loading multi-file Vāktra programs
+//!
+//! A `यन्त्र नाम;` declaration refers to the file `नाम.vk` next to the
+//! declaring file. Modules declared inside `नाम.vk`, whether inline or
+//! external, resolve relative to the directory `नाम/`, so the directory
+//! tree mirrors the module tree:
+//!
+//! ```text
+//! मुख्य.vk          यन्त्र गणित;
+//! गणित.vk           यन्त्र सदिश;
+//! गणित/सदिश.vk
+//! ```
+//!
+//! Every loaded file gets a file id equal to its index in the loader's
+//! [`SourceMap`], and all spans parsed from it carry that id.
+//!
+//! Loading does not stop at the first error: each file is parsed with
+//! recovery and the modules of a partial program are still loaded, so one
+//! run reports the errors of every file.
+
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use vaaktra_lexer::Lexer;
+
+use crate::ast;
+use crate::span::{Location, SourceMap};
+use crate::{ParseError, Parser};
+
+pub use crate::span::SourceFile;
+
+/// File extension of Vāktra source files
+pub const SOURCE_EXTENSION: &str = "vk";
+
+/// Errors raised while loading a program and its modules
+#[derive(Debug, Error)]
+pub enum LoadError {
+    #[error("Cannot read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("{}:{location}: {error}", path.display())]
+    Parse {
+        path: PathBuf,
+        location: Location,
+        error: Box<ParseError>,
+    },
+
+    #[error("Module {name} not found: expected {}", path.display())]
+    ModuleNotFound { name: ast::RcStr, path: PathBuf },
+}
+
+/// Loads an entry file and every external module it declares
+#[derive(Debug, Default)]
+pub struct ModuleLoader {
+    source_map: SourceMap,
+    errors: Vec<LoadError>,
+}
+
+impl ModuleLoader {
+    /// Create a loader with no files loaded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load `entry` and fill in the items of its external modules.
+    /// Fails with every error found in any file, in the order loaded.
+    pub fn load_program(&mut self, entry: impl AsRef<Path>) -> Result<ast::Program, Vec<LoadError>> {
+        let entry = entry.as_ref();
+        let dir = entry.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+        let program = self.load_file(entry, &dir);
+        let errors = std::mem::take(&mut self.errors);
+        match program {
+            Some(program) if errors.is_empty() => Ok(program),
+            _ => Err(errors),
+        }
+    }
+
+    /// All files loaded so far, indexed by file id
+    pub fn files(&self) -> &[SourceFile] {
+        self.source_map.files()
+    }
+
+    /// The file with the given id
+    pub fn file(&self, file_id: u32) -> Option<&SourceFile> {
+        self.source_map.file(file_id)
+    }
+
+    /// Source map for resolving the spans of everything loaded
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /// Parse one file; its modules resolve relative to `module_dir`.
+    /// Returns `None` only if the file cannot be read.
+    fn load_file(&mut self, path: &Path, module_dir: &Path) -> Option<ast::Program> {
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(source) => {
+                self.errors.push(LoadError::Io { path: path.to_path_buf(), source });
+                return None;
+            }
+        };
+
+        // Register the file before parsing so errors in it can be located
+        let file_id = self.source_map.add_file(path, source);
+        let file = &self.source_map.files()[file_id as usize];
+        let (mut program, errors) = Parser::with_file_id(Lexer::new(&file.source), file_id).parse_program_recovering();
+        self.errors.extend(errors.into_iter().map(|error| LoadError::Parse {
+            path: path.to_path_buf(),
+            location: file.location(error.span().map_or(0, |span| span.start)),
+            error: Box::new(error),
+        }));
+
+        self.resolve_modules(&mut program.items, module_dir);
+        Some(program)
+    }
+
+    /// Load the contents of every external module among `items`
+    fn resolve_modules(&mut self, items: &mut [ast::Item], module_dir: &Path) {
+        for item in items {
+            let ast::Item::Yantra(yantra) = item else {
+                continue;
+            };
+            let nested_dir = module_dir.join(yantra.name.as_str());
+
+            if yantra.external {
+                let path = module_dir.join(format!("{}.{}", yantra.name, SOURCE_EXTENSION));
+                if !path.is_file() {
+                    self.errors.push(LoadError::ModuleNotFound {
+                        name: yantra.name.clone(),
+                        path,
+                    });
+                    continue;
+                }
+                if let Some(program) = self.load_file(&path, &nested_dir) {
+                    yantra.items = program.items;
+                }
+            } else {
+                self.resolve_modules(&mut yantra.items, &nested_dir);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory under the system temp dir, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// A fresh directory under the system temp dir for one test
+    fn temp_dir(name: &str) -> TempDir {
+        let dir = std::env::temp_dir().join(format!("vaaktra-loader-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    fn write(path: PathBuf, source: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+    }
+
+    fn yantra(item: &ast::Item) -> &ast::YantraDef {
+        match item {
+            ast::Item::Yantra(yantra) => yantra,
+            other => panic!("expected yantra, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_load_nested_external_modules() {
+        let dir = temp_dir("nested");
+        write(dir.join("मुख्य.vk"), "यन्त्र गणित;\nआनय गणित::सदिश::योग;");
+        write(dir.join("गणित.vk"), "यन्त्र सदिश;");
+        write(dir.join("गणित").join("सदिश.vk"), "मन्त्र योग() {}");
+
+        let mut loader = ModuleLoader::new();
+        let program = loader.load_program(dir.join("मुख्य.vk")).unwrap();
+
+        let ganita = yantra(&program.items[0]);
+        assert!(ganita.external);
+        let sadisha = yantra(&ganita.items[0]);
+        match &sadisha.items[0] {
+            ast::Item::Mantra(mantra) => {
+                assert_eq!(mantra.name.as_str(), "योग");
+                assert_eq!(mantra.span.file_id, 2);
+            }
+            other => panic!("expected mantra, got {:?}", other),
+        }
+
+        assert_eq!(loader.files().len(), 3);
+        assert_eq!(loader.file(2).unwrap().path, dir.join("गणित").join("सदिश.vk"));
+    }
+
+    #[test]
+    fn test_inline_module_resolves_in_subdirectory() {
+        let dir = temp_dir("inline");
+        write(dir.join("मुख्य.vk"), "यन्त्र बाह्य { यन्त्र आन्तर; }");
+        write(dir.join("बाह्य").join("आन्तर.vk"), "सूत्र क = १;");
+
+        let program = ModuleLoader::new().load_program(dir.join("मुख्य.vk")).unwrap();
+        let inner = yantra(&yantra(&program.items[0]).items[0]);
+        assert_eq!(inner.items.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_module_is_reported() {
+        let dir = temp_dir("missing");
+        write(dir.join("मुख्य.vk"), "यन्त्र अनुपस्थित;");
+
+        let errors = ModuleLoader::new().load_program(dir.join("मुख्य.vk")).unwrap_err();
+        match errors.into_iter().next().unwrap() {
+            LoadError::ModuleNotFound { name, path } => {
+                assert_eq!(name.as_str(), "अनुपस्थित");
+                assert_eq!(path, dir.join("अनुपस्थित.vk"));
+            }
+            other => panic!("expected ModuleNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_in_module_has_location() {
+        let dir = temp_dir("location");
+        write(dir.join("मुख्य.vk"), "यन्त्र गणित;");
+        write(dir.join("गणित.vk"), "मन्त्र योग() {}
+सूत्र क्षेत्र = ;");
+
+        let errors = ModuleLoader::new().load_program(dir.join("मुख्य.vk")).unwrap_err();
+        let err = &errors[0];
+        match err {
+            LoadError::Parse { path, location, error } => {
+                assert_eq!(path, &dir.join("गणित.vk"));
+                assert_eq!(location.line, 2);
+                assert_eq!(error.span().unwrap().file_id, 1);
+            }
+            other => panic!("expected Parse, got {:?}", other),
+        }
+        assert!(err.to_string().contains("गणित.vk:2:"));
+    }
+
+    #[test]
+    fn test_errors_in_every_file_are_reported() {
+        let dir = temp_dir("recover");
+        write(dir.join("मुख्य.vk"), "यन्त्र गणित;\nसूत्र क = ;\nयन्त्र अनुपस्थित;\nसूत्र ख = ;");
+        write(dir.join("गणित.vk"), "सूत्र ग = ;\nमन्त्र योग() {}");
+
+        let mut loader = ModuleLoader::new();
+        let errors = loader.load_program(dir.join("मुख्य.vk")).unwrap_err();
+        let reported: Vec<(PathBuf, usize)> = errors.iter().map(|err| match err {
+            LoadError::Parse { path, location, .. } => (path.clone(), location.line),
+            LoadError::ModuleNotFound { path, .. } => (path.clone(), 0),
+            other => panic!("unexpected error {:?}", other),
+        }).collect();
+        assert_eq!(reported, [
+            (dir.join("मुख्य.vk"), 2),
+            (dir.join("मुख्य.vk"), 4),
+            (dir.join("गणित.vk"), 1),
+            (dir.join("अनुपस्थित.vk"), 0),
+        ]);
+        assert_eq!(loader.files().len(), 2);
+    }
+}
@@ -0,0 +1,152 @@
+//! Pattern parsing for Vāktra
+//!
+//! ```text
+//! pattern := '_' | literal | 'चल'? ident | tuple | struct
+//! literal := number | 'ऋण' number | string | 'सत्य' | 'असत्य'
+//! tuple   := '(' (pattern (',' pattern)* ','?)? ')'
+//! struct  := path '{' (field (',' field)* (',' '..')? ','?)? '}'
+//! field   := 'चल'? ident | ident ':' pattern
+//! ```
+//!
+//! Patterns appear in `सूत्र` declarations, `प्रत्येक` loops and the arms
+//! of a `विकल्प` expression. A field written without `: pattern` binds a
+//! variable of the same name, and a multi-segment path without braces
+//! matches a unit dharma. `चल` makes a binding mutable.
+
+use vaaktra_lexer::Token;
+
+use crate::ast::{self, Literal, Pattern};
+use crate::expr::negative_only;
+use crate::{ParseError, ParseResult, Parser};
+
+/// A binding pattern for `name`
+fn bind(name: ast::RcStr, mutable: bool, span: ast::Span) -> Pattern {
+    Pattern::Bind {
+        name,
+        mutable,
+        by_ref: false,
+        subpattern: None,
+        span,
+    }
+}
+
+impl<I> Parser<I>
+where
+    I: Iterator<Item = (Token, std::ops::Range<usize>)>,
+{
+    /// Parse a pattern
+    pub(crate) fn parse_pattern(&mut self) -> ParseResult<Pattern> {
+        let start = self.next_start();
+
+        match self.peek() {
+            Some(Token::Ident(name)) if name == "_" => {
+                self.next();
+                Ok(Pattern::Any(self.span_from(start)))
+            }
+            Some(Token::Ident(_)) => {
+                let mut path = self.parse_path()?;
+                if self.matches(Token::LBrace) {
+                    return self.parse_struct_pattern(path, start);
+                }
+                if path.segments.len() > 1 {
+                    return Ok(Pattern::Struct {
+                        path,
+                        fields: Vec::new(),
+                        rest: false,
+                        span: self.span_from(start),
+                    });
+                }
+                let name = path.segments.swap_remove(0).ident;
+                Ok(bind(name, false, self.span_from(start)))
+            }
+            Some(Token::Mut) => {
+                self.next();
+                let name = self.parse_identifier()?;
+                Ok(bind(name, true, self.span_from(start)))
+            }
+            Some(Token::LParen) => {
+                self.next();
+                let mut elems = Vec::new();
+                let mut trailing_comma = false;
+                while !self.matches(Token::RParen) {
+                    elems.push(self.parse_pattern()?);
+                    trailing_comma = self.matches(Token::Comma);
+                    if !trailing_comma {
+                        self.expect(Token::RParen)?;
+                        break;
+                    }
+                }
+                // `(p)` is just a parenthesized pattern; `(p,)` is a tuple
+                if elems.len() == 1 && !trailing_comma {
+                    return Ok(elems.remove(0));
+                }
+                Ok(Pattern::Tuple(elems, self.span_from(start)))
+            }
+            _ => Ok(Pattern::Literal(self.parse_literal_pattern()?)),
+        }
+    }
+
+    /// Parse the fields of `path { ... }`; the `{` is already consumed
+    fn parse_struct_pattern(&mut self, path: ast::Path, start: usize) -> ParseResult<Pattern> {
+        let mut fields = Vec::new();
+        let mut rest = false;
+
+        while !self.matches(Token::RBrace) {
+            if self.matches(Token::DotDot) {
+                // `..` ignores the remaining fields and must come last
+                rest = true;
+                self.matches(Token::Comma);
+                self.expect(Token::RBrace)?;
+                break;
+            }
+
+            let field_start = self.next_start();
+            let mutable = self.matches(Token::Mut);
+            let name = self.parse_identifier()?;
+            let pattern = if !mutable && self.matches(Token::Colon) {
+                self.parse_pattern()?
+            } else {
+                bind(name.clone(), mutable, self.span_from(field_start))
+            };
+            fields.push(ast::FieldPattern {
+                name,
+                pattern,
+                span: self.span_from(field_start),
+            });
+
+            if !self.matches(Token::Comma) {
+                self.expect(Token::RBrace)?;
+                break;
+            }
+        }
+
+        Ok(Pattern::Struct {
+            path,
+            fields,
+            rest,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a literal pattern, including a negated number
+    fn parse_literal_pattern(&mut self) -> ParseResult<Literal> {
+        let negate = self.matches(Token::Minus);
+        if let Some(error) = self.peek().and_then(negative_only).filter(|_| !negate) {
+            self.next();
+            return Err(ParseError::InvalidToken { error, span: self.token_span() });
+        }
+        let literal = match self.peek() {
+            // `ऋण 9223372036854775808` is lexed as `i64::MIN` already
+            Some(Token::Number(n)) => Literal::Int(if negate { n.wrapping_neg() } else { *n }),
+            Some(Token::TypedNumber((value, width))) if negate => Literal::NegativeTypedInt(*value, *width),
+            Some(Token::TypedNumber((value, width))) => Literal::TypedInt(*value, *width),
+            Some(Token::Float(x)) => Literal::Float(if negate { -*x } else { *x }),
+            Some(Token::True) if !negate => Literal::Bool(true),
+            Some(Token::False) if !negate => Literal::Bool(false),
+            Some(Token::StringLit(s)) if !negate => Literal::String(s.clone()),
+            _ => return Err(self.unexpected("pattern")),
+        };
+        self.next();
+        Ok(literal)
+    }
+}
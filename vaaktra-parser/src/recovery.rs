@@ -0,0 +1,154 @@
+//! Error recovery for the Vāktra parser
+//!
+//! A failed statement or item is recorded in `Parser::errors` and the
+//! parser skips ahead to the next boundary: past a `;`, past a balanced
+//! `{ ... }`, before a `}` closing the enclosing block, or before a
+//! keyword that starts a new item or statement. The skipped input is
+//! represented by an `Expr::Error` statement so later passes still see
+//! where it was.
+//!
+//! A missing type becomes `Type::Error` without abandoning the
+//! surrounding declaration.
+
+use vaaktra_lexer::Token;
+
+use crate::ast::{Expr, Statement, Type};
+use crate::{ParseError, ParseResult, Parser};
+
+/// Keywords that begin a new item or statement and so end a skipped region
+fn is_sync_keyword(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Class
+            | Token::Fn
+            | Token::Let
+            | Token::Module
+            | Token::Import
+            | Token::Trait
+            | Token::Impl
+            | Token::If
+            | Token::While
+            | Token::ForEach
+            | Token::Break
+            | Token::Continue
+            | Token::Return
+            | Token::Pub
+            | Token::Async
+            | Token::Unsafe
+    )
+}
+
+/// Tokens that can directly follow a type annotation
+fn follows_type(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Comma
+            | Token::RParen
+            | Token::Semicolon
+            | Token::DoubleDanda
+            | Token::Equals
+            | Token::LBrace
+            | Token::RBrace
+    )
+}
+
+impl<I> Parser<I>
+where
+    I: Iterator<Item = (Token, std::ops::Range<usize>)>,
+{
+    /// Parse a statement; on error, record it, skip to the next statement
+    /// boundary and return an `Expr::Error` statement in its place
+    pub(crate) fn parse_statement_or_recover(&mut self) -> Statement {
+        let start = self.next_start();
+        let consumed = self.consumed;
+
+        match self.parse_statement() {
+            Ok(stmt) => stmt,
+            Err(error) => {
+                self.record_error(error);
+                self.synchronize(consumed);
+                self.error_statement(start)
+            }
+        }
+    }
+
+    /// Statement standing in for input skipped since `start`
+    pub(crate) fn error_statement(&self, start: usize) -> Statement {
+        Statement::Expr(Box::new(Expr::Error(self.span_from(start))))
+    }
+
+    /// Run `parse`; on error, record it and skip to the next boundary
+    pub(crate) fn recover<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> Option<T> {
+        let consumed = self.consumed;
+        match parse(self) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.record_error(error);
+                self.synchronize(consumed);
+                None
+            }
+        }
+    }
+
+    /// Parse a type annotation, using `Type::Error` if none is present
+    pub(crate) fn parse_type_or_recover(&mut self) -> ParseResult<Type> {
+        match self.peek() {
+            Some(token) if follows_type(token) => {
+                let error = self.unexpected("type");
+                self.record_error(error);
+                Ok(Type::Error)
+            }
+            _ => self.parse_type(),
+        }
+    }
+
+    /// Record an error, collapsing the repeated end-of-input errors raised
+    /// by every enclosing construct
+    pub(crate) fn record_error(&mut self, error: ParseError) {
+        let repeated_eof = matches!(error, ParseError::UnexpectedEof(_))
+            && matches!(self.errors.last(), Some(ParseError::UnexpectedEof(_)));
+        if !repeated_eof {
+            self.errors.push(error);
+        }
+    }
+
+    /// Skip tokens up to the next statement boundary. At least one token is
+    /// skipped if nothing was consumed since `consumed_before`, so recovery
+    /// always makes progress.
+    fn synchronize(&mut self, consumed_before: usize) {
+        let mut depth = 0usize;
+
+        loop {
+            let progressed = self.consumed > consumed_before;
+            match self.peek() {
+                None => return,
+                Some(Token::Semicolon | Token::DoubleDanda) if depth == 0 => {
+                    self.next();
+                    return;
+                }
+                Some(Token::RBrace) if depth == 0 => {
+                    // Leave the `}` for the enclosing block to close
+                    if !progressed {
+                        self.next();
+                    }
+                    return;
+                }
+                Some(token) if depth == 0 && progressed && is_sync_keyword(token) => return,
+                Some(Token::LBrace) => {
+                    depth += 1;
+                    self.next();
+                }
+                Some(Token::RBrace) => {
+                    depth -= 1;
+                    self.next();
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                Some(_) => {
+                    self.next();
+                }
+            }
+        }
+    }
+}
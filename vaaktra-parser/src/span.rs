@@ -1,47 +1,57 @@
 //! Source code span handling for error reporting
+//!
+//! Every AST node and diagnostic carries a [`Span`] of byte offsets into one
+//! source file. A [`SourceMap`] owns the loaded files and turns those offsets
+//! into the line and column a reader sees, counting columns in grapheme
+//! clusters so that a conjunct such as `क्ष` or a vowel sign such as `ि` does
+//! not shift the caret.
 
 use std::fmt;
 use std::ops::Range;
+use std::path::PathBuf;
 
-/// Represents a location in the source code
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Represents a location in the source code with file information
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Span {
     /// Start byte offset (inclusive)
     pub start: usize,
     /// End byte offset (exclusive)
     pub end: usize,
+    /// Index of the source file in its [`SourceMap`]
+    pub file_id: u32,
 }
 
 impl Span {
     /// Create a new span from start and end byte offsets
-    pub fn new(start: usize, end: usize) -> Self {
-        assert!(start <= end, "Span start must be <= end");
-        Span { start, end }
+    pub fn new(start: usize, end: usize, file_id: u32) -> Self {
+        debug_assert!(start <= end, "Invalid span: start > end");
+        Span { start, end, file_id }
+    }
+
+    /// An empty span at the start of file 0, for synthesized nodes
+    pub fn dummy() -> Self {
+        Span { start: 0, end: 0, file_id: 0 }
     }
-    
+
     /// Create a span that covers both this span and another span
     pub fn to(&self, other: Span) -> Self {
         Span {
             start: self.start.min(other.start),
             end: self.end.max(other.end),
+            file_id: self.file_id,
         }
     }
-    
+
     /// Check if a position is within this span
     pub fn contains(&self, pos: usize) -> bool {
         self.start <= pos && pos < self.end
     }
-}
 
-impl From<Range<usize>> for Span {
-    fn from(range: Range<usize>) -> Self {
-        Span::new(range.start, range.end)
-    }
-}
-
-impl From<&Range<usize>> for Span {
-    fn from(range: &Range<usize>) -> Self {
-        Span::new(range.start, range.end)
+    /// The byte range covered by this span
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
     }
 }
 
@@ -51,41 +61,146 @@ impl fmt::Display for Span {
     }
 }
 
-/// A value with an associated source code span
+/// A 1-based line and column; columns count grapheme clusters
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Spanned<T> {
-    /// The value
-    pub value: T,
-    /// The source code span
-    pub span: Span,
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
 }
 
-impl<T> Spanned<T> {
-    /// Create a new spanned value
-    pub fn new(value: T, span: Span) -> Self {
-        Spanned { value, span }
-    }
-    
-    /// Map the inner value while preserving the span
-    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Spanned<U> {
-        Spanned {
-            value: f(self.value),
-            span: self.span,
-        }
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
     }
 }
 
-impl<T: Default> Default for Spanned<T> {
-    fn default() -> Self {
-        Spanned {
-            value: T::default(),
-            span: Span::new(0, 0),
+/// A source file known to a [`SourceMap`]
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub source: String,
+    /// Byte offset of the start of every line
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    pub fn new(path: impl Into<PathBuf>, source: impl Into<String>) -> Self {
+        let source = source.into();
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        SourceFile {
+            path: path.into(),
+            source,
+            line_starts,
+        }
+    }
+
+    /// Line and column of a byte offset. Offsets past the end clamp to the
+    /// end of the file, and offsets inside a grapheme cluster report the
+    /// column of that cluster.
+    pub fn location(&self, offset: usize) -> Location {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let column = self.source[line_start..]
+            .grapheme_indices(true)
+            .take_while(|(i, grapheme)| line_start + i + grapheme.len() <= offset)
+            .count();
+        Location {
+            line: line + 1,
+            column: column + 1,
         }
     }
+
+    /// Text of a 1-based line, without its line terminator
+    pub fn line_text(&self, line: usize) -> Option<&str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self.line_starts.get(line).copied().unwrap_or(self.source.len());
+        Some(self.source[start..end].trim_end_matches(['\n', '\r']))
+    }
 }
 
-impl<T: fmt::Display> fmt::Display for Spanned<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.value.fmt(f)
+/// All source files of a program, indexed by file id
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, returning the id its spans should carry
+    pub fn add_file(&mut self, path: impl Into<PathBuf>, source: impl Into<String>) -> u32 {
+        self.files.push(SourceFile::new(path, source));
+        (self.files.len() - 1) as u32
+    }
+
+    /// All files, indexed by file id
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// The file with the given id
+    pub fn file(&self, file_id: u32) -> Option<&SourceFile> {
+        self.files.get(file_id as usize)
+    }
+
+    /// Where a span starts
+    pub fn location(&self, span: Span) -> Option<Location> {
+        self.file(span.file_id).map(|file| file.location(span.start))
+    }
+
+    /// `path:line:column` of a span, for diagnostics
+    pub fn describe(&self, span: Span) -> Option<String> {
+        let file = self.file(span.file_id)?;
+        Some(format!("{}:{}", file.path.display(), file.location(span.start)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_counts_lines_and_ascii_columns() {
+        let file = SourceFile::new("मुख्य.vk", "ab\ncd\n\nef");
+        assert_eq!(file.location(0), Location { line: 1, column: 1 });
+        assert_eq!(file.location(4), Location { line: 2, column: 2 });
+        assert_eq!(file.location(6), Location { line: 3, column: 1 });
+        assert_eq!(file.location(7), Location { line: 4, column: 1 });
+        assert_eq!(file.location(8), Location { line: 4, column: 2 });
+        assert_eq!(file.location(100), Location { line: 4, column: 3 });
+        assert_eq!(file.line_text(2), Some("cd"));
+        assert_eq!(file.line_text(5), None);
+    }
+
+    #[test]
+    fn test_devanagari_columns_are_graphemes() {
+        // क्षेत्र is far more code points than the clusters a reader sees
+        let source = "सूत्र क्षेत्र = १;";
+        let file = SourceFile::new("मुख्य.vk", source);
+        let expected = source.graphemes(true).position(|g| g == "=").unwrap() + 1;
+        let eq = source.find('=').unwrap();
+        assert_eq!(file.location(eq).column, expected);
+
+        // An offset in the middle of क्षे reports that cluster's column
+        let kshe = source.find("क्षे").unwrap();
+        assert_eq!(file.location(kshe + 3).column, file.location(kshe).column);
+        assert!(expected < source[..eq].chars().count() + 1);
+    }
+
+    #[test]
+    fn test_source_map_describes_spans() {
+        let mut map = SourceMap::new();
+        map.add_file("अ.vk", "");
+        let id = map.add_file("ब.vk", "मन्त्र\n  फ");
+        let offset = "मन्त्र\n  ".len();
+        let span = Span::new(offset, offset + "फ".len(), id);
+        assert_eq!(map.location(span), Some(Location { line: 2, column: 3 }));
+        assert_eq!(map.describe(span).as_deref(), Some("ब.vk:2:3"));
+        assert_eq!(map.describe(Span::new(0, 0, 7)), None);
     }
 }
@@ -0,0 +1,240 @@
+//! Block and control-flow statement parsing for Vāktra
+//!
+//! Conditions are ordinary expressions, so both `यदि अ {` and the
+//! parenthesized `यदि (अ) {` forms are accepted. Bodies are always blocks.
+//! Loops may carry a label written as `'नाम:` which `निर्गम` and
+//! `अनुवृत्ति` can refer to. `प्रत्याहर` returns from the enclosing mantra.
+
+use vaaktra_lexer::Token;
+
+use crate::ast::{self, Statement};
+use crate::expr::is_block_like;
+use crate::{ParseError, ParseResult, Parser};
+
+/// Whether a token begins a statement other than an expression statement
+fn starts_statement(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Class
+            | Token::Fn
+            | Token::Let
+            | Token::Module
+            | Token::Import
+            | Token::Trait
+            | Token::Impl
+            | Token::If
+            | Token::While
+            | Token::ForEach
+            | Token::Label(_)
+            | Token::Break
+            | Token::Continue
+            | Token::Return
+            | Token::Pub
+            | Token::Unsafe
+            | Token::LBrace
+            | Token::Semicolon
+            | Token::DoubleDanda
+    )
+}
+
+impl<I> Parser<I>
+where
+    I: Iterator<Item = (Token, std::ops::Range<usize>)>,
+{
+    /// Parse a `{ ... }` block of statements. An expression directly
+    /// before the closing `}` without a `;` becomes the block's tail
+    /// expression, i.e. its value.
+    pub(crate) fn parse_block(&mut self) -> ParseResult<ast::Block> {
+        let start = self.next_start();
+        self.expect(Token::LBrace)?;
+
+        let mut stmts = Vec::new();
+        let mut expr = None;
+        while !self.matches(Token::RBrace) {
+            if self.peek().is_none() {
+                return Err(self.eof_error());
+            }
+            if self.at_statement() {
+                stmts.push(self.parse_statement_or_recover());
+                continue;
+            }
+            let start = self.next_start();
+            match self.recover(Self::parse_block_expression) {
+                Some((value, true)) => expr = Some(Box::new(value)),
+                Some((value, false)) => stmts.push(Statement::Expr(Box::new(value))),
+                None => stmts.push(self.error_statement(start)),
+            }
+        }
+
+        Ok(ast::Block {
+            stmts,
+            expr,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Whether the next tokens begin a statement other than an expression
+    /// statement
+    fn at_statement(&mut self) -> bool {
+        self.peek().is_some_and(starts_statement) || self.at_modified_item()
+    }
+
+    /// Parse an expression inside a block, returning it with whether it is
+    /// the block's tail expression
+    fn parse_block_expression(&mut self) -> ParseResult<(ast::Expr, bool)> {
+        let value = self.parse_expression()?;
+        if self.peek() == Some(&Token::RBrace) {
+            return Ok((value, true));
+        }
+        if is_block_like(&value) {
+            self.matches_terminator();
+        } else {
+            self.expect_terminator()?;
+        }
+        Ok((value, false))
+    }
+
+    /// Parse a block and wrap it as a statement
+    fn parse_block_statement(&mut self) -> ParseResult<Statement> {
+        Ok(Statement::Block(self.parse_block()?))
+    }
+
+    /// Parse `यदि cond { ... } [अथवा यदि cond { ... }]* [अथवा { ... }]`
+    pub(crate) fn parse_if_statement(&mut self) -> ParseResult<Statement> {
+        self.expect(Token::If)?;
+        let condition = self.parse_expression()?;
+        let then_branch = self.parse_block_statement()?;
+
+        let else_branch = if self.matches(Token::Else) {
+            if self.peek() == Some(&Token::If) {
+                // `अथवा यदि` chains nest as the else branch
+                Some(Box::new(self.parse_if_statement()?))
+            } else {
+                Some(Box::new(self.parse_block_statement()?))
+            }
+        } else {
+            None
+        };
+
+        Ok(Statement::Yadi {
+            condition: Box::new(condition),
+            then_branch: Box::new(then_branch),
+            else_branch,
+        })
+    }
+
+    /// Parse `यावत् cond { ... }`
+    pub(crate) fn parse_while_statement(&mut self, label: Option<ast::LoopLabel>) -> ParseResult<Statement> {
+        self.expect(Token::While)?;
+        let condition = self.parse_expression()?;
+        let body = self.parse_block_statement()?;
+
+        Ok(Statement::Yaavat {
+            condition: Box::new(condition),
+            body: Box::new(body),
+            label,
+        })
+    }
+
+    /// Parse `प्रत्येक pattern मध्ये iterable { ... }`
+    pub(crate) fn parse_foreach_statement(&mut self, label: Option<ast::LoopLabel>) -> ParseResult<Statement> {
+        self.expect(Token::ForEach)?;
+        let pattern = self.parse_pattern()?;
+        self.expect(Token::In)?;
+        let iterable = self.parse_expression()?;
+        let body = self.parse_block_statement()?;
+
+        Ok(Statement::Pratyeka {
+            pattern,
+            iterable: Box::new(iterable),
+            body: Box::new(body),
+            label,
+        })
+    }
+
+    /// Parse `'label: यावत् ...` or `'label: प्रत्येक ...`
+    pub(crate) fn parse_labeled_loop(&mut self) -> ParseResult<Statement> {
+        let label = self.parse_loop_label()?;
+        self.expect(Token::Colon)?;
+
+        match self.peek() {
+            Some(Token::While) => self.parse_while_statement(Some(label)),
+            Some(Token::ForEach) => self.parse_foreach_statement(Some(label)),
+            _ => {
+                let found = self.next();
+                let span = self.token_span();
+                Err(ParseError::UnexpectedToken {
+                    expected: "loop after label".to_string(),
+                    found,
+                    span,
+                })
+            }
+        }
+    }
+
+    /// Parse `निर्गम ['label] [value];`
+    pub(crate) fn parse_break_statement(&mut self) -> ParseResult<Statement> {
+        self.expect(Token::Break)?;
+        let label = self.parse_optional_loop_label()?;
+
+        let value = if matches!(self.peek(), Some(Token::Semicolon | Token::DoubleDanda)) {
+            None
+        } else {
+            Some(Box::new(self.parse_expression()?))
+        };
+        self.expect_terminator()?;
+
+        Ok(Statement::Nirgama { label, value })
+    }
+
+    /// Parse `प्रत्याहर [value];`
+    pub(crate) fn parse_return_statement(&mut self) -> ParseResult<Statement> {
+        self.expect(Token::Return)?;
+
+        let value = if matches!(self.peek(), Some(Token::Semicolon | Token::DoubleDanda)) {
+            None
+        } else {
+            Some(Box::new(self.parse_expression()?))
+        };
+        self.expect_terminator()?;
+
+        Ok(Statement::Pratyahara(value))
+    }
+
+    /// Parse `अनुवृत्ति ['label];`
+    pub(crate) fn parse_continue_statement(&mut self) -> ParseResult<Statement> {
+        self.expect(Token::Continue)?;
+        let label = self.parse_optional_loop_label()?;
+        self.expect_terminator()?;
+
+        Ok(Statement::Anuvrtti(label))
+    }
+
+    /// Parse a loop label if one comes next
+    fn parse_optional_loop_label(&mut self) -> ParseResult<Option<ast::LoopLabel>> {
+        if matches!(self.peek(), Some(Token::Label(_))) {
+            Ok(Some(self.parse_loop_label()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parse a `'label` token
+    fn parse_loop_label(&mut self) -> ParseResult<ast::LoopLabel> {
+        let start = self.next_start();
+        match self.next() {
+            Some(Token::Label(name)) => Ok(ast::LoopLabel {
+                name: ast::RcStr::new(&name),
+                span: self.span_from(start),
+            }),
+            found => {
+                let span = self.token_span();
+                Err(ParseError::UnexpectedToken {
+                    expected: "loop label".to_string(),
+                    found,
+                    span,
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Type expression parsing for Vāktra
+//!
+//! ```text
+//! type  := named | tuple | function | '&' type | '[' type (';' expr)? ']' | '!' | '_'
+//! named := (builtin | path) ('<' type (',' type)* '>')?
+//! path  := ident ('::' ident)*
+//! tuple := '(' (type (',' type)* ','?)? ')'
+//! function := tuple '->' type
+//! params := '<' param (',' param)* ','? '>'
+//! param := ident (':' path ('+' path)*)? ('=' type)?
+//! ```
+//!
+//! Generic arguments of a named type are stored in the second field of
+//! `Type::Named`; `PathSegment::args` is left for expression paths.
+
+use vaaktra_lexer::Token;
+
+use crate::ast::{self, Type};
+use crate::{ParseError, ParseResult, Parser};
+
+/// Name of the builtin type spelled by a keyword token
+pub(crate) fn builtin_type_name(token: &Token) -> Option<&'static str> {
+    let name = match token {
+        Token::NumberType => "सङ्ख्या",
+        Token::FloatType => "दशांश",
+        Token::BoolType => "सत्यासत्य",
+        Token::StringType => "शब्द",
+        Token::ListType => "सूची",
+        Token::MapType => "निधान",
+        Token::Void => "शून्य",
+        Token::Object => "ब्रह्मन्",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// A path consisting of a single identifier
+pub(crate) fn single_segment_path(name: &str, span: ast::Span) -> ast::Path {
+    ast::Path {
+        segments: vec![ast::PathSegment {
+            ident: ast::RcStr::new(name),
+            args: None,
+        }],
+        span,
+    }
+}
+
+/// A named type with a single path segment and no generic arguments
+pub(crate) fn named_type(name: &str, span: ast::Span) -> Type {
+    Type::Named(single_segment_path(name, span), Vec::new())
+}
+
+impl<I> Parser<I>
+where
+    I: Iterator<Item = (Token, std::ops::Range<usize>)>,
+{
+    /// Parse a `::`-separated path such as `गणित::वर्ग`
+    pub(crate) fn parse_path(&mut self) -> ParseResult<ast::Path> {
+        let start = self.next_start();
+        let first = self.parse_identifier()?;
+        self.parse_path_rest(&first, start)
+    }
+
+    /// Parse the `::segment` tail of a path whose first segment is consumed
+    pub(crate) fn parse_path_rest(&mut self, first: &str, start: usize) -> ParseResult<ast::Path> {
+        let mut path = single_segment_path(first, self.span_from(start));
+        while self.matches(Token::PathSep) {
+            path.segments.push(ast::PathSegment {
+                ident: self.parse_identifier()?,
+                args: None,
+            });
+        }
+        path.span = self.span_from(start);
+        Ok(path)
+    }
+
+    /// Parse a type expression
+    pub(crate) fn parse_type(&mut self) -> ParseResult<Type> {
+        let start = self.next_start();
+        let found = self.next();
+
+        if let Some(name) = found.as_ref().and_then(builtin_type_name) {
+            let path = single_segment_path(name, self.span_from(start));
+            return self.parse_type_args(path);
+        }
+
+        match found {
+            Some(Token::Ident(name)) if name == "_" => Ok(Type::Infer(self.span_from(start))),
+            Some(Token::Ident(name)) => {
+                let path = self.parse_path_rest(&name, start)?;
+                self.parse_type_args(path)
+            }
+            Some(Token::Not) => Ok(Type::Never(self.span_from(start))),
+            Some(Token::Ampersand) => {
+                let mutable = self.matches(Token::Mut);
+                let inner = self.parse_type()?;
+                Ok(Type::Reference(Box::new(inner), mutable, self.span_from(start)))
+            }
+            Some(Token::LBracket) => {
+                let elem = self.parse_type()?;
+                if self.matches(Token::Semicolon) {
+                    let len = self.parse_expression()?;
+                    self.expect(Token::RBracket)?;
+                    Ok(Type::Array(Box::new(elem), Some(Box::new(len)), self.span_from(start)))
+                } else {
+                    self.expect(Token::RBracket)?;
+                    Ok(Type::Slice(Box::new(elem), self.span_from(start)))
+                }
+            }
+            Some(Token::LParen) => self.parse_tuple_or_function_type(start),
+            found => {
+                let span = self.token_span();
+                Err(ParseError::UnexpectedToken {
+                    expected: "type".to_string(),
+                    found,
+                    span,
+                })
+            }
+        }
+    }
+
+    /// Parse optional `<T: बन्ध + बन्ध = प्रकार, ...>` type parameters
+    /// after the name of a dharma or mantra
+    pub(crate) fn parse_type_params(&mut self) -> ParseResult<Vec<ast::TypeParam>> {
+        let mut params = Vec::new();
+        if !self.matches(Token::Lt) {
+            return Ok(params);
+        }
+        while !self.matches(Token::Gt) {
+            let start = self.next_start();
+            let name = self.parse_identifier()?;
+            let mut bounds = Vec::new();
+            if self.matches(Token::Colon) {
+                loop {
+                    let bound_start = self.next_start();
+                    let bound = self.parse_path()?;
+                    bounds.push(ast::TypeBound {
+                        bound,
+                        span: self.span_from(bound_start),
+                    });
+                    if !self.matches(Token::Plus) {
+                        break;
+                    }
+                }
+            }
+            let default = if self.matches(Token::Equals) {
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            params.push(ast::TypeParam {
+                name,
+                bounds,
+                default,
+                span: self.span_from(start),
+            });
+            if !self.matches(Token::Comma) {
+                self.expect(Token::Gt)?;
+                break;
+            }
+        }
+        Ok(params)
+    }
+
+    /// Parse optional `<...>` arguments after a type's path
+    fn parse_type_args(&mut self, path: ast::Path) -> ParseResult<Type> {
+        let mut args = Vec::new();
+        if self.matches(Token::Lt) {
+            loop {
+                args.push(self.parse_type()?);
+                if !self.matches(Token::Comma) {
+                    break;
+                }
+            }
+            self.expect_generic_close()?;
+        }
+
+        Ok(Type::Named(path, args))
+    }
+
+    /// Consume the `>` closing generic arguments. In `सूची<सङ्ख्या>= []`
+    /// the lexer joins `>=` into one token, so it is split here and the
+    /// `=` left for the caller.
+    fn expect_generic_close(&mut self) -> ParseResult<()> {
+        if let Some((Token::Ge, span)) = self.peek_spanned(0) {
+            if span.len() == ">=".len() {
+                let start = span.start;
+                self.lookahead[0] = (Token::Equals, start + 1..start + 2);
+                self.current_span = Some(start..start + 1);
+                return Ok(());
+            }
+        }
+        self.expect(Token::Gt)
+    }
+
+    /// Parse the rest of `(A, B)`, `(A)` or `(A, B) -> C`; the `(` is
+    /// already consumed
+    fn parse_tuple_or_function_type(&mut self, start: usize) -> ParseResult<Type> {
+        let mut elems = Vec::new();
+        let mut trailing_comma = false;
+        while !self.matches(Token::RParen) {
+            elems.push(self.parse_type()?);
+            trailing_comma = self.matches(Token::Comma);
+            if !trailing_comma {
+                self.expect(Token::RParen)?;
+                break;
+            }
+        }
+
+        if self.matches(Token::Arrow) {
+            let ret = self.parse_type()?;
+            return Ok(Type::Function(elems, Box::new(ret), self.span_from(start)));
+        }
+
+        // `(T)` is just a parenthesized type; `(T,)` is a one-element tuple
+        if elems.len() == 1 && !trailing_comma {
+            return Ok(elems.remove(0));
+        }
+        Ok(Type::Tuple(elems, self.span_from(start)))
+    }
+}
@@ -0,0 +1,122 @@
+//! Capture analysis for Vāktra (वाक्त्र) lambdas
+//!
+//! Determines which variables of the enclosing scopes a lambda body uses,
+//! so the lambda can be turned into a closure carrying exactly those values.
+
+use std::collections::HashSet;
+use vaaktra_parser::ast::{Expr, Item, Param, RcStr, Span};
+
+use crate::visit::{walk_expr, Visit};
+
+/// Names a lambda body refers to without binding them itself, in order of
+/// first use. Only single-segment paths are considered; the caller drops
+/// names that resolve to globals such as top-level mantras.
+pub fn lambda_captures(params: &[Param], body: &Expr) -> Vec<RcStr> {
+    let mut analysis = CaptureAnalysis::default();
+    analysis.push_scope();
+    for param in params {
+        analysis.visit_param(param);
+    }
+    analysis.visit_expr(body);
+    analysis.free
+}
+
+/// Walks an expression tracking which names are bound in each scope
+#[derive(Default)]
+struct CaptureAnalysis {
+    /// Bound names, innermost scope last
+    scopes: Vec<HashSet<RcStr>>,
+
+    /// Free names found so far
+    free: Vec<RcStr>,
+}
+
+impl CaptureAnalysis {
+    fn bind(&mut self, name: &RcStr) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.clone());
+        }
+    }
+
+    fn use_name(&mut self, name: &RcStr) {
+        let bound = self.scopes.iter().any(|scope| scope.contains(name));
+        if !bound && !self.free.contains(name) {
+            self.free.push(name.clone());
+        }
+    }
+}
+
+impl Visit for CaptureAnalysis {
+    /// Nested items do not close over local variables
+    fn visit_item(&mut self, _item: &Item) {}
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Variable(path, _) => {
+                if let [segment] = path.segments.as_slice() {
+                    self.use_name(&segment.ident);
+                }
+            }
+            // Whatever a nested lambda captures from outside this one is
+            // captured here too
+            expr => walk_expr(self, expr),
+        }
+    }
+
+    fn visit_binding(&mut self, name: &RcStr, _mutable: bool, _span: Span) {
+        self.bind(name);
+    }
+
+    fn visit_param(&mut self, param: &Param) {
+        self.bind(&param.name);
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashSet::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vaaktra_lexer::Lexer;
+    use vaaktra_parser::ast::{Item, Statement};
+    use vaaktra_parser::Parser;
+
+    /// Captures of the lambda initializing the first `सूत्र` in `input`
+    fn captures_of(input: &str) -> Vec<String> {
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let Item::Praarabdha(stmts) = &program.items[0] else {
+            panic!("Expected a top-level statement");
+        };
+        let Statement::Sutra(sutra) = &stmts[0] else {
+            panic!("Expected sutra");
+        };
+        let Expr::Lambda(params, body, _) = &sutra.value else {
+            panic!("Expected lambda");
+        };
+        lambda_captures(params, body).iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_params_and_locals_are_not_captured() {
+        let captures = captures_of("सूत्र फ = |अ| { सूत्र ब = अ धन क; ब गुण ग धन क };");
+        assert_eq!(captures, ["क", "ग"]);
+    }
+
+    #[test]
+    fn test_nested_lambda_captures_propagate() {
+        let captures = captures_of("सूत्र फ = |अ| |ब| अ धन ब धन स;");
+        assert_eq!(captures, ["स"]);
+    }
+
+    #[test]
+    fn test_pattern_bindings_shadow() {
+        let captures = captures_of("सूत्र फ = || विकल्प म { (क, _) => क धन ख, _ => क };");
+        assert_eq!(captures, ["म", "ख", "क"]);
+    }
+}
@@ -0,0 +1,1839 @@
+//! Type checking for Vāktra (वाक्त्र)
+//!
+//! Checks a resolved program bidirectionally. Where the context knows the
+//! type an expression must have, such as an argument, the value of an
+//! annotated सूत्र or the body of a mantra, that type is pushed into the
+//! expression, so an error points at the innermost part that disagrees.
+//! Elsewhere the type is inferred from the expression and compared after.
+//!
+//! Arithmetic may mix सङ्ख्या and दशांश, giving दशांश; nothing else converts
+//! implicitly. An unsuffixed integer literal takes the width of the sized
+//! integer it meets, and must fit in it; unsigned widths cannot be negated.
+//! A mantra returning शून्य may end in an expression whose value is then
+//! discarded.
+//!
+//! Types that are not written are inferred along the way by unification;
+//! see [`crate::infer`].
+//!
+//! A generic dharma or mantra is checked once, with its type parameters as
+//! opaque types that only support what their bounds allow. Each use gets
+//! new type variables for the parameters, and the arguments they are
+//! solved to must satisfy the bounds. The uses are recorded for
+//! [`crate::mono`].
+//!
+//! A dharma with a कार्यान्वय of a गुणधर्म may be used where the गुणधर्म is
+//! expected. Methods called on such a value are chosen at run time, so the
+//! call is renamed `गुणधर्म::मन्त्र` for the compiler to find its vtable. A
+//! type parameter bounded by a गुणधर्म has its methods, and calls to them
+//! become direct once the item is monomorphized.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use vaaktra_parser::ast::{
+    Arm, BinaryOp, Block, DharmaDef, Expr, FieldValue, GunadharmaDef, Guard, IntegerWidth, Item, KaryanvayaDef,
+    Literal, LoopLabel, MantraDef, Path, Pattern, Program, RcStr, Span, Statement, SutraDef, Type, TypeParam, UnaryOp,
+};
+
+use crate::error::{DetailedSemanticError, SemanticErrorType};
+use crate::infer::{self, Solved, Unifier, UnifyError};
+use crate::mono::Instances;
+use crate::resolve::{Resolution, SymbolId, SymbolKind};
+use crate::ty::{ConstraintKind, Ty};
+
+/// Type check `program`, whose names `resolution` resolves
+pub fn check_program(program: &Program, resolution: &Resolution) -> Vec<DetailedSemanticError> {
+    check(program, resolution).0
+}
+
+/// Type check `program` and write the inferred types into it. Returns the
+/// errors and the type arguments inferred for uses of generic items.
+pub fn infer_types(program: &mut Program, resolution: &Resolution) -> (Vec<DetailedSemanticError>, Instances) {
+    let (errors, solved, instances) = check(program, resolution);
+    infer::write_back(program, &solved);
+    (errors, instances)
+}
+
+fn check(program: &Program, resolution: &Resolution) -> (Vec<DetailedSemanticError>, Solved, Instances) {
+    let mut checker = Checker::new(resolution);
+    checker.check_items(&program.items);
+    checker.finish()
+}
+
+/// Parameter and return types of a mantra
+#[derive(Debug, Clone)]
+struct Signature {
+    /// Without the आत्मन् receiver
+    params: Vec<Ty>,
+    /// Parameters without a default value
+    required: usize,
+    ret: Ty,
+}
+
+impl Signature {
+    fn ty(&self) -> Ty {
+        Ty::Function(self.params.clone(), Box::new(self.ret.clone()))
+    }
+
+    fn substitute(&self, args: &HashMap<SymbolId, Ty>) -> Signature {
+        Signature {
+            params: self.params.iter().map(|param| param.substitute(args)).collect(),
+            required: self.required,
+            ret: self.ret.substitute(args),
+        }
+    }
+}
+
+/// A type parameter of a dharma or mantra
+#[derive(Debug, Clone)]
+struct Generic {
+    id: SymbolId,
+    name: RcStr,
+    bounds: Vec<Bound>,
+    default: Option<Ty>,
+}
+
+/// What a type parameter requires of its arguments
+#[derive(Debug, Clone)]
+struct Bound {
+    kind: ConstraintKind,
+    /// The dharma a [`ConstraintKind::SubtypeOf`] bound names, or the
+    /// गुणधर्म an [`ConstraintKind::Implements`] bound names
+    target: Option<SymbolId>,
+    /// As written, for messages
+    name: RcStr,
+}
+
+/// A use of a generic item whose type arguments are inferred
+struct Site {
+    span: Span,
+    item: SymbolId,
+    args: Vec<Ty>,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: RcStr,
+    ty: Ty,
+    has_default: bool,
+}
+
+/// A loop that निर्गम may leave
+struct LoopFrame {
+    label: Option<RcStr>,
+    /// Type of the values it is left with, once one is seen
+    value: Option<Ty>,
+}
+
+struct Checker<'a> {
+    resolution: &'a Resolution,
+    errors: Vec<DetailedSemanticError>,
+
+    /// Types of variables and parameters
+    locals: HashMap<SymbolId, Ty>,
+
+    /// Signatures of mantras outside dharmas
+    signatures: HashMap<SymbolId, Signature>,
+
+    /// Fields of each dharma, in order of declaration
+    fields: HashMap<SymbolId, Vec<Field>>,
+
+    /// Signatures of methods, by dharma and name
+    methods: HashMap<(SymbolId, RcStr), Signature>,
+
+    /// Return type of each enclosing mantra or lambda, innermost last
+    return_types: Vec<Ty>,
+
+    /// Enclosing loops of the current mantra or lambda, innermost last
+    loops: Vec<LoopFrame>,
+
+    unifier: Unifier,
+
+    /// Signatures of all mantras and methods, by the span of the mantra
+    mantra_signatures: HashMap<Span, Signature>,
+
+    /// Type of each `_`, by its span
+    holes: Vec<(Span, Ty)>,
+
+    /// Type of each सूत्र without annotation, by its span
+    unannotated: Vec<(Span, Ty)>,
+
+    /// Type parameters of each generic dharma and mantra
+    generics: HashMap<SymbolId, Vec<Generic>>,
+
+    /// Bounds of each type parameter
+    bounds: HashMap<SymbolId, Vec<Bound>>,
+
+    /// Type arguments given to generic items, with where they were given,
+    /// to check against the bounds once they are solved
+    bound_checks: Vec<(SymbolId, Vec<Ty>, Span)>,
+
+    sites: Vec<Site>,
+
+    /// Methods of each गुणधर्म, in order of declaration
+    gunadharmas: HashMap<SymbolId, Vec<(RcStr, Signature)>>,
+
+    /// Where each dharma implements each गुणधर्म, by dharma and गुणधर्म
+    impls: HashMap<(SymbolId, SymbolId), Span>,
+
+    /// Method calls on गुणधर्म values, with the name they are dispatched by
+    virtual_calls: Vec<(Span, RcStr)>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(resolution: &'a Resolution) -> Self {
+        Checker {
+            resolution,
+            errors: Vec::new(),
+            locals: HashMap::new(),
+            signatures: HashMap::new(),
+            fields: HashMap::new(),
+            methods: HashMap::new(),
+            return_types: Vec::new(),
+            loops: Vec::new(),
+            unifier: Unifier::default(),
+            mantra_signatures: HashMap::new(),
+            holes: Vec::new(),
+            unannotated: Vec::new(),
+            generics: HashMap::new(),
+            bounds: HashMap::new(),
+            bound_checks: Vec::new(),
+            sites: Vec::new(),
+            gunadharmas: HashMap::new(),
+            impls: HashMap::new(),
+            virtual_calls: Vec::new(),
+        }
+    }
+
+    /// The errors found, the inferred types of everything not written and
+    /// the type arguments of generic items where they were left out
+    fn finish(mut self) -> (Vec<DetailedSemanticError>, Solved, Instances) {
+        // Type arguments nothing decided take their defaults
+        for site in &self.sites {
+            let generics = &self.generics[&site.item];
+            let mut given = HashMap::new();
+            for (generic, arg) in generics.iter().zip(&site.args) {
+                if let (Ty::Var(_), Some(default)) = (self.unifier.shallow(arg), &generic.default) {
+                    let _ = self.unifier.unify(arg, &default.substitute(&given));
+                }
+                given.insert(generic.id, arg.clone());
+            }
+        }
+        for (item, args, span) in std::mem::take(&mut self.bound_checks) {
+            let generics = self.generics.get(&item).cloned().unwrap_or_default();
+            for (generic, arg) in generics.iter().zip(&args) {
+                let arg = self.unifier.resolve(arg);
+                for bound in &generic.bounds {
+                    if !arg.has_var() && !self.satisfies(&arg, bound) {
+                        let message = format!(
+                            "`{}` does not satisfy the bound `{}` of `{}`",
+                            arg, bound.name, generic.name
+                        );
+                        self.error(SemanticErrorType::TypeMismatch, message, span);
+                    }
+                }
+            }
+        }
+
+        let mut instances = Instances::default();
+        for site in std::mem::take(&mut self.sites) {
+            let args: Vec<Ty> = site.args.iter().map(|arg| self.unifier.resolve(arg)).collect();
+            if args.iter().any(Ty::has_var) {
+                let name = self.resolution.symbol(site.item).name.clone();
+                let message = format!("cannot infer the type arguments of `{}`", name);
+                self.error(SemanticErrorType::CannotInfer, message, site.span);
+            }
+            instances.insert(site.span, site.item, args);
+        }
+
+        let mut solved = Solved::default();
+        let holes = std::mem::take(&mut self.holes).into_iter().map(|hole| (hole, true));
+        let unannotated = std::mem::take(&mut self.unannotated).into_iter().map(|sutra| (sutra, false));
+        for ((span, ty), is_hole) in holes.chain(unannotated) {
+            let ty = self.unifier.resolve(&ty);
+            if ty.has_var() {
+                let message = format!("cannot infer `{}`; add a type annotation", ty);
+                self.error(SemanticErrorType::CannotInfer, message, span);
+            }
+            let target = if is_hole { &mut solved.holes } else { &mut solved.sutras };
+            target.insert(span, infer::to_type(&ty, span));
+        }
+        solved.virtual_calls = std::mem::take(&mut self.virtual_calls).into_iter().collect();
+        (self.errors, solved, instances)
+    }
+
+    /// Lower a written type, with a new type variable for each `_`
+    fn lower(&mut self, ty: &Type) -> Ty {
+        let Checker { resolution, unifier, holes, .. } = self;
+        let lowered = Ty::lower_with(ty, resolution, &mut |hole| {
+            let var = unifier.fresh();
+            if let Type::Infer(span) = hole {
+                holes.push((*span, var.clone()));
+            }
+            var
+        });
+        self.check_type_args(ty, lowered)
+    }
+
+    fn signature_of(&mut self, mantra: &MantraDef) -> Signature {
+        let params = &mantra.params[usize::from(mantra.has_receiver())..];
+        let signature = Signature {
+            params: params.iter().map(|param| self.lower(&param.ty)).collect(),
+            required: params.iter().filter(|param| param.default_value.is_none()).count(),
+            ret: self.lower(&mantra.return_type),
+        };
+        self.mantra_signatures.insert(mantra.span, signature.clone());
+        signature
+    }
+
+    fn error(&mut self, error_type: SemanticErrorType, message: String, span: Span) {
+        self.errors.push(DetailedSemanticError::new(error_type, message).with_span(span));
+    }
+
+    fn mismatch(&mut self, expected: &Ty, found: &Ty, span: Span) {
+        let (expected, found) = (self.unifier.resolve(expected), self.unifier.resolve(found));
+        let message = format!("expected `{}`, found `{}`", expected, found);
+        self.error(SemanticErrorType::TypeMismatch, message, span);
+    }
+
+    /// Report an unsuffixed literal, negated if `negative`, that `width`
+    /// cannot hold
+    fn check_literal_fits(&mut self, magnitude: u128, negative: bool, width: IntegerWidth, span: Span) {
+        let max = if negative { width.max_magnitude() } else { width.max_literal() };
+        if magnitude > max {
+            let sign = if negative { "ऋण " } else { "" };
+            let message = format!("`{}{}` does not fit in `{}`", sign, magnitude, width);
+            self.error(SemanticErrorType::TypeMismatch, message, span);
+        }
+    }
+
+    fn unsigned_negation(&mut self, width: IntegerWidth, span: Span) {
+        let message = format!("`{}` is unsigned, so it cannot be negated", width);
+        self.error(SemanticErrorType::TypeMismatch, message, span);
+    }
+
+    /// Unify the type of a value with the type expected of it, reporting
+    /// at `span` if they differ. Returns whether they agree.
+    fn expect(&mut self, expected: &Ty, found: &Ty, span: Span) -> bool {
+        self.expect_at(expected, found, Some(span))
+    }
+
+    fn expect_at(&mut self, expected: &Ty, found: &Ty, span: Option<Span>) -> bool {
+        // A dharma converts to each गुणधर्म it implements
+        if let Ty::Gunadharma(gunadharma, _) = self.unifier.shallow(expected) {
+            if self.implements(&self.unifier.shallow(found), gunadharma) {
+                return true;
+            }
+        }
+        let error = match self.unifier.unify(expected, found) {
+            Ok(()) => return true,
+            Err(error) => error,
+        };
+        let (expected, found) = (self.unifier.resolve(expected), self.unifier.resolve(found));
+        let message = match error {
+            UnifyError::Mismatch => format!("expected `{}`, found `{}`", expected, found),
+            UnifyError::Infinite => format!("expected `{}`, found `{}`, which would contain it", expected, found),
+        };
+        let mut error = DetailedSemanticError::new(SemanticErrorType::TypeMismatch, message);
+        error.span = span;
+        self.errors.push(error);
+        false
+    }
+
+    /// Record the type of the variable or parameter declared at `span`
+    fn declare(&mut self, span: Span, ty: Ty) {
+        if let Some(id) = self.resolution.declared_at(span) {
+            self.locals.insert(id, ty);
+        }
+    }
+
+    // ===== Items =====
+
+    /// Check the items of a yantra or the program: signatures first, then
+    /// top-level statements in order, then bodies, as names are resolved
+    fn check_items(&mut self, items: &[Item]) {
+        self.collect_signatures(items);
+        self.check_globals(items);
+        self.check_bodies(items);
+    }
+
+    fn collect_signatures<'i>(&mut self, items: impl IntoIterator<Item = &'i Item>) {
+        // Type parameters first, as any signature may name a generic dharma
+        let items: Vec<&Item> = items.into_iter().collect();
+        self.collect_generics(items.iter().copied());
+        self.collect_item_signatures(items.iter().copied());
+        self.collect_karyanvayas(items);
+    }
+
+    fn collect_item_signatures<'i>(&mut self, items: impl IntoIterator<Item = &'i Item>) {
+        for item in items {
+            match item {
+                Item::Dharma(dharma) => self.collect_dharma(dharma),
+                Item::Mantra(mantra) => {
+                    let signature = self.signature_of(mantra);
+                    if let Some(id) = self.resolution.declared_at(mantra.span) {
+                        self.signatures.insert(id, signature);
+                    }
+                }
+                Item::Yantra(yantra) => self.collect_item_signatures(&yantra.items),
+                Item::Gunadharma(gunadharma) => self.collect_gunadharma(gunadharma),
+                Item::Sutra(_) | Item::Aanaya(_) | Item::Karyanvaya(_) | Item::Praarabdha(_) => {}
+            }
+        }
+    }
+
+    /// Check each कार्यान्वय among `items` against its गुणधर्म, once the
+    /// methods of every dharma and गुणधर्म are known
+    fn collect_karyanvayas<'i>(&mut self, items: impl IntoIterator<Item = &'i Item>) {
+        for item in items {
+            match item {
+                Item::Karyanvaya(karyanvaya) => self.collect_karyanvaya(karyanvaya),
+                Item::Yantra(yantra) => self.collect_karyanvayas(&yantra.items),
+                Item::Dharma(_)
+                | Item::Mantra(_)
+                | Item::Sutra(_)
+                | Item::Gunadharma(_)
+                | Item::Aanaya(_)
+                | Item::Praarabdha(_) => {}
+            }
+        }
+    }
+
+    fn collect_dharma(&mut self, dharma: &DharmaDef) {
+        let Some(id) = self.resolution.declared_at(dharma.span) else {
+            return;
+        };
+        let fields = dharma
+            .fields
+            .iter()
+            .map(|field| Field {
+                name: field.name.clone(),
+                ty: self.lower(&field.ty),
+                has_default: field.default_value.is_some(),
+            })
+            .collect();
+        self.fields.insert(id, fields);
+        for method in &dharma.methods {
+            if let Some(param) = method.type_params.first() {
+                let message = format!("method `{}` cannot have type parameters; declare them on `{}`", method.name, dharma.name);
+                self.error(SemanticErrorType::InvalidOperation, message, param.span);
+            }
+            let signature = self.signature_of(method);
+            self.methods.insert((id, method.name.clone()), signature);
+        }
+    }
+
+    fn collect_gunadharma(&mut self, gunadharma: &GunadharmaDef) {
+        let Some(id) = self.resolution.declared_at(gunadharma.span) else {
+            return;
+        };
+        let mut methods: Vec<(RcStr, Signature)> = Vec::new();
+        for method in &gunadharma.methods {
+            if !method.has_receiver() {
+                let message = format!("method `{}` of `{}` must take आत्मन्", method.name, gunadharma.name);
+                self.error(SemanticErrorType::InvalidOperation, message, method.span);
+            }
+            if methods.iter().any(|(name, _)| *name == method.name) {
+                let message = format!("`{}` is already declared in `{}`", method.name, gunadharma.name);
+                self.error(SemanticErrorType::DuplicateSymbol, message, method.span);
+                continue;
+            }
+            let params = &method.params[usize::from(method.has_receiver())..];
+            let signature = Signature {
+                params: params.iter().map(|param| self.lower(&param.ty)).collect(),
+                required: params.iter().filter(|param| param.default_value.is_none()).count(),
+                ret: self.lower(&method.return_type),
+            };
+            methods.push((method.name.clone(), signature));
+        }
+        self.gunadharmas.insert(id, methods);
+    }
+
+    /// Check that a कार्यान्वय defines exactly the methods of its गुणधर्म,
+    /// with their signatures, and add them to the dharma's methods
+    fn collect_karyanvaya(&mut self, karyanvaya: &KaryanvayaDef) {
+        let (Some(dharma), Some(gunadharma)) =
+            (self.resolution.use_of(&karyanvaya.dharma), self.resolution.use_of(&karyanvaya.gunadharma))
+        else {
+            return;
+        };
+        // Otherwise the resolver has reported what the paths name instead
+        let Some(required) = self.gunadharmas.get(&gunadharma).cloned() else {
+            return;
+        };
+        if self.resolution.symbol(dharma).kind != SymbolKind::Dharma {
+            return;
+        }
+        let dharma_name = self.resolution.symbol(dharma).name.clone();
+        let gunadharma_name = self.resolution.symbol(gunadharma).name.clone();
+        if self.generics.contains_key(&dharma) {
+            let message = format!("`{}` has type parameters, so it cannot implement a गुणधर्म", dharma_name);
+            self.error(SemanticErrorType::InvalidOperation, message, karyanvaya.dharma.span);
+            return;
+        }
+        if let Some(&first) = self.impls.get(&(dharma, gunadharma)) {
+            let message = format!("conflicting implementations of `{}` for `{}`", gunadharma_name, dharma_name);
+            let first = DetailedSemanticError::new(
+                SemanticErrorType::DuplicateSymbol,
+                format!("`{}` is first implemented for `{}` here", gunadharma_name, dharma_name),
+            )
+            .with_span(first);
+            self.errors.push(
+                DetailedSemanticError::new(SemanticErrorType::DuplicateSymbol, message)
+                    .with_span(karyanvaya.span)
+                    .with_related_error(first),
+            );
+            return;
+        }
+        self.impls.insert((dharma, gunadharma), karyanvaya.span);
+
+        for method in &karyanvaya.methods {
+            if let Some(param) = method.type_params.first() {
+                let message = format!("method `{}` cannot have type parameters", method.name);
+                self.error(SemanticErrorType::InvalidOperation, message, param.span);
+            }
+            let signature = self.signature_of(method);
+            match required.iter().find(|(name, _)| *name == method.name) {
+                None => {
+                    let message = format!("`{}` is not a method of `{}`", method.name, gunadharma_name);
+                    self.error(SemanticErrorType::InvalidOperation, message, method.span);
+                }
+                Some(_) if !method.has_receiver() => {
+                    let message = format!("method `{}` must take आत्मन्, as `{}` declares it", method.name, gunadharma_name);
+                    self.error(SemanticErrorType::TypeMismatch, message, method.span);
+                }
+                Some((_, declared)) => {
+                    let agrees = declared.required == signature.required
+                        && self.unifier.unify(&declared.ty(), &signature.ty()).is_ok();
+                    if !agrees {
+                        let message = format!(
+                            "method `{}` has type `{}`, but `{}` declares it as `{}`",
+                            method.name,
+                            self.unifier.resolve(&signature.ty()),
+                            gunadharma_name,
+                            self.unifier.resolve(&declared.ty())
+                        );
+                        self.error(SemanticErrorType::TypeMismatch, message, method.span);
+                    }
+                }
+            }
+            // Methods are called by name alone, so they must not clash
+            // with the dharma's own or those of its other कार्यान्वय
+            if let Entry::Vacant(entry) = self.methods.entry((dharma, method.name.clone())) {
+                entry.insert(signature);
+            } else {
+                let message = format!("`{}` already has a method `{}`", dharma_name, method.name);
+                self.error(SemanticErrorType::DuplicateSymbol, message, method.span);
+            }
+        }
+
+        let missing: Vec<&str> = required
+            .iter()
+            .filter(|(name, _)| !karyanvaya.methods.iter().any(|method| method.name == *name))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            let message = format!(
+                "`{}` does not define `{}` of `{}`",
+                dharma_name,
+                missing.join("`, `"),
+                gunadharma_name
+            );
+            self.error(SemanticErrorType::InvalidOperation, message, karyanvaya.gunadharma.span);
+        }
+    }
+
+    fn check_globals(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Yantra(yantra) => self.check_globals(&yantra.items),
+                Item::Sutra(sutra) => self.check_sutra(sutra),
+                Item::Praarabdha(stmts) => {
+                    for stmt in stmts {
+                        self.check_statement(stmt);
+                    }
+                }
+                Item::Dharma(_) | Item::Mantra(_) | Item::Gunadharma(_) | Item::Karyanvaya(_) | Item::Aanaya(_) => {}
+            }
+        }
+    }
+
+    fn check_bodies(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Dharma(dharma) => {
+                    let id = self.resolution.declared_at(dharma.span);
+                    let fields = id.and_then(|id| self.fields.get(&id)).cloned().unwrap_or_default();
+                    for (field, def) in fields.iter().zip(&dharma.fields) {
+                        if let Some(default) = &def.default_value {
+                            self.check_expr(default, &field.ty);
+                        }
+                    }
+                    for method in &dharma.methods {
+                        self.check_mantra(method);
+                    }
+                }
+                Item::Mantra(mantra) => self.check_mantra(mantra),
+                Item::Karyanvaya(karyanvaya) => {
+                    for method in &karyanvaya.methods {
+                        self.check_mantra(method);
+                    }
+                }
+                Item::Yantra(yantra) => self.check_bodies(&yantra.items),
+                Item::Sutra(_) | Item::Gunadharma(_) | Item::Aanaya(_) | Item::Praarabdha(_) => {}
+            }
+        }
+    }
+
+    fn check_mantra(&mut self, mantra: &MantraDef) {
+        let signature = match self.mantra_signatures.get(&mantra.span) {
+            Some(signature) => signature.clone(),
+            None => self.signature_of(mantra),
+        };
+        let (receiver, params) = mantra.params.split_at(usize::from(mantra.has_receiver()));
+        for param in receiver {
+            // Inside its own methods a generic dharma has its parameters
+            // as arguments
+            let ty = match Ty::lower(&param.ty, self.resolution) {
+                Ty::Dharma(id, name, _) => Ty::Dharma(id, name, self.params_of(id)),
+                ty => ty,
+            };
+            self.declare(param.span, ty);
+        }
+        for (param, ty) in params.iter().zip(signature.params) {
+            if let Some(default) = &param.default_value {
+                self.check_expr(default, &ty);
+            }
+            self.declare(param.span, ty);
+        }
+        let ret = signature.ret;
+        self.in_body(ret.clone(), |checker| {
+            checker.check_block(&mantra.body, Some(&ret));
+        });
+    }
+
+    /// Run `check` for the body of a mantra or lambda returning `ret`
+    fn in_body<T>(&mut self, ret: Ty, check: impl FnOnce(&mut Self) -> T) -> T {
+        self.return_types.push(ret);
+        let loops = std::mem::take(&mut self.loops);
+        let result = check(self);
+        self.loops = loops;
+        self.return_types.pop();
+        result
+    }
+
+    // ===== Statements =====
+
+    fn check_sutra(&mut self, sutra: &SutraDef) {
+        let ty = match &sutra.type_annotation {
+            Some(annotation) => {
+                let ty = self.lower(annotation);
+                self.check_expr(&sutra.value, &ty);
+                ty
+            }
+            None => {
+                let ty = self.infer_expr(&sutra.value);
+                self.unannotated.push((sutra.span, ty.clone()));
+                ty
+            }
+        };
+        self.bind_pattern(&sutra.pattern, &ty, sutra.span);
+    }
+
+    /// Give the variables of `pattern` their types when it matches a value
+    /// of type `ty`. `span` locates errors in patterns without a span.
+    fn bind_pattern(&mut self, pattern: &Pattern, ty: &Ty, span: Span) {
+        let ty = &self.unifier.shallow(ty);
+        match pattern {
+            Pattern::Any(_) => {}
+            Pattern::Literal(literal) => match (literal, ty) {
+                (Literal::Int(n), Ty::Sized(width)) if *n < 0 && !width.is_signed() => {
+                    self.unsigned_negation(*width, span);
+                }
+                (Literal::Int(n), Ty::Sized(width)) => {
+                    self.check_literal_fits(n.unsigned_abs().into(), *n < 0, *width, span);
+                }
+                (Literal::Int(_), ty) if ty.is_integer() => {}
+                (Literal::NegativeTypedInt(_, width), _) if !width.is_signed() => {
+                    self.unsigned_negation(*width, span);
+                }
+                _ => {
+                    self.expect(ty, &literal_ty(literal), span);
+                }
+            },
+            Pattern::Bind { subpattern, span, .. } => {
+                self.declare(*span, ty.clone());
+                if let Some(sub) = subpattern {
+                    self.bind_pattern(sub, ty, *span);
+                }
+            }
+            Pattern::Tuple(elems, span) => match ty {
+                Ty::Tuple(tys) if tys.len() == elems.len() => {
+                    for (elem, ty) in elems.iter().zip(tys) {
+                        self.bind_pattern(elem, ty, *span);
+                    }
+                }
+                Ty::Var(_) => {
+                    let tys: Vec<Ty> = elems.iter().map(|_| self.unifier.fresh()).collect();
+                    self.expect(ty, &Ty::Tuple(tys.clone()), *span);
+                    for (elem, ty) in elems.iter().zip(&tys) {
+                        self.bind_pattern(elem, ty, *span);
+                    }
+                }
+                _ => {
+                    if !matches!(ty, Ty::Unknown | Ty::Object) {
+                        self.mismatch(ty, &Ty::Tuple(vec![Ty::Unknown; elems.len()]), *span);
+                    }
+                    for elem in elems {
+                        self.bind_pattern(elem, &Ty::Unknown, *span);
+                    }
+                }
+            },
+            Pattern::Struct { path, fields, span, .. } => {
+                let dharma = self.dharma_of(path).map(|(_, dharma)| dharma);
+                let dharma = dharma.as_ref();
+                if let Some(dharma) = dharma {
+                    self.expect(ty, dharma, *span);
+                }
+                for field in fields {
+                    let field_ty = match dharma {
+                        Some(dharma) => self.field_ty(dharma, &field.name, field.span),
+                        None => Ty::Unknown,
+                    };
+                    self.bind_pattern(&field.pattern, &field_ty, field.span);
+                }
+            }
+        }
+    }
+
+    /// The dharma a path in a pattern, struct literal or constructor call
+    /// names, with its type arguments to be inferred
+    fn dharma_of(&mut self, path: &Path) -> Option<(SymbolId, Ty)> {
+        let id = self.resolution.use_of(path)?;
+        let symbol = self.resolution.symbol(id);
+        if symbol.kind != SymbolKind::Dharma {
+            return None;
+        }
+        let args = self.instantiate(id, path.span);
+        Some((id, Ty::Dharma(id, symbol.name.clone(), args)))
+    }
+
+    /// Check a block, expecting its value to be of type `expected` if
+    /// given, and return its type
+    fn check_block(&mut self, block: &Block, expected: Option<&Ty>) -> Ty {
+        let expected = expected.map(|ty| self.unifier.shallow(ty));
+        let expected = expected.as_ref();
+        self.collect_signatures(block.stmts.iter().filter_map(|stmt| match stmt {
+            Statement::Item(item) => Some(item),
+            _ => None,
+        }));
+        let mut diverges = false;
+        for stmt in &block.stmts {
+            diverges |= self.check_statement(stmt);
+        }
+        match (&block.expr, expected) {
+            (Some(tail), Some(Ty::Unit)) => {
+                self.infer_expr(tail);
+                Ty::Unit
+            }
+            (Some(tail), Some(expected)) => {
+                self.check_expr(tail, expected);
+                expected.clone()
+            }
+            (Some(tail), None) => self.infer_expr(tail),
+            (None, _) if diverges => Ty::Never,
+            (None, expected) => {
+                if let Some(expected) = expected {
+                    self.expect(expected, &Ty::Unit, block.span);
+                }
+                Ty::Unit
+            }
+        }
+    }
+
+    /// Check a statement and return whether control never continues past it
+    fn check_statement(&mut self, stmt: &Statement) -> bool {
+        match stmt {
+            Statement::Item(item) => {
+                let item = std::slice::from_ref(item);
+                self.check_globals(item);
+                self.check_bodies(item);
+                false
+            }
+            Statement::Sutra(sutra) => {
+                self.check_sutra(sutra);
+                false
+            }
+            Statement::Expr(expr) => self.infer_expr(expr) == Ty::Never,
+            Statement::Block(block) => self.check_block(block, None) == Ty::Never,
+            Statement::Yadi { condition, then_branch, else_branch } => {
+                self.check_expr(condition, &Ty::Bool);
+                let then_diverges = self.check_statement(then_branch);
+                let else_diverges = else_branch.as_ref().map(|stmt| self.check_statement(stmt));
+                then_diverges && else_diverges == Some(true)
+            }
+            Statement::Yaavat { condition, body, label } => {
+                self.check_expr(condition, &Ty::Bool);
+                self.in_loop(label, |checker| checker.check_statement(body));
+                false
+            }
+            Statement::Pratyeka { pattern, iterable, body, label } => {
+                let elem = self.element_ty(iterable);
+                self.bind_pattern(pattern, &elem, iterable.span());
+                self.in_loop(label, |checker| checker.check_statement(body));
+                false
+            }
+            Statement::Pratyahara(value) => {
+                self.check_return(value.as_deref(), None);
+                true
+            }
+            Statement::Nirgama { label, value } => {
+                self.check_break(label, value.as_deref(), None);
+                true
+            }
+            Statement::Anuvrtti(_) => true,
+            Statement::Shunya => false,
+        }
+    }
+
+    /// Check a प्रत्याहर against the return type of the enclosing body
+    fn check_return(&mut self, value: Option<&Expr>, span: Option<Span>) {
+        let expected = self.return_types.last().cloned().unwrap_or(Ty::Unit);
+        match value {
+            Some(value) => self.check_expr(value, &expected),
+            None => {
+                self.expect_at(&expected, &Ty::Unit, span);
+            }
+        }
+    }
+
+    /// Run `check` inside a loop that निर्गम may leave, returning the type
+    /// of the values it is left with, if any
+    fn in_loop<T>(&mut self, label: &Option<LoopLabel>, check: impl FnOnce(&mut Self) -> T) -> Option<Ty> {
+        self.loops.push(LoopFrame { label: label.as_ref().map(|label| label.name.clone()), value: None });
+        check(self);
+        self.loops.pop().and_then(|frame| frame.value)
+    }
+
+    /// Check a निर्गम: every exit of one loop leaves it with the same type
+    fn check_break(&mut self, label: &Option<LoopLabel>, value: Option<&Expr>, span: Option<Span>) {
+        let frame = match label {
+            Some(label) => self.loops.iter().rposition(|frame| frame.label.as_ref() == Some(&label.name)),
+            None => self.loops.len().checked_sub(1),
+        };
+        let found = match (value, frame.and_then(|frame| self.loops[frame].value.clone())) {
+            (Some(value), Some(expected)) => {
+                self.check_expr(value, &expected);
+                return;
+            }
+            (Some(value), None) => self.infer_expr(value),
+            (None, expected) => {
+                if let Some(expected) = expected {
+                    self.expect_at(&expected, &Ty::Unit, span);
+                }
+                Ty::Unit
+            }
+        };
+        if let Some(frame) = frame {
+            self.loops[frame].value.get_or_insert(found);
+        }
+    }
+
+    // ===== Expressions =====
+
+    /// Check `expr` against the type the context expects of it
+    fn check_expr(&mut self, expr: &Expr, expected: &Ty) {
+        let expected = &self.unifier.shallow(expected);
+        match expr {
+            // Unsuffixed integer literals take any integer type they fit
+            Expr::Literal(Literal::Int(n), span) if expected.is_integer() => {
+                if let Ty::Sized(width) = expected {
+                    self.check_literal_fits(n.unsigned_abs().into(), false, *width, *span);
+                }
+            }
+            Expr::Unary(UnaryOp::Negate, operand, span) if expected.is_numeric() => match (expected, &**operand) {
+                (Ty::Sized(width), _) if !width.is_signed() => {
+                    self.unsigned_negation(*width, *span);
+                    self.check_expr(operand, expected);
+                }
+                (Ty::Sized(width), Expr::Literal(Literal::Int(n), _)) => {
+                    self.check_literal_fits(n.unsigned_abs().into(), true, *width, *span);
+                }
+                _ => self.check_expr(operand, expected),
+            },
+            Expr::Array(elems, _) if matches!(expected, Ty::List(_)) => {
+                let Ty::List(elem) = expected else { unreachable!() };
+                for e in elems {
+                    self.check_expr(e, elem);
+                }
+            }
+            Expr::Tuple(elems, _) if matches!(expected, Ty::Tuple(tys) if tys.len() == elems.len()) => {
+                let Ty::Tuple(tys) = expected else { unreachable!() };
+                for (e, ty) in elems.iter().zip(tys) {
+                    self.check_expr(e, ty);
+                }
+            }
+            Expr::Block(block, _) => {
+                self.check_block(block, Some(expected));
+            }
+            Expr::If(condition, then_block, else_expr, span) => {
+                self.check_expr(condition, &Ty::Bool);
+                match else_expr {
+                    Some(else_expr) => {
+                        self.check_block(then_block, Some(expected));
+                        self.check_expr(else_expr, expected);
+                    }
+                    None => {
+                        self.check_block(then_block, Some(&Ty::Unit));
+                        self.expect(expected, &Ty::Unit, *span);
+                    }
+                }
+            }
+            Expr::Match(scrutinee, arms, _) => {
+                let scrutinee = self.infer_expr(scrutinee);
+                for arm in arms {
+                    self.check_arm_head(arm, &scrutinee);
+                    self.check_expr(&arm.body, expected);
+                }
+            }
+            Expr::Lambda(params, body, _) if matches!(expected, Ty::Function(tys, _) if tys.len() == params.len()) => {
+                let Ty::Function(param_tys, ret) = expected else { unreachable!() };
+                for (param, expected) in params.iter().zip(param_tys) {
+                    // The lambda must accept whatever it will be given
+                    let ty = self.lower(&param.ty);
+                    self.expect(&ty, expected, param.span);
+                    self.declare(param.span, ty);
+                }
+                self.in_body((**ret).clone(), |checker| checker.check_expr(body, ret));
+            }
+            _ => {
+                let found = self.infer_expr(expr);
+                self.expect(expected, &found, expr.span());
+            }
+        }
+    }
+
+    /// Infer the type of `expr` from the expression alone
+    fn infer_expr(&mut self, expr: &Expr) -> Ty {
+        match expr {
+            Expr::Literal(literal, _) => literal_ty(literal),
+            Expr::Variable(path, _) => self.variable_ty(path),
+            Expr::FieldAccess(object, name, span) => {
+                let object = self.infer_expr(object);
+                self.field_ty(&object, name, *span)
+            }
+            Expr::MethodCall(receiver, name, _, args, span) => {
+                let receiver = self.infer_expr(receiver);
+                let receiver = self.unifier.shallow(&receiver);
+                let method = match &receiver {
+                    Ty::Dharma(id, _, type_args) => {
+                        let type_args = self.substitution(*id, type_args);
+                        let method = self.methods.get(&(*id, name.clone()));
+                        method.map(|signature| signature.substitute(&type_args))
+                    }
+                    Ty::Gunadharma(id, gunadharma) => {
+                        let method = self.gunadharma_method(*id, name);
+                        if method.is_some() {
+                            let qualified = format!("{}::{}", gunadharma, name);
+                            self.virtual_calls.push((*span, RcStr::new(&qualified)));
+                        }
+                        method
+                    }
+                    // The methods of the गुणधर्म it is bounded by
+                    Ty::Param(id, _) => {
+                        let bounds = self.bounds.get(id).cloned().unwrap_or_default();
+                        bounds
+                            .iter()
+                            .filter(|bound| bound.kind == ConstraintKind::Implements)
+                            .filter_map(|bound| bound.target)
+                            .find_map(|gunadharma| self.gunadharma_method(gunadharma, name))
+                    }
+                    _ => None,
+                };
+                match (method, &receiver) {
+                    (Some(signature), _) => self.check_args(&signature, args, *span),
+                    (None, ty @ (Ty::Dharma(..) | Ty::Gunadharma(..) | Ty::Param(..))) => {
+                        let message = format!("`{}` has no method `{}`", ty, name);
+                        self.error(SemanticErrorType::InvalidOperation, message, *span);
+                        self.infer_args(args)
+                    }
+                    // Methods of builtin types are provided by the runtime
+                    (None, _) => self.infer_args(args),
+                }
+            }
+            Expr::Call(callee, args, span) => self.infer_call(callee, args, *span),
+            Expr::Binary(left, op, right, span) => self.infer_binary(left, *op, right, *span),
+            Expr::Unary(UnaryOp::Negate, operand, span) => {
+                let ty = self.infer_expr(operand);
+                let ty = self.unifier.shallow(&ty);
+                match ty {
+                    Ty::Sized(width) if !width.is_signed() => self.unsigned_negation(width, *span),
+                    ref ty if !ty.is_numeric() && !matches!(ty, Ty::Unknown | Ty::Never | Ty::Var(_)) => {
+                        self.mismatch(&Ty::Int, ty, *span);
+                    }
+                    _ => {}
+                }
+                ty
+            }
+            Expr::Unary(UnaryOp::Not, operand, _) => {
+                self.check_expr(operand, &Ty::Bool);
+                Ty::Bool
+            }
+            Expr::Assign(target, value, _) => {
+                self.check_place(target);
+                let target = self.infer_expr(target);
+                self.check_expr(value, &target);
+                Ty::Unit
+            }
+            Expr::Block(block, _) => self.check_block(block, None),
+            Expr::If(condition, then_block, else_expr, _) => {
+                self.check_expr(condition, &Ty::Bool);
+                match else_expr {
+                    None => {
+                        self.check_block(then_block, Some(&Ty::Unit));
+                        Ty::Unit
+                    }
+                    Some(else_expr) => {
+                        let then_ty = self.check_block(then_block, None);
+                        match self.unifier.shallow(&then_ty) {
+                            Ty::Never => self.infer_expr(else_expr),
+                            then_ty => {
+                                self.check_expr(else_expr, &then_ty);
+                                then_ty
+                            }
+                        }
+                    }
+                }
+            }
+            Expr::Loop(body, label, _) => {
+                // A loop without निर्गम never ends
+                self.in_loop(label, |checker| checker.check_block(body, Some(&Ty::Unit))).unwrap_or(Ty::Never)
+            }
+            Expr::While(condition, body, label, _) => {
+                self.check_expr(condition, &Ty::Bool);
+                self.in_loop(label, |checker| checker.check_block(body, Some(&Ty::Unit)));
+                Ty::Unit
+            }
+            Expr::For(pattern, iterable, body, label, _) => {
+                let elem = self.element_ty(iterable);
+                self.bind_pattern(pattern, &elem, iterable.span());
+                self.in_loop(label, |checker| checker.check_block(body, Some(&Ty::Unit)));
+                Ty::Unit
+            }
+            Expr::Match(scrutinee, arms, _) => {
+                let scrutinee = self.infer_expr(scrutinee);
+                let mut result = Ty::Never;
+                for arm in arms {
+                    self.check_arm_head(arm, &scrutinee);
+                    if self.unifier.shallow(&result) == Ty::Never {
+                        result = self.infer_expr(&arm.body);
+                    } else {
+                        self.check_expr(&arm.body, &result);
+                    }
+                }
+                result
+            }
+            Expr::Return(value, span) => {
+                self.check_return(value.as_deref(), Some(*span));
+                Ty::Never
+            }
+            Expr::Break(label, value, span) => {
+                self.check_break(label, value.as_deref(), Some(*span));
+                Ty::Never
+            }
+            Expr::Continue(..) => Ty::Never,
+            Expr::Lambda(params, body, _) => {
+                let params: Vec<Ty> = params
+                    .iter()
+                    .map(|param| {
+                        let ty = self.lower(&param.ty);
+                        self.declare(param.span, ty.clone());
+                        ty
+                    })
+                    .collect();
+                let ret = self.unifier.fresh();
+                self.in_body(ret.clone(), |checker| checker.check_expr(body, &ret));
+                Ty::Function(params, Box::new(ret))
+            }
+            Expr::Array(elems, _) => match elems.split_first() {
+                None => Ty::List(Box::new(self.unifier.fresh())),
+                Some((first, rest)) => {
+                    let elem = self.infer_expr(first);
+                    for e in rest {
+                        self.check_expr(e, &elem);
+                    }
+                    Ty::List(Box::new(elem))
+                }
+            },
+            Expr::Tuple(elems, _) => Ty::Tuple(elems.iter().map(|e| self.infer_expr(e)).collect()),
+            Expr::Struct(path, fields, span) => self.infer_struct(path, fields, *span),
+            Expr::Range(start, end, _, _) => {
+                let mut elem = Ty::Unknown;
+                for bound in [start, end].into_iter().flatten() {
+                    if elem == Ty::Unknown {
+                        elem = self.infer_expr(bound);
+                        elem = self.unifier.shallow(&elem);
+                        if !elem.is_integer() && !matches!(elem, Ty::Unknown | Ty::Var(_)) {
+                            self.mismatch(&Ty::Int, &elem, bound.span());
+                        }
+                    } else {
+                        self.check_expr(bound, &elem);
+                    }
+                }
+                Ty::List(Box::new(elem))
+            }
+            Expr::Async(body, _) => {
+                self.in_body(Ty::Unknown, |checker| checker.check_block(body, None));
+                Ty::Unknown
+            }
+            Expr::Await(inner, _) => {
+                self.infer_expr(inner);
+                Ty::Unknown
+            }
+            Expr::Try(body, _) => self.check_block(body, None),
+            Expr::Error(_) => Ty::Unknown,
+        }
+    }
+
+    /// Report an assignment target that is not a variable or a field, or
+    /// is a binding not declared `चल`. Dharmas are shared by reference, so
+    /// the fields of any dharma may be assigned.
+    fn check_place(&mut self, target: &Expr) {
+        let (path, span) = match target {
+            Expr::FieldAccess(..) => return,
+            Expr::Variable(path, span) => (path, span),
+            _ => {
+                self.error(
+                    SemanticErrorType::InvalidAssignment,
+                    "only a variable or a field can be assigned to".to_string(),
+                    target.span(),
+                );
+                return;
+            }
+        };
+        let Some(id) = self.resolution.use_of(path) else {
+            return;
+        };
+        let symbol = self.resolution.symbol(id);
+        match symbol.kind {
+            SymbolKind::Variable { mutable: true } | SymbolKind::Param => {}
+            SymbolKind::Variable { mutable: false } => {
+                let message = format!("`{}` is not declared `चल`, so it cannot be assigned to", symbol.name);
+                self.error(SemanticErrorType::InvalidAssignment, message, *span);
+            }
+            _ => {
+                let message = format!("`{}` is not a variable", symbol.name);
+                self.error(SemanticErrorType::InvalidAssignment, message, *span);
+            }
+        }
+    }
+
+    fn variable_ty(&mut self, path: &Path) -> Ty {
+        let Some(id) = self.resolution.use_of(path) else {
+            return Ty::Unknown;
+        };
+        match self.resolution.symbol(id).kind {
+            SymbolKind::Variable { .. } | SymbolKind::Param => self.locals.get(&id).cloned().unwrap_or(Ty::Unknown),
+            SymbolKind::Mantra => self.signature_at(id, path).map_or(Ty::Unknown, |signature| signature.ty()),
+            _ => Ty::Unknown,
+        }
+    }
+
+    /// Type of field `name` of a value of type `object`
+    fn field_ty(&mut self, object: &Ty, name: &RcStr, span: Span) -> Ty {
+        let object = &self.unifier.shallow(object);
+        let field = match object {
+            // The dharma must be known before its fields are used
+            Ty::Var(_) => return Ty::Unknown,
+            Ty::Dharma(id, _, args) => {
+                let args = self.substitution(*id, args);
+                let fields = self.fields.get(id).into_iter().flatten();
+                fields.filter(|f| &f.name == name).map(|f| f.ty.substitute(&args)).next()
+            }
+            Ty::Unknown | Ty::Object => return Ty::Unknown,
+            _ => None,
+        };
+        match field {
+            Some(ty) => ty,
+            None => {
+                let message = format!("`{}` has no field `{}`", object, name);
+                self.error(SemanticErrorType::InvalidOperation, message, span);
+                Ty::Unknown
+            }
+        }
+    }
+
+    /// Type of the elements `प्रत्येक` visits in `iterable`. A निधान yields
+    /// its key and value as a pair.
+    fn element_ty(&mut self, iterable: &Expr) -> Ty {
+        let ty = self.infer_expr(iterable);
+        match self.unifier.shallow(&ty) {
+            Ty::List(elem) => *elem,
+            var @ Ty::Var(_) => {
+                let elem = self.unifier.fresh();
+                self.expect(&var, &Ty::List(Box::new(elem.clone())), iterable.span());
+                elem
+            }
+            Ty::Map(key, value) => Ty::Tuple(vec![*key, *value]),
+            Ty::String => Ty::String,
+            Ty::Unknown | Ty::Object => Ty::Unknown,
+            other => {
+                let message = format!("`{}` cannot be iterated", other);
+                self.error(SemanticErrorType::InvalidOperation, message, iterable.span());
+                Ty::Unknown
+            }
+        }
+    }
+
+    fn check_arm_head(&mut self, arm: &Arm, scrutinee: &Ty) {
+        self.bind_pattern(&arm.pattern, scrutinee, arm.span);
+        match &arm.guard {
+            Some(Guard::If(condition)) => self.check_expr(condition, &Ty::Bool),
+            Some(Guard::IfLet(pattern, value)) => {
+                let ty = self.infer_expr(value);
+                self.bind_pattern(pattern, &ty, arm.span);
+            }
+            None => {}
+        }
+    }
+
+    fn infer_call(&mut self, callee: &Expr, args: &[Expr], span: Span) -> Ty {
+        if let Expr::Variable(path, _) = callee {
+            if let Some(id) = self.resolution.use_of(path) {
+                if let Some(signature) = self.signature_at(id, path) {
+                    return self.check_args(&signature, args, span);
+                }
+                // Calling a dharma makes a new value of it
+                if let Some((_, dharma)) = self.dharma_of(path) {
+                    self.infer_args(args);
+                    return dharma;
+                }
+                // प्रिंट takes any values
+                if self.resolution.symbol(id).kind == SymbolKind::Builtin {
+                    self.infer_args(args);
+                    return Ty::Unit;
+                }
+            }
+        }
+        let callee_ty = self.infer_expr(callee);
+        match self.unifier.shallow(&callee_ty) {
+            var @ Ty::Var(_) => {
+                let params: Vec<Ty> = args.iter().map(|_| self.unifier.fresh()).collect();
+                let ret = self.unifier.fresh();
+                self.expect(&var, &Ty::Function(params.clone(), Box::new(ret.clone())), callee.span());
+                let signature = Signature { required: params.len(), params, ret };
+                self.check_args(&signature, args, span)
+            }
+            Ty::Function(params, ret) => {
+                let signature = Signature { required: params.len(), params, ret: *ret };
+                self.check_args(&signature, args, span)
+            }
+            Ty::Unknown | Ty::Object | Ty::Never => self.infer_args(args),
+            other => {
+                let message = format!("`{}` is not a mantra", other);
+                self.error(SemanticErrorType::InvalidOperation, message, callee.span());
+                self.infer_args(args)
+            }
+        }
+    }
+
+    /// Check the arguments of a call and return its type
+    fn check_args(&mut self, signature: &Signature, args: &[Expr], span: Span) -> Ty {
+        let max = signature.params.len();
+        if args.len() < signature.required || args.len() > max {
+            let expected = if signature.required == max {
+                max.to_string()
+            } else {
+                format!("{} to {}", signature.required, max)
+            };
+            let message = format!("expected {} arguments, found {}", expected, args.len());
+            self.error(SemanticErrorType::InvalidOperation, message, span);
+        }
+        for (i, arg) in args.iter().enumerate() {
+            match signature.params.get(i) {
+                Some(param) => self.check_expr(arg, param),
+                None => {
+                    self.infer_expr(arg);
+                }
+            }
+        }
+        signature.ret.clone()
+    }
+
+    /// Infer the types of arguments to a callee whose signature is not
+    /// known
+    fn infer_args(&mut self, args: &[Expr]) -> Ty {
+        for arg in args {
+            self.infer_expr(arg);
+        }
+        Ty::Unknown
+    }
+
+    fn infer_struct(&mut self, path: &Path, values: &[FieldValue], span: Span) -> Ty {
+        let Some((id, dharma)) = self.dharma_of(path) else {
+            for value in values {
+                self.infer_expr(&value.value);
+            }
+            return Ty::Unknown;
+        };
+        let fields = self.fields.get(&id).cloned().unwrap_or_default();
+        for value in values {
+            match fields.iter().find(|field| field.name == value.name) {
+                Some(field) => self.check_expr(&value.value, &field.ty),
+                None => {
+                    let message = format!("`{}` has no field `{}`", dharma, value.name);
+                    self.error(SemanticErrorType::InvalidOperation, message, value.span);
+                    self.infer_expr(&value.value);
+                }
+            }
+        }
+        for field in &fields {
+            if !field.has_default && !values.iter().any(|value| value.name == field.name) {
+                let message = format!("missing field `{}` of `{}`", field.name, dharma);
+                self.error(SemanticErrorType::InvalidOperation, message, span);
+            }
+        }
+        dharma
+    }
+
+    fn infer_binary(&mut self, left: &Expr, op: BinaryOp, right: &Expr, span: Span) -> Ty {
+        if matches!(op, BinaryOp::And | BinaryOp::Or) {
+            self.check_expr(left, &Ty::Bool);
+            self.check_expr(right, &Ty::Bool);
+            return Ty::Bool;
+        }
+
+        let left_ty = self.infer_expr(left);
+        let left_ty = self.unifier.shallow(&left_ty);
+        let right_ty = self.infer_expr(right);
+        let right_ty = self.unifier.shallow(&right_ty);
+        let open = |ty: &Ty| matches!(ty, Ty::Unknown | Ty::Never | Ty::Var(_));
+        match op {
+            // String concatenation accepts any right operand
+            BinaryOp::Add if left_ty == Ty::String => Ty::String,
+            BinaryOp::Add if matches!(left_ty, Ty::Var(_)) && right_ty == Ty::String => {
+                self.expect(&left_ty, &Ty::String, left.span());
+                Ty::String
+            }
+            BinaryOp::Add | BinaryOp::Subtract | BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::Modulo => {
+                match (&left_ty, &right_ty) {
+                    (l, r) if open(l) && open(r) => {
+                        self.expect(l, r, right.span());
+                        if *l == Ty::Never { r.clone() } else { l.clone() }
+                    }
+                    (l, other) | (other, l) if open(l) && other.is_numeric() => {
+                        self.expect(other, l, span);
+                        other.clone()
+                    }
+                    (l, r) if l == r && self.is_numeric(l) => l.clone(),
+                    (Ty::Float, r) | (r, Ty::Float) if r.is_numeric() => Ty::Float,
+                    // An unsuffixed literal takes the width of the other
+                    // operand; a सङ्ख्या value would have to narrow
+                    (Ty::Int, sized @ Ty::Sized(_)) | (sized @ Ty::Sized(_), Ty::Int) => {
+                        let int = if left_ty == Ty::Int { left } else { right };
+                        if unsuffixed_literal(int) {
+                            self.check_expr(int, sized);
+                        } else {
+                            self.mismatch(&left_ty, &right_ty, right.span());
+                        }
+                        sized.clone()
+                    }
+                    (l, r) if l.is_numeric() && r.is_numeric() => {
+                        self.mismatch(l, r, right.span());
+                        l.clone()
+                    }
+                    _ => {
+                        let message = format!("expected numbers, found `{}` and `{}`", left_ty, right_ty);
+                        self.error(SemanticErrorType::TypeMismatch, message, span);
+                        Ty::Unknown
+                    }
+                }
+            }
+            _ => {
+                // Mixed numbers compare after promotion
+                let numeric = left_ty.is_numeric() && right_ty.is_numeric();
+                let ordering = !matches!(op, BinaryOp::Equal | BinaryOp::NotEqual);
+                if !numeric && self.expect(&left_ty, &right_ty, right.span()) && ordering {
+                    let ty = self.unifier.shallow(&left_ty);
+                    if !self.is_comparable(&ty) && !open(&ty) {
+                        let message = format!("`{}` values cannot be ordered", ty);
+                        self.error(SemanticErrorType::TypeMismatch, message, span);
+                    }
+                }
+                Ty::Bool
+            }
+        }
+    }
+}
+
+impl Checker<'_> {
+    // ===== Generics =====
+
+    /// Collect the type parameters of the dharmas and mantras among `items`
+    fn collect_generics<'i>(&mut self, items: impl IntoIterator<Item = &'i Item>) {
+        for item in items {
+            let (span, params) = match item {
+                Item::Dharma(dharma) => (dharma.span, &dharma.type_params),
+                Item::Mantra(mantra) => (mantra.span, &mantra.type_params),
+                Item::Yantra(yantra) => {
+                    self.collect_generics(&yantra.items);
+                    continue;
+                }
+                Item::Sutra(_)
+                | Item::Gunadharma(_)
+                | Item::Karyanvaya(_)
+                | Item::Aanaya(_)
+                | Item::Praarabdha(_) => continue,
+            };
+            if let (Some(id), false) = (self.resolution.declared_at(span), params.is_empty()) {
+                let generics = params.iter().filter_map(|param| self.generic(param)).collect();
+                self.generics.insert(id, generics);
+            }
+        }
+    }
+
+    fn generic(&mut self, param: &TypeParam) -> Option<Generic> {
+        let id = self.resolution.declared_at(param.span)?;
+        let mut bounds = Vec::new();
+        for bound in &param.bounds {
+            let Some(bound_id) = self.resolution.use_of(&bound.bound) else {
+                continue;
+            };
+            let symbol = self.resolution.symbol(bound_id);
+            let kind = match (symbol.kind, symbol.name.as_str()) {
+                (SymbolKind::BuiltinBound, "तुलनीय") => ConstraintKind::Comparable,
+                (SymbolKind::BuiltinBound, _) => ConstraintKind::Numeric,
+                (SymbolKind::Gunadharma, _) => ConstraintKind::Implements,
+                _ => ConstraintKind::SubtypeOf,
+            };
+            let target = matches!(kind, ConstraintKind::SubtypeOf | ConstraintKind::Implements).then_some(bound_id);
+            bounds.push(Bound { kind, target, name: symbol.name.clone() });
+        }
+        self.bounds.insert(id, bounds.clone());
+        let default = param.default.as_ref().map(|ty| self.lower(ty));
+        Some(Generic { id, name: param.name.clone(), bounds, default })
+    }
+
+    /// The type parameters of a generic item, as types
+    fn params_of(&self, item: SymbolId) -> Vec<Ty> {
+        let generics = self.generics.get(&item).map_or(&[][..], Vec::as_slice);
+        generics.iter().map(|generic| Ty::Param(generic.id, generic.name.clone())).collect()
+    }
+
+    /// The type each parameter of `item` is given by `args`
+    fn substitution(&self, item: SymbolId, args: &[Ty]) -> HashMap<SymbolId, Ty> {
+        let generics = self.generics.get(&item).map_or(&[][..], Vec::as_slice);
+        generics.iter().map(|generic| generic.id).zip(args.iter().cloned()).collect()
+    }
+
+    /// New type variables for the type parameters of `item`, used at
+    /// `span`, whose solutions are checked and recorded
+    fn instantiate(&mut self, item: SymbolId, span: Span) -> Vec<Ty> {
+        let count = self.generics.get(&item).map_or(0, Vec::len);
+        if count == 0 {
+            return Vec::new();
+        }
+        let args: Vec<Ty> = (0..count).map(|_| self.unifier.fresh()).collect();
+        self.bound_checks.push((item, args.clone(), span));
+        self.sites.push(Site { span, item, args: args.clone() });
+        args
+    }
+
+    /// The signature of mantra `id` as used at `path`
+    fn signature_at(&mut self, id: SymbolId, path: &Path) -> Option<Signature> {
+        let signature = self.signatures.get(&id)?.clone();
+        let args = self.instantiate(id, path.span);
+        Some(signature.substitute(&self.substitution(id, &args)))
+    }
+
+    /// Check the number of type arguments of each dharma in a written type,
+    /// `lowered` being its lowered form, and fill in defaults
+    fn check_type_args(&mut self, written: &Type, lowered: Ty) -> Ty {
+        let written_args: Vec<&Type> = match written {
+            Type::Reference(inner, _, _) => return self.check_type_args(inner, lowered),
+            Type::Named(_, args) | Type::Tuple(args, _) => args.iter().collect(),
+            Type::Function(params, ret, _) => params.iter().chain([&**ret]).collect(),
+            Type::Array(elem, _, _) | Type::Slice(elem, _) => vec![&**elem],
+            Type::Never(_) | Type::Infer(_) | Type::Error => Vec::new(),
+        };
+        let mut written_args = written_args.into_iter();
+        let lowered = lowered.map(|arg| match written_args.next() {
+            Some(written) => self.check_type_args(written, arg.clone()),
+            None => arg.clone(),
+        });
+        let (Type::Named(path, _), Ty::Dharma(id, name, mut args)) = (written, lowered.clone()) else {
+            return lowered;
+        };
+        let generics = self.generics.get(&id).cloned().unwrap_or_default();
+        let required = generics.iter().filter(|generic| generic.default.is_none()).count();
+        if args.len() < required || args.len() > generics.len() {
+            let expected = if required == generics.len() {
+                required.to_string()
+            } else {
+                format!("{} to {}", required, generics.len())
+            };
+            let message = format!("expected {} type arguments to `{}`, found {}", expected, name, args.len());
+            self.error(SemanticErrorType::InvalidOperation, message, path.span);
+            args.truncate(generics.len());
+        }
+        while args.len() < generics.len() {
+            let generic = &generics[args.len()];
+            let given = self.substitution(id, &args);
+            args.push(generic.default.as_ref().map_or(Ty::Unknown, |default| default.substitute(&given)));
+        }
+        if !generics.is_empty() {
+            self.bound_checks.push((id, args.clone(), path.span));
+        }
+        Ty::Dharma(id, name, args)
+    }
+
+    /// Whether values of `ty` are numbers
+    fn is_numeric(&self, ty: &Ty) -> bool {
+        ty.is_numeric() || self.param_has(ty, ConstraintKind::Numeric)
+    }
+
+    /// Whether values of `ty` can be ordered
+    fn is_comparable(&self, ty: &Ty) -> bool {
+        ty.is_comparable() || self.param_has(ty, ConstraintKind::Comparable) || self.is_numeric(ty)
+    }
+
+    /// Whether `ty` is a type parameter with a bound of kind `kind`
+    fn param_has(&self, ty: &Ty, kind: ConstraintKind) -> bool {
+        let Ty::Param(id, _) = ty else {
+            return false;
+        };
+        self.bounds.get(id).is_some_and(|bounds| bounds.iter().any(|bound| bound.kind == kind))
+    }
+
+    fn satisfies(&self, ty: &Ty, bound: &Bound) -> bool {
+        match (&bound.kind, ty) {
+            (_, Ty::Unknown | Ty::Never) => true,
+            (ConstraintKind::Numeric, _) => self.is_numeric(ty),
+            (ConstraintKind::Comparable, _) => self.is_comparable(ty),
+            (ConstraintKind::SubtypeOf | ConstraintKind::Equals, Ty::Dharma(id, ..)) => bound.target == Some(*id),
+            (ConstraintKind::SubtypeOf | ConstraintKind::Equals, Ty::Param(id, _)) => self
+                .bounds
+                .get(id)
+                .is_some_and(|bounds| bounds.iter().any(|own| own.kind == bound.kind && own.target == bound.target)),
+            (ConstraintKind::Implements, _) => bound.target.is_some_and(|target| self.implements(ty, target)),
+            _ => false,
+        }
+    }
+
+    /// Whether values of `ty` may be used as the गुणधर्म `gunadharma`
+    fn implements(&self, ty: &Ty, gunadharma: SymbolId) -> bool {
+        match ty {
+            Ty::Dharma(id, ..) => self.impls.contains_key(&(*id, gunadharma)),
+            Ty::Gunadharma(id, _) => *id == gunadharma,
+            Ty::Param(id, _) => self.bounds.get(id).is_some_and(|bounds| {
+                bounds
+                    .iter()
+                    .any(|bound| bound.kind == ConstraintKind::Implements && bound.target == Some(gunadharma))
+            }),
+            _ => false,
+        }
+    }
+
+    /// The signature of method `name` of गुणधर्म `id`
+    fn gunadharma_method(&self, id: SymbolId, name: &RcStr) -> Option<Signature> {
+        let methods = self.gunadharmas.get(&id)?;
+        methods.iter().find(|(method, _)| method == name).map(|(_, signature)| signature.clone())
+    }
+}
+
+/// Whether `expr` is an integer literal without a suffix, possibly negated
+fn unsuffixed_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Literal(Literal::Int(_), _) => true,
+        Expr::Unary(UnaryOp::Negate, operand, _) => matches!(**operand, Expr::Literal(Literal::Int(_), _)),
+        _ => false,
+    }
+}
+
+fn literal_ty(literal: &Literal) -> Ty {
+    match literal {
+        Literal::Int(_) => Ty::Int,
+        Literal::TypedInt(_, width) | Literal::NegativeTypedInt(_, width) => Ty::Sized(*width),
+        Literal::Float(_) => Ty::Float,
+        Literal::Bool(_) => Ty::Bool,
+        Literal::String(_) => Ty::String,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolve::resolve;
+    use vaaktra_lexer::Lexer;
+    use vaaktra_parser::Parser;
+
+    /// Kind, message and source text of each error, in source order
+    fn errors_of(input: &str) -> Vec<(SemanticErrorType, String, &str)> {
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let (resolution, errors) = resolve(&program);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let mut errors = check_program(&program, &resolution);
+        errors.sort_by_key(|e| e.span.map(|span| span.start));
+        errors
+            .into_iter()
+            .map(|e| {
+                let text = e.span.map_or("", |span| &input[span.range()]);
+                (e.error_type, e.message, text)
+            })
+            .collect()
+    }
+
+    fn mismatch<'s>(expected: &str, found: &str, text: &'s str) -> (SemanticErrorType, String, &'s str) {
+        (SemanticErrorType::TypeMismatch, format!("expected `{}`, found `{}`", expected, found), text)
+    }
+
+    fn mismatch_message<'s>(message: &str, text: &'s str) -> (SemanticErrorType, String, &'s str) {
+        (SemanticErrorType::TypeMismatch, message.to_string(), text)
+    }
+
+    #[test]
+    fn test_calls_are_checked_against_parameters() {
+        let input = "मन्त्र योग(अ: सङ्ख्या, ब: सङ्ख्या = १) -> सङ्ख्या { अ धन ब }\n\
+                     योग(१);\nयोग(\"क\");\nयोग(१, २, ३);\nसूत्र ग: शब्द = योग(१, २);\n\
+                     सूत्र फ: (सङ्ख्या) -> सत्यासत्य = |य| य महत्तर ग;";
+        assert_eq!(
+            errors_of(input),
+            [
+                mismatch("सङ्ख्या", "शब्द", "\"क\""),
+                (
+                    SemanticErrorType::InvalidOperation,
+                    "expected 1 to 2 arguments, found 3".to_string(),
+                    "योग(१, २, ३)"
+                ),
+                mismatch("शब्द", "सङ्ख्या", "योग(१, २)"),
+                mismatch("सङ्ख्या", "शब्द", "ग"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conditions_returns_and_block_tails() {
+        let input = "मन्त्र फ(अ: सङ्ख्या) -> सत्यासत्य {\n    यदि अ { प्रत्याहर \"न\"; }\n    अ महत्तर १\n}\n\
+                     मन्त्र ग() -> सङ्ख्या { सूत्र ब = १; }\n\
+                     मन्त्र घ(अ: सत्यासत्य) -> सङ्ख्या { यदि अ { प्रत्याहर १; } अथवा { प्रत्याहर २; } }\n\
+                     मन्त्र ङ() -> दशांश { १.५ गुण २ }";
+        assert_eq!(
+            errors_of(input),
+            [
+                mismatch("सत्यासत्य", "सङ्ख्या", "अ"),
+                mismatch("सत्यासत्य", "शब्द", "\"न\""),
+                mismatch("सङ्ख्या", "शून्य", "{ सूत्र ब = १; }"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fields_lists_and_maps() {
+        let input = "धर्म बिन्दु { सूत्र क: सङ्ख्या; सूत्र ख: सङ्ख्या; }\n\
+                     मन्त्र फ(ब: बिन्दु, स: सूची<बिन्दु>, ध: निधान<शब्द, सङ्ख्या>) {\n\
+                     सूत्र अ: सूची<सङ्ख्या> = [ब.क, ब.ख, ब.ग];\n\
+                     सूत्र द: सूची<शब्द> = [\"द\", १];\n\
+                     प्रत्येक (क, म) मध्ये ध { सूत्र ल: शब्द = म; }\n\
+                     प्रत्येक य मध्ये स { य.क = \"क\"; }\n}";
+        assert_eq!(
+            errors_of(input),
+            [
+                (SemanticErrorType::InvalidOperation, "`बिन्दु` has no field `ग`".to_string(), "ब.ग"),
+                mismatch("शब्द", "सङ्ख्या", "१"),
+                mismatch("शब्द", "सङ्ख्या", "म"),
+                mismatch("सङ्ख्या", "शब्द", "\"क\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsuffixed_literals_fit_sized_integers() {
+        let input = "सूत्र क = २५५u8 धन ३००;\nसूत्र ख = १२७i8 धन ऋण १२८;\nसूत्र ग = १i8 गुण ऋण १२९;\n\
+                     सूत्र घ = ऋण १u8;\nसूत्र ङ = २u16 धन ऋण १;\nसूत्र ट = १;\nसूत्र ठ = ट धन १u8;\n\
+                     सूत्र ड: सङ्ख्या = १u64 धन १;\nविकल्प १u8 { २५६ => सत्य, _ => असत्य };\n\
+                     विकल्प ऋण १i8 { ऋण १२८i8 => सत्य, _ => असत्य };\nविकल्प १u8 { ऋण १u8 => सत्य, _ => असत्य };";
+        let unsigned = "`u8` is unsigned, so it cannot be negated";
+        assert_eq!(
+            errors_of(input),
+            [
+                mismatch_message("`300` does not fit in `u8`", "३००"),
+                mismatch_message("`ऋण 129` does not fit in `i8`", "ऋण १२९"),
+                mismatch_message(unsigned, "ऋण १u8"),
+                mismatch_message("`u16` is unsigned, so it cannot be negated", "ऋण १"),
+                mismatch("सङ्ख्या", "u8", "१u8"),
+                mismatch("सङ्ख्या", "u64", "१u64 धन १"),
+                mismatch_message("`256` does not fit in `u8`", "२५६ => सत्य"),
+                mismatch_message(unsigned, "ऋण १u8 => सत्य"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assignments_need_a_mutable_place() {
+        let input = "मन्त्र फ(प: सङ्ख्या) {\n\
+                     सूत्र अ = १;\nसूत्र चल ब = २;\n\
+                     अ = ३;\nब = ४;\nप = ५;\nअ धन ब = ६;\nफ = फ;\n}";
+        assert_eq!(
+            errors_of(input),
+            [
+                (
+                    SemanticErrorType::InvalidAssignment,
+                    "`अ` is not declared `चल`, so it cannot be assigned to".to_string(),
+                    "अ"
+                ),
+                (
+                    SemanticErrorType::InvalidAssignment,
+                    "only a variable or a field can be assigned to".to_string(),
+                    "अ धन ब"
+                ),
+                (SemanticErrorType::InvalidAssignment, "`फ` is not a variable".to_string(), "फ"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generic_items_are_checked_against_bounds() {
+        let input = "धर्म पेटिका<त> { सूत्र मूल्य: त; मन्त्र ले(आत्मन्) -> त { आत्मन्.मूल्य } }\n\
+                     धर्म युग्म<क, ख = क> { सूत्र प्रथम: क; सूत्र द्वितीय: ख; }\n\
+                     मन्त्र महत्तम<त: तुलनीय>(अ: त, ब: त) -> त { यदि अ महत्तर ब { प्रत्याहर अ; } ब }\n\
+                     मन्त्र योग<त>(अ: त, ब: त) -> त { अ धन ब }\n\
+                     मन्त्र फ(प: पेटिका<सङ्ख्या>, य: युग्म<शब्द>) {\n\
+                     सूत्र अ: शब्द = प.ले();\n\
+                     सूत्र ब: सङ्ख्या = य.द्वितीय;\n\
+                     सूत्र ग: शब्द = महत्तम(१, २);\n\
+                     महत्तम([१], [२]);\n\
+                     पेटिका();\n}\n\
+                     मन्त्र घ(प: पेटिका, य: युग्म<सङ्ख्या, शब्द, सङ्ख्या>) {}";
+        let invalid = |message: &str, text| (SemanticErrorType::InvalidOperation, message.to_string(), text);
+        assert_eq!(
+            errors_of(input),
+            [
+                mismatch_message("expected numbers, found `त` and `त`", "अ धन ब"),
+                mismatch("शब्द", "सङ्ख्या", "प.ले()"),
+                mismatch("सङ्ख्या", "शब्द", "य.द्वितीय"),
+                mismatch("शब्द", "सङ्ख्या", "महत्तम(१, २)"),
+                mismatch_message("`सूची<सङ्ख्या>` does not satisfy the bound `तुलनीय` of `त`", "महत्तम"),
+                (
+                    SemanticErrorType::CannotInfer,
+                    "cannot infer the type arguments of `पेटिका`".to_string(),
+                    "पेटिका"
+                ),
+                invalid("expected 1 type arguments to `पेटिका`, found 0", "पेटिका"),
+                invalid("expected 1 to 2 type arguments to `युग्म`, found 3", "युग्म"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gunadharma_conformance_and_dispatch() {
+        let input = "गुणधर्म आकार { मन्त्र क्षेत्रफल(आत्मन्) -> दशांश; मन्त्र भुजाः(आत्मन्) -> सङ्ख्या; }\n\
+                     धर्म वृत्त { सूत्र त्रिज्या: दशांश; }\n\
+                     धर्म वर्ग { मन्त्र भुजाः(आत्मन्) -> सङ्ख्या { ४ } }\n\
+                     धर्म बिन्दु {}\nधर्म रेखा {}\n\
+                     कार्यान्वय वृत्त: आकार {\n\
+                     मन्त्र क्षेत्रफल(आत्मन्) -> दशांश { आत्मन्.त्रिज्या गुण आत्मन्.त्रिज्या }\n\
+                     मन्त्र भुजाः(आत्मन्) -> सङ्ख्या { ० }\n}\n\
+                     कार्यान्वय वर्ग: आकार {\n\
+                     मन्त्र क्षेत्रफल(आत्मन्) -> सङ्ख्या { १ }\n\
+                     मन्त्र भुजाः(आत्मन्) -> सङ्ख्या { ४ }\n\
+                     मन्त्र परिधि(आत्मन्) -> दशांश { १.० }\n}\n\
+                     कार्यान्वय वृत्त: आकार {}\n\
+                     कार्यान्वय बिन्दु: आकार { मन्त्र भुजाः(आत्मन्) -> सङ्ख्या { ० } }\n\
+                     मन्त्र कुल<त: आकार>(क: त) -> दशांश { क.क्षेत्रफल() }\n\
+                     मन्त्र फ(व: वृत्त) {\n\
+                     सूत्र सब: सूची<आकार> = [व, वर्ग(), रेखा()];\n\
+                     सूत्र आ: आकार = व;\n\
+                     सूत्र अ: शब्द = आ.क्षेत्रफल();\n\
+                     आ.परिधि();\n\
+                     कुल(व);\n\
+                     कुल(आ);\n\
+                     कुल(रेखा());\n}";
+        let invalid = |message: &str, text| (SemanticErrorType::InvalidOperation, message.to_string(), text);
+        let duplicate = |message: &str, text| (SemanticErrorType::DuplicateSymbol, message.to_string(), text);
+        assert_eq!(
+            errors_of(input),
+            [
+                mismatch_message(
+                    "method `क्षेत्रफल` has type `() -> सङ्ख्या`, but `आकार` declares it as `() -> दशांश`",
+                    "मन्त्र क्षेत्रफल(आत्मन्) -> सङ्ख्या { १ }"
+                ),
+                duplicate("`वर्ग` already has a method `भुजाः`", "मन्त्र भुजाः(आत्मन्) -> सङ्ख्या { ४ }"),
+                invalid("`परिधि` is not a method of `आकार`", "मन्त्र परिधि(आत्मन्) -> दशांश { १.० }"),
+                duplicate("conflicting implementations of `आकार` for `वृत्त`", "कार्यान्वय वृत्त: आकार {}"),
+                invalid("`बिन्दु` does not define `क्षेत्रफल` of `आकार`", "आकार"),
+                mismatch("आकार", "रेखा", "रेखा()"),
+                mismatch("शब्द", "दशांश", "आ.क्षेत्रफल()"),
+                invalid("`आकार` has no method `परिधि`", "आ.परिधि()"),
+                mismatch_message("`रेखा` does not satisfy the bound `आकार` of `त`", "कुल"),
+            ]
+        );
+
+        // Calls on a गुणधर्म value are renamed for dispatch through its
+        // vtable; calls on a bounded parameter are left to monomorphization
+        let input = "गुणधर्म आकार { मन्त्र क्षेत्रफल(आत्मन्) -> दशांश; }\n\
+                     मन्त्र कुल<त: आकार>(क: त, आ: आकार) -> दशांश { क.क्षेत्रफल() धन आ.क्षेत्रफल() }";
+        let mut program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let (resolution, _) = resolve(&program);
+        assert!(infer_types(&mut program, &resolution).0.is_empty());
+        let Item::Mantra(mantra) = &program.items[1] else {
+            panic!("Expected mantra");
+        };
+        let Some(Expr::Binary(direct, _, virtual_call, _)) = mantra.body.expr.as_deref() else {
+            panic!("Expected binary expression");
+        };
+        let method_name = |expr: &Expr| match expr {
+            Expr::MethodCall(_, name, ..) => name.to_string(),
+            other => panic!("Expected method call, got {:?}", other),
+        };
+        assert_eq!(method_name(direct), "क्षेत्रफल");
+        assert_eq!(method_name(virtual_call), "आकार::क्षेत्रफल");
+    }
+
+    /// A written type, as it would be written in source
+    fn written(ty: &Type) -> String {
+        let list = |tys: &[Type]| tys.iter().map(written).collect::<Vec<_>>().join(", ");
+        match ty {
+            Type::Named(path, args) if args.is_empty() => path.segments[0].ident.to_string(),
+            Type::Named(path, args) => format!("{}<{}>", path.segments[0].ident, list(args)),
+            Type::Function(params, ret, _) => format!("({}) -> {}", list(params), written(ret)),
+            other => panic!("unexpected type {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_inferred_types_are_written_back() {
+        let input = "सूत्र चल अ = [];\nअ = [१];\nसूत्र फ = |य| य धन १;\nसूत्र ग: सूची<_> = [\"क\"];\nसूत्र घ = [];";
+        let mut program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let (resolution, errors) = resolve(&program);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let errors: Vec<_> = infer_types(&mut program, &resolution)
+            .0
+            .into_iter()
+            .map(|e| (e.error_type, e.message, &input[e.span.unwrap().range()]))
+            .collect();
+        assert_eq!(
+            errors,
+            [(
+                SemanticErrorType::CannotInfer,
+                "cannot infer `सूची<_>`; add a type annotation".to_string(),
+                "सूत्र घ = [];"
+            )]
+        );
+
+        let mut types = Vec::new();
+        for item in &program.items {
+            if let Item::Praarabdha(stmts) = item {
+                let Statement::Sutra(sutra) = &stmts[0] else {
+                    continue;
+                };
+                types.push(written(sutra.type_annotation.as_ref().unwrap()));
+                if let Expr::Lambda(params, _, _) = &sutra.value {
+                    types.push(written(&params[0].ty));
+                }
+            }
+        }
+        assert_eq!(
+            types,
+            ["सूची<सङ्ख्या>", "(सङ्ख्या) -> सङ्ख्या", "सङ्ख्या", "सूची<शब्द>", "सूची<ब्रह्मन्>"]
+        );
+    }
+}
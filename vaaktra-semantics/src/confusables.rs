@@ -0,0 +1,181 @@
+//! Lint for identifiers that look alike
+//!
+//! The lexer already normalizes identifiers to NFC, but distinct
+//! spellings such as `रवाना` and `खाना` or `अाम` and `आम` can still render
+//! the same. This lint warns when two such names are visible in the same
+//! scope, where a reader could mistake one for the other.
+
+use std::fmt;
+
+use vaaktra_lexer::confusable_skeleton;
+use vaaktra_parser::ast::{Item, Param, Program, RcStr, Span, Statement};
+
+use crate::error::{DetailedSemanticError, SemanticErrorType};
+use crate::visit::{walk_pattern, walk_statement, Visit};
+
+/// A name declared while a confusable one is in scope
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfusableName {
+    pub name: RcStr,
+    pub span: Span,
+    /// The name already in scope, and where it was declared
+    pub other: RcStr,
+    pub other_span: Span,
+}
+
+impl fmt::Display for ConfusableName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "भ्रामक नाम (Confusable name): `{}` looks like `{}` declared at {}",
+            self.name, self.other, self.other_span
+        )
+    }
+}
+
+impl ConfusableName {
+    /// A warning at the later name, noting where the other is declared
+    pub fn to_warning(&self) -> DetailedSemanticError {
+        let kind = SemanticErrorType::ConfusableName;
+        DetailedSemanticError::new(kind.clone(), format!("`{}` looks like `{}`", self.name, self.other))
+            .with_span(self.span)
+            .with_related_error(
+                DetailedSemanticError::new(kind, format!("`{}` is declared here", self.other)).with_span(self.other_span),
+            )
+    }
+}
+
+/// Every declaration in `program` that looks like another name in scope
+pub fn confusable_names(program: &Program) -> Vec<ConfusableName> {
+    let mut lint = ConfusablesLint::default();
+    lint.visit_items(&program.items);
+    lint.found
+}
+
+struct Binding {
+    name: RcStr,
+    skeleton: String,
+    span: Span,
+}
+
+#[derive(Default)]
+struct ConfusablesLint {
+    /// Declared names, innermost scope last
+    scopes: Vec<Vec<Binding>>,
+
+    found: Vec<ConfusableName>,
+}
+
+impl ConfusablesLint {
+    fn declare(&mut self, name: &RcStr, span: Span) {
+        let skeleton = confusable_skeleton(name);
+        let clash = self
+            .scopes
+            .iter()
+            .flatten()
+            .find(|b| b.skeleton == skeleton && b.name != *name);
+        if let Some(other) = clash {
+            self.found.push(ConfusableName {
+                name: name.clone(),
+                span,
+                other: other.name.clone(),
+                other_span: other.span,
+            });
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Binding { name: name.clone(), skeleton, span });
+        }
+    }
+}
+
+impl Visit for ConfusablesLint {
+    /// Items of one module: their names first, since they are visible
+    /// throughout, then their bodies
+    fn visit_items(&mut self, items: &[Item]) {
+        self.push_scope();
+        for item in items {
+            match item {
+                Item::Dharma(dharma) => self.declare(&dharma.name, dharma.span),
+                Item::Mantra(mantra) => self.declare(&mantra.name, mantra.span),
+                Item::Yantra(yantra) => self.declare(&yantra.name, yantra.span),
+                Item::Gunadharma(gunadharma) => self.declare(&gunadharma.name, gunadharma.span),
+                Item::Sutra(sutra) => walk_pattern(self, &sutra.pattern),
+                Item::Aanaya(_) | Item::Karyanvaya(_) | Item::Praarabdha(_) => {}
+            }
+        }
+        for item in items {
+            match item {
+                // Its names are declared above
+                Item::Sutra(sutra) => self.visit_expr(&sutra.value),
+                item => self.visit_item(item),
+            }
+        }
+        self.pop_scope();
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Item(item) => self.visit_items(std::slice::from_ref(item)),
+            stmt => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_binding(&mut self, name: &RcStr, _mutable: bool, span: Span) {
+        self.declare(name, span);
+    }
+
+    fn visit_param(&mut self, param: &Param) {
+        self.declare(&param.name, param.span);
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vaaktra_lexer::Lexer;
+    use vaaktra_parser::Parser;
+
+    fn lint(input: &str) -> Vec<(String, String)> {
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        confusable_names(&program)
+            .into_iter()
+            .map(|c| (c.name.to_string(), c.other.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lookalikes_in_one_scope_are_reported() {
+        let found = lint("मन्त्र फ(खाना: सङ्ख्या) { सूत्र रवाना = खाना; }");
+        assert_eq!(found, [("रवाना".to_string(), "खाना".to_string())]);
+    }
+
+    #[test]
+    fn test_lookalikes_in_sibling_scopes_are_not() {
+        assert!(lint("मन्त्र फ(आम: सङ्ख्या) { } मन्त्र ब(अाम: सङ्ख्या) { }").is_empty());
+        assert!(lint("सूत्र अ = |आम| आम; सूत्र ब = |अाम| अाम;").is_empty());
+    }
+
+    #[test]
+    fn test_warning_points_at_both_names() {
+        let input = "सूत्र खाना = १; सूत्र रवाना = २;";
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let warning = confusable_names(&program)[0].to_warning();
+        assert_eq!(warning.error_type, SemanticErrorType::ConfusableName);
+        assert_eq!(&input[warning.span.unwrap().range()], "रवाना");
+        assert_eq!(&input[warning.related_errors[0].span.unwrap().range()], "खाना");
+    }
+
+    #[test]
+    fn test_items_clash_with_later_locals() {
+        let found = lint("मन्त्र फ() { सूत्र योग1 = १; } मन्त्र योग१() { }");
+        assert_eq!(found, [("योग1".to_string(), "योग१".to_string())]);
+    }
+}
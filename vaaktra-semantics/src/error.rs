@@ -42,6 +42,12 @@ pub enum SemanticErrorType {
     
     /// Circular dependency
     CircularDependency,
+    
+    /// Type that could not be inferred
+    CannotInfer,
+    
+    /// Name that looks like another in scope; only ever a warning
+    ConfusableName,
 }
 
 impl DetailedSemanticError {
@@ -101,6 +107,12 @@ impl DetailedSemanticError {
             SemanticErrorType::CircularDependency => {
                 format!("चक्रीय निर्भरता (Circular Dependency): {}", self.message)
             }
+            SemanticErrorType::CannotInfer => {
+                format!("अज्ञात प्रकार (Cannot Infer Type): {}", self.message)
+            }
+            SemanticErrorType::ConfusableName => {
+                format!("भ्रामक नाम (Confusable Name): {}", self.message)
+            }
         }
     }
 }
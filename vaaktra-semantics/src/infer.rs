@@ -0,0 +1,197 @@
+//! Local type inference for Vāktra (वाक्त्र)
+//!
+//! Types that are not written, such as those of a सूत्र without annotation,
+//! a `_`, a lambda parameter or the elements of an empty list, start out as
+//! type variables. The checker solves them by unification as it goes, and
+//! the solved types are then written into the program, so later stages
+//! never see [`Type::Infer`].
+//!
+//! Inference is local: a mantra's signature is not generalized, so every
+//! use of a mantra with a `_` in its signature must agree on one type.
+
+use std::collections::HashMap;
+use vaaktra_parser::ast::{Expr, Path, PathSegment, Program, RcStr, Span, SutraDef, Type};
+
+use crate::ty::Ty;
+use crate::walk::{walk_expr, walk_program, walk_sutra, walk_type, VisitMut};
+
+/// Why two types could not be unified
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnifyError {
+    Mismatch,
+    /// Binding the variable would make its type contain itself
+    Infinite,
+}
+
+/// Bindings of type variables, built up by unification
+#[derive(Debug, Default)]
+pub struct Unifier {
+    bindings: Vec<Option<Ty>>,
+}
+
+impl Unifier {
+    /// A new unbound type variable
+    pub fn fresh(&mut self) -> Ty {
+        self.bindings.push(None);
+        Ty::Var(self.bindings.len() as u32 - 1)
+    }
+
+    /// `ty` with bound variables at its top replaced by their bindings
+    pub fn shallow(&self, ty: &Ty) -> Ty {
+        let mut ty = ty.clone();
+        while let Ty::Var(var) = ty {
+            match &self.bindings[var as usize] {
+                Some(bound) => ty = bound.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    /// `ty` with every bound variable replaced by its binding
+    pub fn resolve(&self, ty: &Ty) -> Ty {
+        self.shallow(ty).map(|ty| self.resolve(ty))
+    }
+
+    /// Whether `var` appears in `ty`
+    fn occurs(&self, var: u32, ty: &Ty) -> bool {
+        match self.shallow(ty) {
+            Ty::Var(other) => other == var,
+            ty => ty.children().into_iter().any(|ty| self.occurs(var, ty)),
+        }
+    }
+
+    /// Bind variables so that a value of type `found` may be used where
+    /// `expected` is. Nothing converts implicitly, but an unknown type
+    /// matches anything, a ब्रह्मन् holds anything and a never-returning
+    /// expression fits anywhere.
+    pub fn unify(&mut self, expected: &Ty, found: &Ty) -> Result<(), UnifyError> {
+        let (expected, found) = (self.shallow(expected), self.shallow(found));
+        match (&expected, &found) {
+            (Ty::Unknown, _) | (_, Ty::Unknown) | (Ty::Object, _) | (_, Ty::Never) => Ok(()),
+            (Ty::Var(a), Ty::Var(b)) if a == b => Ok(()),
+            (Ty::Var(var), ty) | (ty, Ty::Var(var)) => {
+                if self.occurs(*var, ty) {
+                    return Err(UnifyError::Infinite);
+                }
+                self.bindings[*var as usize] = Some(ty.clone());
+                Ok(())
+            }
+            (Ty::List(a), Ty::List(b)) => self.unify(a, b),
+            (Ty::Map(k1, v1), Ty::Map(k2, v2)) => {
+                self.unify(k1, k2)?;
+                self.unify(v1, v2)
+            }
+            (Ty::Tuple(a), Ty::Tuple(b)) if a.len() == b.len() => {
+                a.iter().zip(b).try_for_each(|(a, b)| self.unify(a, b))
+            }
+            (Ty::Function(p1, r1), Ty::Function(p2, r2)) if p1.len() == p2.len() => {
+                p1.iter().zip(p2).try_for_each(|(a, b)| self.unify(a, b))?;
+                self.unify(r1, r2)
+            }
+            (Ty::Dharma(a, _, args1), Ty::Dharma(b, _, args2)) if a == b && args1.len() == args2.len() => {
+                args1.iter().zip(args2).try_for_each(|(a, b)| self.unify(a, b))
+            }
+            _ if expected == found => Ok(()),
+            _ => Err(UnifyError::Mismatch),
+        }
+    }
+}
+
+/// Solved types to write into a program
+#[derive(Debug, Default)]
+pub struct Solved {
+    /// Types for each `_`, by its span
+    pub holes: HashMap<Span, Type>,
+    /// Annotations for each सूत्र written without one, by its span
+    pub sutras: HashMap<Span, Type>,
+    /// Method calls on a गुणधर्म value, by span, with the `गुणधर्म::मन्त्र`
+    /// name they are dispatched by
+    pub virtual_calls: HashMap<Span, RcStr>,
+}
+
+/// Write the types of `solved` into `program`
+pub fn write_back(program: &mut Program, solved: &Solved) {
+    walk_program(&mut WriteBack(solved), program);
+}
+
+/// The syntax for a solved type. Variables are not expected here; a type
+/// that is still unknown is written as ब्रह्मन्, which holds any value.
+pub fn to_type(ty: &Ty, span: Span) -> Type {
+    let named = |name: &str, args: Vec<Type>| {
+        let segment = PathSegment { ident: RcStr::new(name), args: None };
+        Type::Named(Path { segments: vec![segment], span }, args)
+    };
+    match ty {
+        Ty::Int => named("सङ्ख्या", Vec::new()),
+        Ty::Sized(width) => named(width.suffix(), Vec::new()),
+        Ty::Float => named("दशांश", Vec::new()),
+        Ty::Bool => named("सत्यासत्य", Vec::new()),
+        Ty::String => named("शब्द", Vec::new()),
+        Ty::Unit => named("शून्य", Vec::new()),
+        Ty::Object | Ty::Unknown | Ty::Var(_) => named("ब्रह्मन्", Vec::new()),
+        Ty::List(elem) => named("सूची", vec![to_type(elem, span)]),
+        Ty::Map(key, value) => named("निधान", vec![to_type(key, span), to_type(value, span)]),
+        Ty::Tuple(elems) => Type::Tuple(elems.iter().map(|t| to_type(t, span)).collect(), span),
+        Ty::Function(params, ret) => {
+            Type::Function(params.iter().map(|t| to_type(t, span)).collect(), Box::new(to_type(ret, span)), span)
+        }
+        Ty::Dharma(_, name, args) => named(name, args.iter().map(|t| to_type(t, span)).collect()),
+        Ty::Gunadharma(_, name) | Ty::Param(_, name) => named(name, Vec::new()),
+        Ty::Never => Type::Never(span),
+    }
+}
+
+/// Writes the types of a [`Solved`] into the program it visits
+struct WriteBack<'s>(&'s Solved);
+
+impl VisitMut for WriteBack<'_> {
+    fn visit_sutra(&mut self, sutra: &mut SutraDef) {
+        if sutra.type_annotation.is_none() {
+            sutra.type_annotation = self.0.sutras.get(&sutra.span).cloned();
+        }
+        walk_sutra(self, sutra);
+    }
+
+    fn visit_type(&mut self, ty: &mut Type) {
+        match ty {
+            Type::Infer(span) => {
+                if let Some(solved) = self.0.holes.get(span) {
+                    *ty = solved.clone();
+                }
+            }
+            _ => walk_type(self, ty),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        if let Expr::MethodCall(_, name, _, _, span) = expr {
+            if let Some(qualified) = self.0.virtual_calls.get(span) {
+                *name = qualified.clone();
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unify_binds_and_checks_occurrence() {
+        let mut unifier = Unifier::default();
+        let (a, b) = (unifier.fresh(), unifier.fresh());
+        let list_of_b = Ty::List(Box::new(b.clone()));
+        assert_eq!(unifier.unify(&a, &list_of_b), Ok(()));
+        assert_eq!(unifier.unify(&b, &Ty::Int), Ok(()));
+        assert_eq!(unifier.resolve(&a), Ty::List(Box::new(Ty::Int)));
+
+        let c = unifier.fresh();
+        assert_eq!(unifier.unify(&c, &Ty::List(Box::new(c.clone()))), Err(UnifyError::Infinite));
+        assert_eq!(unifier.unify(&a, &Ty::List(Box::new(Ty::String))), Err(UnifyError::Mismatch));
+        // A ब्रह्मन् holds anything without binding it
+        assert_eq!(unifier.unify(&Ty::Object, &c), Ok(()));
+        assert_eq!(unifier.resolve(&c), c);
+    }
+}
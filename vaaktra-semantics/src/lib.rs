@@ -3,13 +3,20 @@
 //! Provides type checking, symbol resolution, and semantic validation
 //! inspired by Vedic principles of knowledge (ज्ञान) and understanding (बोध).
 
-pub mod analyzer;
+pub mod captures;
+pub mod check;
+pub mod confusables;
+pub mod infer;
+pub mod mono;
+pub mod resolve;
+pub mod ty;
 pub mod type_checker;
 pub mod symbol_table;
 pub mod error;
+pub mod visit;
+pub mod walk;
 
-use std::collections::HashMap;
-use vaaktra_parser::ast::{Program, Item, Type, Expr};
+use vaaktra_parser::ast::Program;
 use thiserror::Error;
 
 /// Semantic analysis errors
@@ -35,127 +42,77 @@ pub type SemanticResult<T> = Result<T, SemanticError>;
 
 /// Main semantic analyzer for Vāktra programs
 pub struct VaaktraSemanticAnalyzer {
-    /// Symbol table for tracking declarations
-    symbol_table: symbol_table::SymbolTable,
-    
     /// Type checker for validating types
     type_checker: type_checker::TypeChecker,
     
-    /// Current analysis context
-    context: AnalysisContext,
-}
-
-/// Analysis context for tracking current scope and state
-#[derive(Debug, Default)]
-pub struct AnalysisContext {
-    /// Current function being analyzed
-    current_function: Option<String>,
-    
-    /// Current class being analyzed
-    current_class: Option<String>,
-    
-    /// Loop nesting level
-    loop_depth: usize,
+    /// Symbol each name refers to
+    resolution: resolve::Resolution,
     
-    /// Return type of current function
-    expected_return_type: Option<Type>,
+    /// Errors found so far, with their locations
+    errors: error::ErrorCollector,
 }
 
 impl VaaktraSemanticAnalyzer {
     /// Create a new semantic analyzer
     pub fn new() -> Self {
         VaaktraSemanticAnalyzer {
-            symbol_table: symbol_table::SymbolTable::new(),
             type_checker: type_checker::TypeChecker::new(),
-            context: AnalysisContext::default(),
+            resolution: resolve::Resolution::default(),
+            errors: error::ErrorCollector::new(),
         }
     }
     
-    /// Analyze a complete program
-    pub fn analyze_program(&mut self, program: &Program) -> SemanticResult<()> {
+    /// Analyze a complete program, filling in the types it leaves out
+    pub fn analyze_program(&mut self, program: &mut Program) -> SemanticResult<()> {
         log::info!("Starting semantic analysis of Vāktra program");
         
-        // First pass: collect all declarations
-        for item in &program.items {
-            self.collect_declarations(item)?;
+        // Resolve names first; later passes look symbols up by id
+        let (resolution, errors) = resolve::resolve(program);
+        self.resolution = resolution;
+        for error in errors {
+            self.errors.add_error(error);
         }
-        
-        // Second pass: analyze implementations
-        for item in &program.items {
-            self.analyze_item(item)?;
+        // Lookalike names are legal, so they only warn
+        for confusable in confusables::confusable_names(program) {
+            self.errors.add_warning(confusable.to_warning());
+        }
+        if let Some(error) = self.errors.errors().first() {
+            return Err(match error.error_type {
+                error::SemanticErrorType::DuplicateSymbol => SemanticError::DuplicateSymbol(error.message.clone()),
+                _ => SemanticError::SymbolNotFound(error.message.clone()),
+            });
         }
         
-        log::info!("Semantic analysis completed successfully");
-        Ok(())
-    }
-    
-    /// Collect declarations in first pass
-    fn collect_declarations(&mut self, item: &Item) -> SemanticResult<()> {
-        match item {
-            Item::Mantra(mantra) => {
-                self.symbol_table.declare_function(
-                    &mantra.name,
-                    &mantra.params,
-                    &mantra.return_type,
-                )?;
-            }
-            Item::Dharma(dharma) => {
-                self.symbol_table.declare_class(&dharma.name, &dharma.fields)?;
-            }
-            Item::Sutra(sutra) => {
-                self.symbol_table.declare_variable(&sutra.name, &sutra.var_type)?;
-            }
-            _ => {}
+        // Then types, which needs every name resolved. Inferred types are
+        // written back so later passes never see `_`.
+        let (errors, instances) = check::infer_types(program, &self.resolution);
+        for error in errors {
+            self.errors.add_error(error);
         }
-        Ok(())
-    }
-    
-    /// Analyze an item in second pass
-    fn analyze_item(&mut self, item: &Item) -> SemanticResult<()> {
-        match item {
-            Item::Mantra(mantra) => {
-                self.context.current_function = Some(mantra.name.clone());
-                self.context.expected_return_type = Some(mantra.return_type.clone());
-                
-                // Analyze function body
-                if let Some(body) = &mantra.body {
-                    for statement in body {
-                        self.analyze_statement(statement)?;
-                    }
-                }
-                
-                self.context.current_function = None;
-                self.context.expected_return_type = None;
-            }
-            Item::Dharma(dharma) => {
-                self.context.current_class = Some(dharma.name.clone());
-                
-                // Analyze class methods
-                for method in &dharma.methods {
-                    self.analyze_item(&Item::Mantra(method.clone()))?;
-                }
-                
-                self.context.current_class = None;
-            }
-            _ => {}
+        if let Some(error) = self.errors.errors().first() {
+            return Err(SemanticError::TypeError(error.message.clone()));
         }
+        
+        // The bytecode compiler and the JIT only see concrete types
+        mono::monomorphize(program, &self.resolution, &instances);
+        
+        log::info!("Semantic analysis completed successfully");
         Ok(())
     }
     
-    /// Analyze a statement
-    fn analyze_statement(&mut self, statement: &vaaktra_parser::ast::Statement) -> SemanticResult<()> {
-        // Implementation would analyze different statement types
-        Ok(())
+    /// Get the type checker
+    pub fn type_checker(&self) -> &type_checker::TypeChecker {
+        &self.type_checker
     }
     
-    /// Get the symbol table
-    pub fn symbol_table(&self) -> &symbol_table::SymbolTable {
-        &self.symbol_table
+    /// Get the result of name resolution
+    pub fn resolution(&self) -> &resolve::Resolution {
+        &self.resolution
     }
     
-    /// Get the type checker
-    pub fn type_checker(&self) -> &type_checker::TypeChecker {
-        &self.type_checker
+    /// Get the errors found, with their locations
+    pub fn errors(&self) -> &error::ErrorCollector {
+        &self.errors
     }
 }
 
@@ -0,0 +1,395 @@
+//! Monomorphization for Vāktra (वाक्त्र)
+//!
+//! The VM's bytecode compiler and the JIT only see concrete types. After
+//! checking, each generic dharma and mantra is replaced by one copy per
+//! list of type arguments it is used with, named after them, such as
+//! `पेटिका<सङ्ख्या>`, and every use is renamed to its copy. Uses inside a
+//! generic item take the arguments of the copy being made, so a generic
+//! mantra that calls another instantiates it in turn. A generic item that
+//! is never used with concrete arguments is dropped.
+//!
+//! Written types name their arguments; for calls, constructors and
+//! patterns the checker records the inferred arguments in [`Instances`].
+
+use std::collections::{HashMap, HashSet};
+use vaaktra_parser::ast::{DharmaDef, Item, MantraDef, Path, PathSegment, Program, RcStr, Span, Statement, Type};
+
+use crate::infer;
+use crate::resolve::{Resolution, SymbolId};
+use crate::ty::Ty;
+use crate::visit::{self, Visit};
+use crate::walk::{walk_dharma, walk_item, walk_mantra, walk_program, walk_type, VisitMut};
+
+/// Type arguments inferred for the uses of generic items
+#[derive(Debug, Default)]
+pub struct Instances {
+    /// Item and type arguments, by the span of the path naming the item
+    sites: HashMap<Span, (SymbolId, Vec<Ty>)>,
+}
+
+impl Instances {
+    pub(crate) fn insert(&mut self, span: Span, item: SymbolId, args: Vec<Ty>) {
+        self.sites.insert(span, (item, args));
+    }
+
+    /// The item used at the path with span `span`, and its type arguments
+    pub fn at(&self, span: Span) -> Option<(SymbolId, &[Ty])> {
+        self.sites.get(&span).map(|(item, args)| (*item, args.as_slice()))
+    }
+}
+
+/// Replace the generic items of `program` by concrete copies
+pub fn monomorphize(program: &mut Program, resolution: &Resolution, instances: &Instances) {
+    let mut collect = CollectGenerics { resolution, generics: HashMap::new() };
+    visit::walk_program(&mut collect, program);
+
+    let mut mono = Mono {
+        resolution,
+        instances,
+        dharmas_by_name: collect
+            .generics
+            .iter()
+            .filter_map(|(id, item)| match item {
+                Item::Dharma(dharma) => Some((dharma.name.clone(), *id)),
+                _ => None,
+            })
+            .collect(),
+        generics: collect.generics,
+        subst: HashMap::new(),
+        param_names: HashMap::new(),
+        this: None,
+        seen: HashSet::new(),
+        queue: Vec::new(),
+        copies: HashMap::new(),
+    };
+    // Rename uses in the concrete code, which queues the copies it needs,
+    // then make those copies, which may queue more
+    walk_program(&mut mono, program);
+    while let Some((name, id, args)) = mono.queue.pop() {
+        mono.instantiate(name, id, args);
+    }
+
+    let mut place = Place { resolution, copies: mono.copies };
+    walk_program(&mut place, program);
+}
+
+/// The symbol of a dharma or mantra with type parameters
+fn generic_id(item: &Item, resolution: &Resolution) -> Option<SymbolId> {
+    match item {
+        Item::Dharma(DharmaDef { type_params, span, .. }) | Item::Mantra(MantraDef { type_params, span, .. })
+            if !type_params.is_empty() =>
+        {
+            resolution.declared_at(*span)
+        }
+        _ => None,
+    }
+}
+
+/// The name of a type as it is written, for naming copies
+fn type_name(ty: &Type) -> String {
+    let list = |tys: &[Type]| tys.iter().map(type_name).collect::<Vec<_>>().join(", ");
+    match ty {
+        Type::Named(path, args) => {
+            let name = path.segments.last().map_or("", |segment| segment.ident.as_str());
+            if args.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}<{}>", name, list(args))
+            }
+        }
+        Type::Tuple(elems, _) if elems.len() == 1 => format!("({},)", type_name(&elems[0])),
+        Type::Tuple(elems, _) => format!("({})", list(elems)),
+        Type::Function(params, ret, _) => format!("({}) -> {}", list(params), type_name(ret)),
+        Type::Reference(inner, _, _) => type_name(inner),
+        Type::Array(elem, _, _) | Type::Slice(elem, _) => format!("सूची<{}>", type_name(elem)),
+        Type::Never(_) => "!".to_string(),
+        Type::Infer(_) | Type::Error => "_".to_string(),
+    }
+}
+
+fn single_segment(name: RcStr, span: Span) -> Path {
+    Path { segments: vec![PathSegment { ident: name, args: None }], span }
+}
+
+struct CollectGenerics<'r> {
+    resolution: &'r Resolution,
+    generics: HashMap<SymbolId, Item>,
+}
+
+impl Visit for CollectGenerics<'_> {
+    fn visit_item(&mut self, item: &Item) {
+        if let Some(id) = generic_id(item, self.resolution) {
+            self.generics.insert(id, item.clone());
+        }
+        visit::walk_item(self, item);
+    }
+}
+
+struct Mono<'r> {
+    resolution: &'r Resolution,
+    instances: &'r Instances,
+
+    /// Generic dharmas and mantras as declared
+    generics: HashMap<SymbolId, Item>,
+
+    /// Generic dharmas by name, for types written back by inference, whose
+    /// paths were never resolved
+    dharmas_by_name: HashMap<RcStr, SymbolId>,
+
+    /// Type arguments of the copy being made, by parameter
+    subst: HashMap<SymbolId, Type>,
+
+    /// Parameters of the copy being made, by name, for written back types
+    param_names: HashMap<RcStr, SymbolId>,
+
+    /// The generic dharma being copied and the name of its copy, for the
+    /// receivers of its methods
+    this: Option<(SymbolId, RcStr)>,
+
+    /// Names of the copies queued so far
+    seen: HashSet<RcStr>,
+    queue: Vec<(RcStr, SymbolId, Vec<Type>)>,
+
+    /// Copies made of each generic item
+    copies: HashMap<SymbolId, Vec<Item>>,
+}
+
+impl Mono<'_> {
+    fn type_params(&self, id: SymbolId) -> &[vaaktra_parser::ast::TypeParam] {
+        match &self.generics[&id] {
+            Item::Dharma(dharma) => &dharma.type_params,
+            Item::Mantra(mantra) => &mantra.type_params,
+            _ => &[],
+        }
+    }
+
+    /// The type parameter of the current copy that `path` names
+    fn param_of(&self, path: &Path) -> Option<SymbolId> {
+        match self.resolution.use_of(path) {
+            Some(id) => self.subst.contains_key(&id).then_some(id),
+            None if path.segments.len() == 1 => self.param_names.get(&path.segments[0].ident).copied(),
+            None => None,
+        }
+    }
+
+    /// The generic dharma that `path` in a type names
+    fn generic_dharma_of(&self, path: &Path) -> Option<SymbolId> {
+        let id = match self.resolution.use_of(path) {
+            Some(id) => id,
+            None if path.segments.len() == 1 => *self.dharmas_by_name.get(&path.segments[0].ident)?,
+            None => return None,
+        };
+        matches!(self.generics.get(&id), Some(Item::Dharma(_))).then_some(id)
+    }
+
+    /// The name of the copy of `id` for `args`, which is queued if new.
+    /// Missing arguments take their defaults.
+    fn copy_name(&mut self, id: SymbolId, mut args: Vec<Type>) -> RcStr {
+        let params = self.type_params(id).to_vec();
+        if args.len() < params.len() {
+            // Defaults may name the parameters before them
+            let outer = (std::mem::take(&mut self.subst), std::mem::take(&mut self.param_names));
+            for (i, param) in params.iter().enumerate() {
+                if i >= args.len() {
+                    let mut default = param.default.clone().unwrap_or(Type::Error);
+                    self.visit_type(&mut default);
+                    args.push(default);
+                }
+                if let Some(param_id) = self.resolution.declared_at(param.span) {
+                    self.subst.insert(param_id, args[i].clone());
+                    self.param_names.insert(param.name.clone(), param_id);
+                }
+            }
+            (self.subst, self.param_names) = outer;
+        }
+
+        let item_name = match &self.generics[&id] {
+            Item::Dharma(dharma) => &dharma.name,
+            Item::Mantra(mantra) => &mantra.name,
+            _ => unreachable!("only dharmas and mantras are generic"),
+        };
+        let names: Vec<String> = args.iter().map(type_name).collect();
+        let name = RcStr::new(&format!("{}<{}>", item_name, names.join(", ")));
+        if self.seen.insert(name.clone()) {
+            self.queue.push((name.clone(), id, args));
+        }
+        name
+    }
+
+    /// Make the copy of generic item `id` named `name` for `args`
+    fn instantiate(&mut self, name: RcStr, id: SymbolId, args: Vec<Type>) {
+        let mut item = self.generics[&id].clone();
+        self.subst.clear();
+        self.param_names.clear();
+        for (param, arg) in self.type_params(id).to_vec().into_iter().zip(args) {
+            if let Some(param_id) = self.resolution.declared_at(param.span) {
+                self.subst.insert(param_id, arg);
+                self.param_names.insert(param.name, param_id);
+            }
+        }
+        match &mut item {
+            Item::Dharma(dharma) => {
+                dharma.name = name.clone();
+                dharma.type_params.clear();
+                self.this = Some((id, name));
+                walk_dharma(self, dharma);
+                self.this = None;
+            }
+            Item::Mantra(mantra) => {
+                mantra.name = name;
+                mantra.type_params.clear();
+                walk_mantra(self, mantra);
+            }
+            _ => {}
+        }
+        self.copies.entry(id).or_default().push(item);
+    }
+}
+
+impl VisitMut for Mono<'_> {
+    fn visit_item(&mut self, item: &mut Item) {
+        // Generic items are only visited as copies
+        if generic_id(item, self.resolution).is_none() {
+            walk_item(self, item);
+        }
+    }
+
+    fn visit_type(&mut self, ty: &mut Type) {
+        walk_type(self, ty);
+        let Type::Named(path, args) = ty else {
+            return;
+        };
+        if let Some(param) = self.param_of(path) {
+            *ty = self.subst[&param].clone();
+            return;
+        }
+        let Some(id) = self.generic_dharma_of(path) else {
+            return;
+        };
+        let name = match &self.this {
+            Some((this, name)) if *this == id && args.is_empty() => name.clone(),
+            _ => self.copy_name(id, std::mem::take(args)),
+        };
+        *path = single_segment(name, path.span);
+    }
+
+    fn visit_path(&mut self, path: &mut Path) {
+        let Some((id, tys)) = self.instances.at(path.span) else {
+            return;
+        };
+        // Zero-width spans, which no path in the source has, so that the
+        // parameters in the arguments are found by name
+        let span = Span::new(path.span.end, path.span.end, path.span.file_id);
+        let args = tys
+            .iter()
+            .map(|ty| {
+                let mut arg = infer::to_type(ty, span);
+                self.visit_type(&mut arg);
+                arg
+            })
+            .collect();
+        let name = self.copy_name(id, args);
+        *path = single_segment(name, path.span);
+    }
+}
+
+/// Puts the copies of each generic item where the item was declared
+struct Place<'r> {
+    resolution: &'r Resolution,
+    copies: HashMap<SymbolId, Vec<Item>>,
+}
+
+impl VisitMut for Place<'_> {
+    fn visit_items(&mut self, items: &mut Vec<Item>) {
+        for mut item in std::mem::take(items) {
+            match generic_id(&item, self.resolution) {
+                Some(id) => items.extend(self.copies.remove(&id).unwrap_or_default()),
+                None => {
+                    self.visit_item(&mut item);
+                    items.push(item);
+                }
+            }
+        }
+    }
+
+    fn visit_statements(&mut self, stmts: &mut Vec<Statement>) {
+        for mut stmt in std::mem::take(stmts) {
+            let generic = match &stmt {
+                Statement::Item(item) => generic_id(item, self.resolution),
+                _ => None,
+            };
+            match generic {
+                Some(id) => {
+                    let copies = self.copies.remove(&id).unwrap_or_default();
+                    stmts.extend(copies.into_iter().map(Statement::Item));
+                }
+                None => {
+                    self.visit_statement(&mut stmt);
+                    stmts.push(stmt);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::infer_types;
+    use crate::resolve::resolve;
+    use vaaktra_lexer::Lexer;
+    use vaaktra_parser::ast::{Expr, SutraDef};
+    use vaaktra_parser::Parser;
+
+    fn name_of(item: &Item) -> &str {
+        match item {
+            Item::Dharma(dharma) => dharma.name.as_str(),
+            Item::Mantra(mantra) => mantra.name.as_str(),
+            _ => "",
+        }
+    }
+
+    fn path_name(path: &Path) -> &str {
+        path.segments[0].ident.as_str()
+    }
+
+    #[test]
+    fn test_generic_items_are_replaced_by_copies() {
+        let input = "धर्म पेटिका<त> { सूत्र मूल्य: त; मन्त्र ले(आत्मन्) -> त { आत्मन्.मूल्य } }\n\
+                     मन्त्र प्रथम<त>(सू: सूची<त>) -> पेटिका<त> { पेटिका() }\n\
+                     मन्त्र अप्रयुक्त<त>() {}\n\
+                     मन्त्र फ() -> सङ्ख्या { सूत्र प = प्रथम([१]); प.ले() }\n\
+                     सूत्र श: पेटिका<शब्द> = प्रथम([\"क\"]);";
+        let mut program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        let (resolution, errors) = resolve(&program);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let (errors, instances) = infer_types(&mut program, &resolution);
+        assert!(errors.is_empty(), "{:?}", errors);
+        monomorphize(&mut program, &resolution, &instances);
+
+        let names: Vec<&str> = program.items.iter().map(name_of).collect();
+        assert_eq!(names, ["पेटिका<शब्द>", "पेटिका<सङ्ख्या>", "प्रथम<शब्द>", "प्रथम<सङ्ख्या>", "फ", ""]);
+
+        // Types inside a copy take its arguments
+        let Item::Dharma(boxed) = &program.items[1] else { unreachable!() };
+        assert_eq!(type_name(&boxed.fields[0].ty), "सङ्ख्या");
+        assert_eq!(type_name(&boxed.methods[0].return_type), "सङ्ख्या");
+        let Item::Mantra(first) = &program.items[3] else { unreachable!() };
+        assert_eq!(type_name(&first.params[0].ty), "सूची<सङ्ख्या>");
+        assert_eq!(type_name(&first.return_type), "पेटिका<सङ्ख्या>");
+        let Some(Expr::Call(callee, _, _)) = first.body.expr.as_deref() else { unreachable!() };
+        let Expr::Variable(constructor, _) = &**callee else { unreachable!() };
+        assert_eq!(path_name(constructor), "पेटिका<सङ्ख्या>");
+
+        // Uses name the copies
+        let Item::Mantra(f) = &program.items[4] else { unreachable!() };
+        let Statement::Sutra(SutraDef { type_annotation: Some(ty), value: Expr::Call(callee, _, _), .. }) =
+            &f.body.stmts[0]
+        else {
+            unreachable!()
+        };
+        assert_eq!(type_name(ty), "पेटिका<सङ्ख्या>");
+        let Expr::Variable(callee, _) = &**callee else { unreachable!() };
+        assert_eq!(path_name(callee), "प्रथम<सङ्ख्या>");
+    }
+}
@@ -0,0 +1,597 @@
+//! Name resolution for Vāktra (वाक्त्र)
+//!
+//! Gives every declaration a [`SymbolId`] and works out which declaration
+//! each name in the program refers to, so later passes look names up by id
+//! instead of by string.
+//!
+//! Scopes nest lexically. The items of a यन्त्र and the names it brings in
+//! with आनय are visible throughout it and in the yantras inside it.
+//! From outside, a path or आनय reaches only the items marked सार्वजनिक;
+//! the rest stay private to their यन्त्र and the yantras inside it.
+//! Top-level statements run in order, so a top-level सूत्र is visible to the
+//! statements after it and to every mantra body. Inside a block a name is
+//! visible from its declaration on; it may shadow a name of an enclosing
+//! scope but not one of its own. Mantras and dharmas declared inside a
+//! block do not see the block's local variables.
+//!
+//! Type names are resolved like any other path. The builtin types, such as
+//! सङ्ख्या and सूची, are symbols of the prelude, as are the builtin bounds
+//! तुलनीय and सांख्यिक. The type parameters of a dharma or mantra are
+//! visible in its whole definition. A गुणधर्म is both a type and a bound; a
+//! कार्यान्वय declares nothing itself but names a dharma and a गुणधर्म.
+
+use std::collections::HashMap;
+use vaaktra_parser::ast::{
+    Block, DharmaDef, Item, KaryanvayaDef, MantraDef, Param, Path, Program, RcStr, Span, Statement, Type, TypeParam,
+    Visibility, YantraDef,
+};
+
+use crate::error::{DetailedSemanticError, SemanticErrorType};
+use crate::visit::{walk_dharma, walk_karyanvaya, walk_mantra, walk_statement, walk_type, Visit};
+
+/// Identifies one declaration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SymbolId(pub u32);
+
+/// What a symbol was declared as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// Provided by the runtime, such as प्रिंट
+    Builtin,
+    /// A type of the language, such as सङ्ख्या
+    BuiltinType,
+    /// A bound a type parameter may require, such as तुलनीय
+    BuiltinBound,
+    Dharma,
+    Mantra,
+    Yantra,
+    Gunadharma,
+    /// Bound by a सूत्र or a pattern
+    Variable { mutable: bool },
+    /// A mantra or lambda parameter, including आत्मन्
+    Param,
+    /// A type parameter of a dharma or mantra
+    TypeParam,
+}
+
+/// A declared name
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub name: RcStr,
+    pub kind: SymbolKind,
+    /// `None` for builtins
+    pub span: Option<Span>,
+}
+
+/// Symbols of a program and what each name refers to
+#[derive(Debug, Default)]
+pub struct Resolution {
+    symbols: Vec<Symbol>,
+    /// Symbol each resolved path refers to, by the span of the path
+    uses: HashMap<Span, SymbolId>,
+    /// Symbol each item, parameter or binding declares, by its span
+    declarations: HashMap<Span, SymbolId>,
+}
+
+impl Resolution {
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id.0 as usize]
+    }
+
+    /// All symbols in order of declaration
+    pub fn symbols(&self) -> impl Iterator<Item = (SymbolId, &Symbol)> {
+        self.symbols.iter().enumerate().map(|(i, symbol)| (SymbolId(i as u32), symbol))
+    }
+
+    /// The symbol a path in an expression, pattern or आनय refers to
+    pub fn use_of(&self, path: &Path) -> Option<SymbolId> {
+        self.uses.get(&path.span).copied()
+    }
+
+    /// The symbol declared by the item, parameter or pattern binding whose
+    /// span is `span`
+    pub fn declared_at(&self, span: Span) -> Option<SymbolId> {
+        self.declarations.get(&span).copied()
+    }
+}
+
+/// Names of the builtin types
+pub const BUILTIN_TYPES: [&str; 8] = ["सङ्ख्या", "दशांश", "सत्यासत्य", "शब्द", "सूची", "निधान", "शून्य", "ब्रह्मन्"];
+
+/// Names of the builtin bounds: types that can be ordered, and numbers
+pub const BUILTIN_BOUNDS: [&str; 2] = ["तुलनीय", "सांख्यिक"];
+
+/// Resolve every name in `program`. Names that cannot be resolved are
+/// reported and left out of the [`Resolution`].
+pub fn resolve(program: &Program) -> (Resolution, Vec<DetailedSemanticError>) {
+    let mut resolver = Resolver::default();
+    let print = resolver.add_symbol(RcStr::from("प्रिंट"), SymbolKind::Builtin, None);
+    resolver.prelude.insert(RcStr::from("प्रिंट"), print);
+    for name in BUILTIN_TYPES {
+        let id = resolver.add_symbol(RcStr::from(name), SymbolKind::BuiltinType, None);
+        resolver.prelude.insert(RcStr::from(name), id);
+    }
+    for name in BUILTIN_BOUNDS {
+        let id = resolver.add_symbol(RcStr::from(name), SymbolKind::BuiltinBound, None);
+        resolver.prelude.insert(RcStr::from(name), id);
+    }
+
+    resolver.modules.push(HashMap::new());
+    resolver.scopes.push(Scope::Module(0));
+    resolver.resolve_items(&program.items);
+    (resolver.resolution, resolver.errors)
+}
+
+enum Scope {
+    /// Names declared in a block, a parameter list or a pattern
+    Local(HashMap<RcStr, SymbolId>),
+    /// Index of a yantra in [`Resolver::modules`]
+    Module(usize),
+}
+
+#[derive(Default)]
+struct Resolver {
+    resolution: Resolution,
+    errors: Vec<DetailedSemanticError>,
+
+    /// Names every scope can see
+    prelude: HashMap<RcStr, SymbolId>,
+
+    /// Open scopes, innermost last
+    scopes: Vec<Scope>,
+
+    /// Local scopes before this index belong to the block around the item
+    /// being visited and are not visible
+    visible_from: usize,
+
+    /// Names declared in each yantra, the program itself first
+    modules: Vec<HashMap<RcStr, SymbolId>>,
+
+    /// Index in `modules` of each yantra symbol
+    module_of: HashMap<SymbolId, usize>,
+
+    /// Index in `modules` of the yantra each private item belongs to
+    private: HashMap<SymbolId, usize>,
+}
+
+impl Resolver {
+    fn add_symbol(&mut self, name: RcStr, kind: SymbolKind, span: Option<Span>) -> SymbolId {
+        let id = SymbolId(self.resolution.symbols.len() as u32);
+        self.resolution.symbols.push(Symbol { name, kind, span });
+        id
+    }
+
+    fn innermost(&mut self) -> &mut HashMap<RcStr, SymbolId> {
+        match self.scopes.last_mut().expect("no open scope") {
+            Scope::Local(names) => names,
+            Scope::Module(module) => &mut self.modules[*module],
+        }
+    }
+
+    /// Declare a new symbol in the innermost scope
+    fn declare(&mut self, name: &RcStr, kind: SymbolKind, span: Span) -> SymbolId {
+        let id = self.add_symbol(name.clone(), kind, Some(span));
+        self.resolution.declarations.insert(span, id);
+        self.bind(name, id, span);
+        id
+    }
+
+    /// Make `name` refer to `id` in the innermost scope, unless the scope
+    /// already has something else by that name
+    fn bind(&mut self, name: &RcStr, id: SymbolId, span: Span) {
+        match self.innermost().get(name).copied() {
+            None => {
+                self.innermost().insert(name.clone(), id);
+            }
+            Some(existing) if existing == id => {}
+            Some(existing) => {
+                let mut error = DetailedSemanticError::new(
+                    SemanticErrorType::DuplicateSymbol,
+                    format!("`{}` is already declared in this scope", name),
+                )
+                .with_span(span);
+                if let Some(first) = self.resolution.symbol(existing).span {
+                    error = error.with_related_error(
+                        DetailedSemanticError::new(
+                            SemanticErrorType::DuplicateSymbol,
+                            format!("`{}` is first declared here", name),
+                        )
+                        .with_span(first),
+                    );
+                }
+                self.errors.push(error);
+            }
+        }
+    }
+
+    fn lookup(&self, name: &RcStr) -> Option<SymbolId> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, scope)| match scope {
+                Scope::Local(names) if depth >= self.visible_from => names.get(name).copied(),
+                Scope::Local(_) => None,
+                Scope::Module(module) => self.modules[*module].get(name).copied(),
+            })
+            .or_else(|| self.prelude.get(name).copied())
+    }
+
+    /// Resolve a path and record the symbol it refers to. The first segment
+    /// is looked up in the open scopes, each further one among the names
+    /// of the yantra before it.
+    fn resolve_path(&mut self, path: &Path) -> Option<SymbolId> {
+        let (first, rest) = path.segments.split_first()?;
+        let Some(mut id) = self.lookup(&first.ident) else {
+            self.not_found(format!("`{}` is not declared", first.ident), path.span);
+            return None;
+        };
+        for segment in rest {
+            let outer = self.resolution.symbol(id).name.clone();
+            let Some(&module) = self.module_of.get(&id) else {
+                self.not_found(format!("`{}` is not a यन्त्र, so it has no `{}`", outer, segment.ident), path.span);
+                return None;
+            };
+            let Some(&member) = self.modules[module].get(&segment.ident) else {
+                self.not_found(format!("`{}` is not declared in यन्त्र `{}`", segment.ident, outer), path.span);
+                return None;
+            };
+            if !self.can_see(member) {
+                self.not_found(format!("`{}` is private to यन्त्र `{}`", segment.ident, outer), path.span);
+                return None;
+            }
+            id = member;
+        }
+        self.resolution.uses.insert(path.span, id);
+        Some(id)
+    }
+
+    /// Whether `id` is public or belongs to a yantra that is open
+    fn can_see(&self, id: SymbolId) -> bool {
+        self.private.get(&id).is_none_or(|&home| {
+            self.scopes.iter().any(|scope| matches!(scope, Scope::Module(module) if *module == home))
+        })
+    }
+
+    fn not_found(&mut self, message: String, span: Span) {
+        self.errors
+            .push(DetailedSemanticError::new(SemanticErrorType::SymbolNotFound, message).with_span(span));
+    }
+
+    /// Resolve the items of a yantra or the program. Item names come first
+    /// so that items may refer to each other in any order, then imports and
+    /// top-level statements in order, then the bodies of mantras and
+    /// dharmas, which see everything declared before.
+    fn resolve_items(&mut self, items: &[Item]) {
+        self.declare_items(items);
+        self.declare_globals(items);
+        self.visit_bodies(items);
+    }
+
+    /// Visit the items of a yantra inside its own scope
+    fn in_yantra(&mut self, yantra: &YantraDef, visit: impl FnOnce(&mut Self, &[Item])) {
+        let id = self.resolution.declared_at(yantra.span);
+        if let Some(&module) = id.and_then(|id| self.module_of.get(&id)) {
+            self.scopes.push(Scope::Module(module));
+            visit(self, &yantra.items);
+            self.scopes.pop();
+        }
+    }
+
+    /// Declare an item, remembering which yantra it is private to. Items
+    /// of a block cannot be named from outside it anyway.
+    fn declare_item(&mut self, name: &RcStr, kind: SymbolKind, span: Span, visibility: &Visibility) {
+        let id = self.declare(name, kind, span);
+        if let (Visibility::Private, Some(Scope::Module(module))) = (visibility, self.scopes.last()) {
+            self.private.insert(id, *module);
+        }
+    }
+
+    fn declare_items<'a>(&mut self, items: impl IntoIterator<Item = &'a Item>) {
+        for item in items {
+            match item {
+                Item::Dharma(dharma) => {
+                    self.declare_item(&dharma.name, SymbolKind::Dharma, dharma.span, &dharma.visibility);
+                }
+                Item::Mantra(mantra) => {
+                    self.declare_item(&mantra.name, SymbolKind::Mantra, mantra.span, &mantra.visibility);
+                }
+                Item::Yantra(yantra) => {
+                    let id = self.declare(&yantra.name, SymbolKind::Yantra, yantra.span);
+                    self.module_of.insert(id, self.modules.len());
+                    self.modules.push(HashMap::new());
+                    self.in_yantra(yantra, |resolver, items| resolver.declare_items(items));
+                }
+                Item::Gunadharma(gunadharma) => {
+                    let visibility = &gunadharma.visibility;
+                    self.declare_item(&gunadharma.name, SymbolKind::Gunadharma, gunadharma.span, visibility);
+                }
+                Item::Sutra(_) | Item::Aanaya(_) | Item::Karyanvaya(_) | Item::Praarabdha(_) => {}
+            }
+        }
+    }
+
+    fn declare_globals(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Yantra(yantra) => self.in_yantra(yantra, Self::declare_globals),
+                Item::Aanaya(import) => {
+                    if let (Some(id), Some(last)) = (self.resolve_path(&import.path), import.path.segments.last()) {
+                        self.bind(&last.ident, id, import.span);
+                    }
+                }
+                Item::Sutra(sutra) => self.visit_sutra(sutra),
+                Item::Praarabdha(stmts) => stmts.iter().for_each(|stmt| self.visit_statement(stmt)),
+                Item::Dharma(_) | Item::Mantra(_) | Item::Gunadharma(_) | Item::Karyanvaya(_) => {}
+            }
+        }
+    }
+
+    fn visit_bodies(&mut self, items: &[Item]) {
+        let visible_from = std::mem::replace(&mut self.visible_from, self.scopes.len());
+        for item in items {
+            match item {
+                Item::Dharma(dharma) => self.visit_dharma(dharma),
+                Item::Mantra(mantra) => self.visit_mantra(mantra),
+                Item::Yantra(yantra) => self.in_yantra(yantra, Self::visit_bodies),
+                Item::Gunadharma(gunadharma) => self.visit_gunadharma(gunadharma),
+                Item::Karyanvaya(karyanvaya) => self.visit_karyanvaya(karyanvaya),
+                Item::Sutra(_) | Item::Aanaya(_) | Item::Praarabdha(_) => {}
+            }
+        }
+        self.visible_from = visible_from;
+    }
+
+    /// Resolve `path`, reporting it if it names something other than a
+    /// `kind`, which is called `what` in source
+    fn expect_kind(&mut self, path: &Path, kind: SymbolKind, what: &str) {
+        let found = self.resolve_path(path).map(|id| self.resolution.symbol(id).kind);
+        if found.is_some_and(|found| found != kind) {
+            let name = path.segments.last().map_or("", |segment| segment.ident.as_str());
+            self.not_found(format!("`{}` is not a {}", name, what), path.span);
+        }
+    }
+
+    /// Visit `body` in a scope holding the type parameters `params`
+    fn visit_type_params(&mut self, params: &[TypeParam], body: impl FnOnce(&mut Self)) {
+        if params.is_empty() {
+            return body(self);
+        }
+        self.push_scope();
+        for param in params {
+            self.declare(&param.name, SymbolKind::TypeParam, param.span);
+        }
+        for param in params {
+            for bound in &param.bounds {
+                let kind = self.resolve_path(&bound.bound).map(|id| self.resolution.symbol(id).kind);
+                let bounds = [SymbolKind::BuiltinBound, SymbolKind::Dharma, SymbolKind::Gunadharma];
+                if kind.is_some_and(|kind| !bounds.contains(&kind)) {
+                    let name = bound.bound.segments.last().map_or("", |segment| segment.ident.as_str());
+                    self.not_found(format!("`{}` is not a bound", name), bound.span);
+                }
+            }
+            if let Some(default) = &param.default {
+                self.visit_type(default);
+            }
+        }
+        body(self);
+        self.pop_scope();
+    }
+}
+
+impl Visit for Resolver {
+    fn visit_dharma(&mut self, dharma: &DharmaDef) {
+        self.visit_type_params(&dharma.type_params, |resolver| walk_dharma(resolver, dharma));
+    }
+
+    fn visit_mantra(&mut self, mantra: &MantraDef) {
+        self.visit_type_params(&mantra.type_params, |resolver| walk_mantra(resolver, mantra));
+    }
+
+    fn visit_karyanvaya(&mut self, karyanvaya: &KaryanvayaDef) {
+        self.expect_kind(&karyanvaya.dharma, SymbolKind::Dharma, "धर्म");
+        self.expect_kind(&karyanvaya.gunadharma, SymbolKind::Gunadharma, "गुणधर्म");
+        walk_karyanvaya(self, karyanvaya);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.push_scope();
+        // Items of a block are visible throughout it, like those of a yantra
+        self.declare_items(block.stmts.iter().filter_map(|stmt| match stmt {
+            Statement::Item(item) => Some(item),
+            _ => None,
+        }));
+        self.visit_statements(&block.stmts);
+        if let Some(expr) = &block.expr {
+            self.visit_expr(expr);
+        }
+        self.pop_scope();
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Item(item) => {
+                let item = std::slice::from_ref(item);
+                self.declare_globals(item);
+                self.visit_bodies(item);
+            }
+            stmt => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        if let Type::Named(path, _) = ty {
+            let id = self.resolve_path(path);
+            let kind = id.map(|id| self.resolution.symbol(id).kind);
+            let types = [SymbolKind::Dharma, SymbolKind::Gunadharma, SymbolKind::BuiltinType, SymbolKind::TypeParam];
+            if kind.is_some_and(|kind| !types.contains(&kind)) {
+                let name = path.segments.last().map_or("", |segment| segment.ident.as_str());
+                self.not_found(format!("`{}` is not a type", name), path.span);
+            }
+        }
+        walk_type(self, ty);
+    }
+
+    fn visit_path(&mut self, path: &Path) {
+        self.resolve_path(path);
+    }
+
+    fn visit_binding(&mut self, name: &RcStr, mutable: bool, span: Span) {
+        self.declare(name, SymbolKind::Variable { mutable }, span);
+    }
+
+    fn visit_param(&mut self, param: &Param) {
+        self.declare(&param.name, SymbolKind::Param, param.span);
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(Scope::Local(HashMap::new()));
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vaaktra_lexer::Lexer;
+    use vaaktra_parser::Parser;
+
+    fn resolve_source(input: &str) -> (Resolution, Vec<DetailedSemanticError>) {
+        let program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        resolve(&program)
+    }
+
+    /// The symbol used or declared by the `nth` occurrence of `name`
+    fn symbol_at(resolution: &Resolution, input: &str, name: &str, nth: usize) -> Option<SymbolId> {
+        let (offset, _) = input.match_indices(name).nth(nth).unwrap();
+        let mut spans = resolution.uses.iter().chain(&resolution.declarations);
+        spans.find(|(span, _)| span.start == offset).map(|(_, id)| *id)
+    }
+
+    /// Kind and source text of each error, in source order
+    fn errors_of(input: &str) -> Vec<(SemanticErrorType, &str)> {
+        let (_, mut errors) = resolve_source(input);
+        errors.sort_by_key(|e| e.span.unwrap().start);
+        errors.into_iter().map(|e| (e.error_type, &input[e.span.unwrap().range()])).collect()
+    }
+
+    #[test]
+    fn test_uses_resolve_to_their_declarations() {
+        let input = "मन्त्र फ(अ: सङ्ख्या) -> सङ्ख्या {\n    सूत्र ब = अ;\n    { सूत्र अ = ब; प्रिंट(अ); }\n    फ(अ)\n}";
+        let (resolution, errors) = resolve_source(input);
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let at = |name, nth| symbol_at(&resolution, input, name, nth).unwrap();
+        // The parameter, then the shadowing सूत्र in the inner block
+        assert_eq!(at("अ", 1), at("अ", 0));
+        assert_ne!(at("अ", 2), at("अ", 0));
+        assert_eq!(at("अ", 3), at("अ", 2));
+        assert_eq!(at("अ", 4), at("अ", 0));
+        assert_eq!(at("ब", 1), at("ब", 0));
+        assert_eq!(resolution.symbol(at("फ", 1)).kind, SymbolKind::Mantra);
+        assert_eq!(resolution.symbol(at("प्रिंट", 0)).kind, SymbolKind::Builtin);
+    }
+
+    #[test]
+    fn test_unknown_and_duplicate_names_are_reported() {
+        let input = "मन्त्र फ(अ: सङ्ख्या, अ: सङ्ख्या) {\n    सूत्र ब = क;\n    सूत्र ब = १;\n}\nधर्म फ {}";
+        assert_eq!(
+            errors_of(input),
+            [
+                (SemanticErrorType::DuplicateSymbol, "अ: सङ्ख्या"),
+                (SemanticErrorType::SymbolNotFound, "क"),
+                (SemanticErrorType::DuplicateSymbol, "ब"),
+                (SemanticErrorType::DuplicateSymbol, "धर्म फ {}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paths_through_yantras_and_imports() {
+        let input = "यन्त्र गणित {\n    यन्त्र सदिश { सार्वजनिक मन्त्र योग() {} }\n    सार्वजनिक मन्त्र द्वि() { सदिश::योग() }\n}\n\
+                     आनय गणित::सदिश::योग;\nमन्त्र मुख्य() { योग(); गणित::द्वि(); गणित::त्रि(); योग::अ(); }";
+        let (resolution, errors) = resolve_source(input);
+        let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["`त्रि` is not declared in यन्त्र `गणित`", "`योग` is not a यन्त्र, so it has no `अ`"]
+        );
+
+        let path_use = |prefix: &str| {
+            let offset = input.find(prefix).unwrap();
+            let mut uses = resolution.uses.iter();
+            uses.find(|(span, _)| span.start == offset).map(|(_, id)| *id).unwrap()
+        };
+        let yoga = path_use("गणित::सदिश::योग;");
+        assert_eq!(resolution.symbol(yoga).kind, SymbolKind::Mantra);
+        assert_eq!(resolution.symbol(yoga).span.map(|s| &input[s.range()]), Some("सार्वजनिक मन्त्र योग() {}"));
+        assert_eq!(path_use("सदिश::योग()"), yoga);
+        assert_eq!(path_use("योग();"), yoga);
+        assert_eq!(resolution.symbol(path_use("गणित::द्वि")).name.as_str(), "द्वि");
+    }
+
+    #[test]
+    fn test_private_items_stay_inside_their_yantra() {
+        let input = "यन्त्र गणित {\n    सार्वजनिक मन्त्र योग() {}\n    धर्म सहायक {}\n\
+                     यन्त्र अन्तः { मन्त्र फ(अ: गणित::सहायक) { योग(); } }\n}\n\
+                     आनय गणित::सहायक;\nमन्त्र मुख्य() { गणित::योग(); गणित::अन्तः::फ(); }";
+        let (_, errors) = resolve_source(input);
+        let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["`सहायक` is private to यन्त्र `गणित`", "`फ` is private to यन्त्र `अन्तः`"]
+        );
+    }
+
+    #[test]
+    fn test_top_level_statements_and_nested_items() {
+        let input = "प्रिंट(क);\nसूत्र क = १;\nमन्त्र फ() { क }\n\
+                     मन्त्र ग() { सूत्र स = ह(); मन्त्र ह() -> सङ्ख्या { स } }";
+        assert_eq!(
+            errors_of(input),
+            [(SemanticErrorType::SymbolNotFound, "क"), (SemanticErrorType::SymbolNotFound, "स")]
+        );
+    }
+
+    #[test]
+    fn test_type_parameters_and_bounds() {
+        let input = "धर्म पेटिका<त> { सूत्र मूल्य: त; मन्त्र ले(आत्मन्) -> त { आत्मन्.मूल्य } }\n\
+                     मन्त्र बृहत्<त: तुलनीय>(अ: त, ब: त) -> त { अ }\n\
+                     मन्त्र फ<क: बृहत्>(अ: त) {}";
+        let (resolution, _) = resolve_source(input);
+        let uses: Vec<_> = resolution
+            .uses
+            .iter()
+            .filter(|(span, _)| &input[span.range()] == "त")
+            .map(|(_, &id)| resolution.symbol(id).kind)
+            .collect();
+        assert_eq!(uses.len(), 5);
+        assert!(uses.iter().all(|&kind| kind == SymbolKind::TypeParam));
+        assert_eq!(
+            errors_of(input),
+            [(SemanticErrorType::SymbolNotFound, "बृहत्"), (SemanticErrorType::SymbolNotFound, "त")]
+        );
+    }
+
+    #[test]
+    fn test_gunadharma_and_karyanvaya() {
+        let input = "गुणधर्म आकार { मन्त्र क्षेत्रफल(आत्मन्) -> दशांश; }\n\
+                     कार्यान्वय वृत्त: आकार { मन्त्र क्षेत्रफल(आत्मन्) -> दशांश { आत्मन्.त्रिज्या } }\n\
+                     धर्म वृत्त { सूत्र त्रिज्या: दशांश; }\n\
+                     कार्यान्वय आकार: वृत्त {}\n\
+                     मन्त्र फ<त: आकार>(अ: त, ब: सूची<आकार>) {}";
+        let (resolution, _) = resolve_source(input);
+        let gunadharma = symbol_at(&resolution, input, "आकार", 1).unwrap();
+        assert_eq!(resolution.symbol(gunadharma).kind, SymbolKind::Gunadharma);
+        for nth in 2..5 {
+            assert_eq!(symbol_at(&resolution, input, "आकार", nth), Some(gunadharma), "{}", nth);
+        }
+        assert_eq!(
+            errors_of(input),
+            [(SemanticErrorType::SymbolNotFound, "आकार"), (SemanticErrorType::SymbolNotFound, "वृत्त")]
+        );
+    }
+}
@@ -4,7 +4,7 @@
 //! of knowledge hierarchy (ज्ञान पदानुक्रम).
 
 use std::collections::HashMap;
-use vaaktra_parser::ast::{Type, Param, FieldDef};
+use vaaktra_parser::ast::{Type, Param, FieldDef, Path, PathSegment, Span};
 use crate::{SemanticError, SemanticResult};
 
 /// Symbol information
@@ -44,6 +44,12 @@ pub enum SymbolType {
     TypeAlias(Type),
 }
 
+/// The builtin type `name`, which has no place in the source
+fn builtin_type(name: &str) -> Type {
+    let segment = PathSegment { ident: name.into(), args: None };
+    Type::Named(Path { segments: vec![segment], span: Span::dummy() }, Vec::new())
+}
+
 /// Symbol table with hierarchical scoping
 pub struct SymbolTable {
     /// Scopes stack (outermost to innermost)
@@ -85,20 +91,14 @@ impl SymbolTable {
         self.declare_builtin_type("शून्य", "Void type");
         
         // Built-in functions
-        self.declare_builtin_function("प्रिंट", "Print function", vec![], Type::Named {
-            name: "शून्य".into(),
-            generics: None,
-        });
+        self.declare_builtin_function("प्रिंट", "Print function", vec![], builtin_type("शून्य"));
     }
     
     /// Declare a built-in type
     fn declare_builtin_type(&mut self, name: &str, _description: &str) {
         let symbol = Symbol {
             name: name.to_string(),
-            symbol_type: SymbolType::TypeAlias(Type::Named {
-                name: name.into(),
-                generics: None,
-            }),
+            symbol_type: SymbolType::TypeAlias(builtin_type(name)),
             scope_level: 0,
             is_mutable: false,
             location: None,
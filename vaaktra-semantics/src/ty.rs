@@ -0,0 +1,211 @@
+//! Semantic types of Vāktra (वाक्त्र)
+//!
+//! The types the checker works with, as opposed to the [`Type`] syntax they
+//! are written in. Fixed-size arrays and slices are lists, and references
+//! are checked as the type they refer to.
+
+use std::collections::HashMap;
+use std::fmt;
+use vaaktra_parser::ast::{IntegerWidth, RcStr, Type};
+
+use crate::resolve::{Resolution, SymbolId, SymbolKind};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ty {
+    /// सङ्ख्या
+    Int,
+    /// An integer of a fixed width, from a suffixed literal
+    Sized(IntegerWidth),
+    /// दशांश
+    Float,
+    /// सत्यासत्य
+    Bool,
+    /// शब्द
+    String,
+    /// शून्य
+    Unit,
+    /// ब्रह्मन्, which holds a value of any type
+    Object,
+    /// सूची<T>
+    List(Box<Ty>),
+    /// निधान<K, V>
+    Map(Box<Ty>, Box<Ty>),
+    Tuple(Vec<Ty>),
+    Function(Vec<Ty>, Box<Ty>),
+    /// A धर्म, with its name for messages and its type arguments
+    Dharma(SymbolId, RcStr, Vec<Ty>),
+    /// A value of any dharma with a कार्यान्वय of the गुणधर्म. Its methods
+    /// are dispatched through a vtable at run time.
+    Gunadharma(SymbolId, RcStr),
+    /// A type parameter, inside the dharma or mantra declaring it
+    Param(SymbolId, RcStr),
+    /// The type of expressions that never produce a value, such as
+    /// प्रत्याहर
+    Never,
+    /// A type that is not known, because of an earlier error or because
+    /// the runtime provides the value. It is compatible with every type.
+    Unknown,
+    /// A type still to be inferred; see [`crate::infer`]
+    Var(u32),
+}
+
+impl Ty {
+    /// Lower a written type. Names that do not resolve to a type become
+    /// [`Ty::Unknown`]; the resolver has already reported them.
+    pub fn lower(ty: &Type, resolution: &Resolution) -> Ty {
+        Ty::lower_with(ty, resolution, &mut |_| Ty::Unknown)
+    }
+
+    /// Lower a written type, asking `hole` for the type of each `_`
+    pub fn lower_with(ty: &Type, resolution: &Resolution, hole: &mut impl FnMut(&Type) -> Ty) -> Ty {
+        match ty {
+            Type::Named(path, args) => {
+                let Some(id) = resolution.use_of(path) else {
+                    return Ty::Unknown;
+                };
+                let symbol = resolution.symbol(id);
+                let mut args = args.iter().map(|arg| Ty::lower_with(arg, resolution, hole));
+                let mut arg = || Box::new(args.next().unwrap_or(Ty::Unknown));
+                match (symbol.kind, symbol.name.as_str()) {
+                    (SymbolKind::Dharma, _) => Ty::Dharma(id, symbol.name.clone(), args.collect()),
+                    (SymbolKind::Gunadharma, _) => Ty::Gunadharma(id, symbol.name.clone()),
+                    (SymbolKind::TypeParam, _) => Ty::Param(id, symbol.name.clone()),
+                    (SymbolKind::BuiltinType, "सङ्ख्या") => Ty::Int,
+                    (SymbolKind::BuiltinType, "दशांश") => Ty::Float,
+                    (SymbolKind::BuiltinType, "सत्यासत्य") => Ty::Bool,
+                    (SymbolKind::BuiltinType, "शब्द") => Ty::String,
+                    (SymbolKind::BuiltinType, "शून्य") => Ty::Unit,
+                    (SymbolKind::BuiltinType, "सूची") => Ty::List(arg()),
+                    (SymbolKind::BuiltinType, "निधान") => Ty::Map(arg(), arg()),
+                    (SymbolKind::BuiltinType, _) => Ty::Object,
+                    _ => Ty::Unknown,
+                }
+            }
+            Type::Tuple(elems, _) => Ty::Tuple(elems.iter().map(|t| Ty::lower_with(t, resolution, hole)).collect()),
+            Type::Function(params, ret, _) => Ty::Function(
+                params.iter().map(|t| Ty::lower_with(t, resolution, hole)).collect(),
+                Box::new(Ty::lower_with(ret, resolution, hole)),
+            ),
+            Type::Reference(inner, _, _) => Ty::lower_with(inner, resolution, hole),
+            Type::Array(elem, _, _) | Type::Slice(elem, _) => {
+                Ty::List(Box::new(Ty::lower_with(elem, resolution, hole)))
+            }
+            Type::Never(_) => Ty::Never,
+            Type::Infer(_) => hole(ty),
+            Type::Error => Ty::Unknown,
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Ty::Int | Ty::Sized(_) | Ty::Float)
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Ty::Int | Ty::Sized(_))
+    }
+
+    /// Whether ordering comparisons apply
+    pub fn is_comparable(&self) -> bool {
+        self.is_numeric() || matches!(self, Ty::Bool | Ty::String)
+    }
+
+    /// Whether a type variable appears in the type
+    pub fn has_var(&self) -> bool {
+        matches!(self, Ty::Var(_)) || self.children().into_iter().any(Ty::has_var)
+    }
+
+    /// The types directly inside this one
+    pub fn children(&self) -> Vec<&Ty> {
+        match self {
+            Ty::List(elem) => vec![elem],
+            Ty::Map(key, value) => vec![key, value],
+            Ty::Tuple(elems) | Ty::Dharma(_, _, elems) => elems.iter().collect(),
+            Ty::Function(params, ret) => params.iter().chain([&**ret]).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// This type with `f` applied to each type directly inside it
+    pub fn map(&self, mut f: impl FnMut(&Ty) -> Ty) -> Ty {
+        match self {
+            Ty::List(elem) => Ty::List(Box::new(f(elem))),
+            Ty::Map(key, value) => Ty::Map(Box::new(f(key)), Box::new(f(value))),
+            Ty::Tuple(elems) => Ty::Tuple(elems.iter().map(f).collect()),
+            Ty::Function(params, ret) => Ty::Function(params.iter().map(&mut f).collect(), Box::new(f(ret))),
+            Ty::Dharma(id, name, args) => Ty::Dharma(*id, name.clone(), args.iter().map(f).collect()),
+            ty => ty.clone(),
+        }
+    }
+
+    /// Replace type parameters by the types `args` gives them
+    pub fn substitute(&self, args: &HashMap<SymbolId, Ty>) -> Ty {
+        match self {
+            Ty::Param(id, _) => args.get(id).cloned().unwrap_or_else(|| self.clone()),
+            ty => ty.map(|ty| ty.substitute(args)),
+        }
+    }
+}
+
+/// What a bound on a type parameter requires of its argument
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstraintKind {
+    /// Must implement trait
+    Implements,
+    
+    /// Must be subtype of
+    SubtypeOf,
+    
+    /// Must be same as
+    Equals,
+    
+    /// Must be numeric
+    Numeric,
+    
+    /// Must be comparable
+    Comparable,
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |f: &mut fmt::Formatter<'_>, tys: &[Ty]| {
+            for (i, ty) in tys.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", ty)?;
+            }
+            Ok(())
+        };
+        match self {
+            Ty::Int => f.write_str("सङ्ख्या"),
+            Ty::Sized(width) => write!(f, "{}", width),
+            Ty::Float => f.write_str("दशांश"),
+            Ty::Bool => f.write_str("सत्यासत्य"),
+            Ty::String => f.write_str("शब्द"),
+            Ty::Unit => f.write_str("शून्य"),
+            Ty::Object => f.write_str("ब्रह्मन्"),
+            Ty::List(elem) => write!(f, "सूची<{}>", elem),
+            Ty::Map(key, value) => write!(f, "निधान<{}, {}>", key, value),
+            Ty::Tuple(elems) if elems.len() == 1 => write!(f, "({},)", elems[0]),
+            Ty::Tuple(elems) => {
+                f.write_str("(")?;
+                list(f, elems)?;
+                f.write_str(")")
+            }
+            Ty::Function(params, ret) => {
+                f.write_str("(")?;
+                list(f, params)?;
+                write!(f, ") -> {}", ret)
+            }
+            Ty::Dharma(_, name, args) if args.is_empty() => write!(f, "{}", name),
+            Ty::Dharma(_, name, args) => {
+                write!(f, "{}<", name)?;
+                list(f, args)?;
+                f.write_str(">")
+            }
+            Ty::Gunadharma(_, name) | Ty::Param(_, name) => write!(f, "{}", name),
+            Ty::Never => f.write_str("!"),
+            Ty::Unknown | Ty::Var(_) => f.write_str("_"),
+        }
+    }
+}
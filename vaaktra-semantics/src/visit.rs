@@ -0,0 +1,315 @@
+//! Read-only traversal of a Vāktra (वाक्त्र) program
+//!
+//! The immutable twin of [`crate::walk`], for passes that only look at a
+//! program, such as name resolution, lints and capture analysis. Nodes are
+//! visited in evaluation order, so a pattern comes after the value it
+//! binds, and the traversal reports where scopes open and close and what
+//! each one binds. A pass overrides those hooks to track names and the
+//! `visit_*` methods for the nodes it cares about; an override can still
+//! descend by calling the matching `walk_*` function.
+
+use vaaktra_parser::ast::{
+    Block, DharmaDef, Expr, GunadharmaDef, Guard, Item, KaryanvayaDef, MantraDef, Param, Path, Pattern, Program,
+    RcStr, Span, Statement, SutraDef, Type,
+};
+
+pub trait Visit {
+    /// The items of the program, of a yantra or of a block
+    fn visit_items(&mut self, items: &[Item]) {
+        items.iter().for_each(|item| self.visit_item(item));
+    }
+
+    fn visit_item(&mut self, item: &Item) {
+        walk_item(self, item);
+    }
+
+    fn visit_dharma(&mut self, dharma: &DharmaDef) {
+        walk_dharma(self, dharma);
+    }
+
+    fn visit_mantra(&mut self, mantra: &MantraDef) {
+        walk_mantra(self, mantra);
+    }
+
+    fn visit_sutra(&mut self, sutra: &SutraDef) {
+        walk_sutra(self, sutra);
+    }
+
+    fn visit_gunadharma(&mut self, gunadharma: &GunadharmaDef) {
+        walk_gunadharma(self, gunadharma);
+    }
+
+    fn visit_karyanvaya(&mut self, karyanvaya: &KaryanvayaDef) {
+        walk_karyanvaya(self, karyanvaya);
+    }
+
+    /// The statements of a block or of a top-level प्रारब्ध
+    fn visit_statements(&mut self, stmts: &[Statement]) {
+        stmts.iter().for_each(|stmt| self.visit_statement(stmt));
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        walk_statement(self, stmt);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr);
+    }
+
+    /// A pattern binding variables, after the value it matches
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        walk_type(self, ty);
+    }
+
+    /// A path naming a value, or a dharma in a pattern or struct literal.
+    /// Paths in types are part of [`Visit::visit_type`].
+    fn visit_path(&mut self, _path: &Path) {}
+
+    /// A variable bound by a pattern, in the innermost open scope
+    fn visit_binding(&mut self, _name: &RcStr, _mutable: bool, _span: Span) {}
+
+    /// A mantra or lambda parameter, in the scope of its body. Its type
+    /// and default value are visited before that scope opens.
+    fn visit_param(&mut self, _param: &Param) {}
+
+    /// A block, a loop body with its pattern, a match arm, or the
+    /// parameters of a mantra or lambda begin
+    fn push_scope(&mut self) {}
+
+    fn pop_scope(&mut self) {}
+}
+
+pub fn walk_program<V: Visit + ?Sized>(visitor: &mut V, program: &Program) {
+    visitor.visit_items(&program.items);
+}
+
+pub fn walk_item<V: Visit + ?Sized>(visitor: &mut V, item: &Item) {
+    match item {
+        Item::Dharma(dharma) => visitor.visit_dharma(dharma),
+        Item::Mantra(mantra) => visitor.visit_mantra(mantra),
+        Item::Sutra(sutra) => visitor.visit_sutra(sutra),
+        Item::Yantra(yantra) => visitor.visit_items(&yantra.items),
+        Item::Gunadharma(gunadharma) => visitor.visit_gunadharma(gunadharma),
+        Item::Karyanvaya(karyanvaya) => visitor.visit_karyanvaya(karyanvaya),
+        Item::Praarabdha(stmts) => visitor.visit_statements(stmts),
+        Item::Aanaya(_) => {}
+    }
+}
+
+pub fn walk_dharma<V: Visit + ?Sized>(visitor: &mut V, dharma: &DharmaDef) {
+    for field in &dharma.fields {
+        visitor.visit_type(&field.ty);
+        if let Some(default) = &field.default_value {
+            visitor.visit_expr(default);
+        }
+    }
+    dharma.methods.iter().for_each(|method| visitor.visit_mantra(method));
+}
+
+pub fn walk_mantra<V: Visit + ?Sized>(visitor: &mut V, mantra: &MantraDef) {
+    walk_params(visitor, &mantra.params);
+    visitor.visit_type(&mantra.return_type);
+    visitor.push_scope();
+    mantra.params.iter().for_each(|param| visitor.visit_param(param));
+    visitor.visit_block(&mantra.body);
+    visitor.pop_scope();
+}
+
+/// The types and default values of parameters, which are evaluated by the
+/// caller and so cannot see the parameters themselves
+fn walk_params<V: Visit + ?Sized>(visitor: &mut V, params: &[Param]) {
+    for param in params {
+        visitor.visit_type(&param.ty);
+        if let Some(default) = &param.default_value {
+            visitor.visit_expr(default);
+        }
+    }
+}
+
+pub fn walk_sutra<V: Visit + ?Sized>(visitor: &mut V, sutra: &SutraDef) {
+    if let Some(ty) = &sutra.type_annotation {
+        visitor.visit_type(ty);
+    }
+    visitor.visit_expr(&sutra.value);
+    visitor.visit_pattern(&sutra.pattern);
+}
+
+pub fn walk_gunadharma<V: Visit + ?Sized>(visitor: &mut V, gunadharma: &GunadharmaDef) {
+    for method in &gunadharma.methods {
+        method.params.iter().for_each(|param| visitor.visit_type(&param.ty));
+        visitor.visit_type(&method.return_type);
+    }
+}
+
+/// The paths naming the dharma and the गुणधर्म are left to overrides
+pub fn walk_karyanvaya<V: Visit + ?Sized>(visitor: &mut V, karyanvaya: &KaryanvayaDef) {
+    karyanvaya.methods.iter().for_each(|method| visitor.visit_mantra(method));
+}
+
+pub fn walk_block<V: Visit + ?Sized>(visitor: &mut V, block: &Block) {
+    visitor.push_scope();
+    visitor.visit_statements(&block.stmts);
+    if let Some(expr) = &block.expr {
+        visitor.visit_expr(expr);
+    }
+    visitor.pop_scope();
+}
+
+pub fn walk_statement<V: Visit + ?Sized>(visitor: &mut V, stmt: &Statement) {
+    match stmt {
+        Statement::Item(item) => visitor.visit_item(item),
+        Statement::Sutra(sutra) => visitor.visit_sutra(sutra),
+        Statement::Expr(expr) => visitor.visit_expr(expr),
+        Statement::Block(block) => visitor.visit_block(block),
+        Statement::Yadi { condition, then_branch, else_branch } => {
+            visitor.visit_expr(condition);
+            visitor.visit_statement(then_branch);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_statement(else_branch);
+            }
+        }
+        Statement::Yaavat { condition, body, .. } => {
+            visitor.visit_expr(condition);
+            visitor.visit_statement(body);
+        }
+        Statement::Pratyeka { pattern, iterable, body, .. } => {
+            visitor.visit_expr(iterable);
+            visitor.push_scope();
+            visitor.visit_pattern(pattern);
+            visitor.visit_statement(body);
+            visitor.pop_scope();
+        }
+        Statement::Pratyahara(value) | Statement::Nirgama { value, .. } => {
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        Statement::Anuvrtti(_) | Statement::Shunya => {}
+    }
+}
+
+pub fn walk_expr<V: Visit + ?Sized>(visitor: &mut V, expr: &Expr) {
+    match expr {
+        Expr::Variable(path, _) => visitor.visit_path(path),
+        Expr::FieldAccess(object, _, _) | Expr::Unary(_, object, _) | Expr::Await(object, _) => {
+            visitor.visit_expr(object)
+        }
+        Expr::MethodCall(receiver, _, type_args, args, _) => {
+            visitor.visit_expr(receiver);
+            type_args.iter().for_each(|t| visitor.visit_type(t));
+            args.iter().for_each(|a| visitor.visit_expr(a));
+        }
+        Expr::Call(callee, args, _) => {
+            visitor.visit_expr(callee);
+            args.iter().for_each(|a| visitor.visit_expr(a));
+        }
+        Expr::Binary(left, _, right, _) | Expr::Assign(left, right, _) => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::Block(block, _) | Expr::Loop(block, _, _) | Expr::Async(block, _) | Expr::Try(block, _) => {
+            visitor.visit_block(block)
+        }
+        Expr::If(condition, then_block, else_expr, _) => {
+            visitor.visit_expr(condition);
+            visitor.visit_block(then_block);
+            if let Some(else_expr) = else_expr {
+                visitor.visit_expr(else_expr);
+            }
+        }
+        Expr::While(condition, body, _, _) => {
+            visitor.visit_expr(condition);
+            visitor.visit_block(body);
+        }
+        Expr::For(pattern, iterable, body, _, _) => {
+            visitor.visit_expr(iterable);
+            visitor.push_scope();
+            visitor.visit_pattern(pattern);
+            visitor.visit_block(body);
+            visitor.pop_scope();
+        }
+        Expr::Match(scrutinee, arms, _) => {
+            visitor.visit_expr(scrutinee);
+            for arm in arms {
+                visitor.push_scope();
+                visitor.visit_pattern(&arm.pattern);
+                match &arm.guard {
+                    Some(Guard::If(condition)) => visitor.visit_expr(condition),
+                    Some(Guard::IfLet(pattern, value)) => {
+                        visitor.visit_expr(value);
+                        visitor.visit_pattern(pattern);
+                    }
+                    None => {}
+                }
+                visitor.visit_expr(&arm.body);
+                visitor.pop_scope();
+            }
+        }
+        Expr::Return(value, _) | Expr::Break(_, value, _) => {
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        Expr::Lambda(params, body, _) => {
+            walk_params(visitor, params);
+            visitor.push_scope();
+            params.iter().for_each(|param| visitor.visit_param(param));
+            visitor.visit_expr(body);
+            visitor.pop_scope();
+        }
+        Expr::Array(elems, _) | Expr::Tuple(elems, _) => elems.iter().for_each(|e| visitor.visit_expr(e)),
+        Expr::Struct(path, fields, _) => {
+            visitor.visit_path(path);
+            fields.iter().for_each(|f| visitor.visit_expr(&f.value));
+        }
+        Expr::Range(start, end, _, _) => {
+            for bound in [start, end].into_iter().flatten() {
+                visitor.visit_expr(bound);
+            }
+        }
+        Expr::Literal(..) | Expr::Continue(..) | Expr::Error(_) => {}
+    }
+}
+
+pub fn walk_pattern<V: Visit + ?Sized>(visitor: &mut V, pattern: &Pattern) {
+    match pattern {
+        Pattern::Bind { name, mutable, subpattern, span, .. } => {
+            visitor.visit_binding(name, *mutable, *span);
+            if let Some(sub) = subpattern {
+                visitor.visit_pattern(sub);
+            }
+        }
+        Pattern::Tuple(elems, _) => elems.iter().for_each(|p| visitor.visit_pattern(p)),
+        Pattern::Struct { path, fields, .. } => {
+            visitor.visit_path(path);
+            fields.iter().for_each(|f| visitor.visit_pattern(&f.pattern));
+        }
+        Pattern::Any(_) | Pattern::Literal(_) => {}
+    }
+}
+
+pub fn walk_type<V: Visit + ?Sized>(visitor: &mut V, ty: &Type) {
+    match ty {
+        Type::Named(_, args) | Type::Tuple(args, _) => args.iter().for_each(|t| visitor.visit_type(t)),
+        Type::Function(params, ret, _) => {
+            params.iter().for_each(|t| visitor.visit_type(t));
+            visitor.visit_type(ret);
+        }
+        Type::Reference(inner, _, _) | Type::Slice(inner, _) => visitor.visit_type(inner),
+        Type::Array(elem, len, _) => {
+            visitor.visit_type(elem);
+            if let Some(len) = len {
+                visitor.visit_expr(len);
+            }
+        }
+        Type::Never(_) | Type::Infer(_) | Type::Error => {}
+    }
+}
@@ -0,0 +1,273 @@
+//! Mutable traversal of a Vāktra (वाक्त्र) program
+//!
+//! Passes that rewrite a program in place, such as writing back inferred
+//! types or monomorphization, implement [`VisitMut`] and override the
+//! methods for the nodes they change. Each `walk_*` function visits the
+//! children of a node and is what the default method calls, so an
+//! override can still descend by calling it. Passes that only read a
+//! program use the immutable twin, [`crate::visit::Visit`].
+
+use vaaktra_parser::ast::{
+    Block, DharmaDef, Expr, GunadharmaDef, Guard, Item, KaryanvayaDef, MantraDef, Path, Pattern, Program, SutraDef,
+    Statement, Type,
+};
+
+pub trait VisitMut {
+    /// The items of the program, of a yantra or of a block
+    fn visit_items(&mut self, items: &mut Vec<Item>) {
+        items.iter_mut().for_each(|item| self.visit_item(item));
+    }
+
+    fn visit_item(&mut self, item: &mut Item) {
+        walk_item(self, item);
+    }
+
+    fn visit_dharma(&mut self, dharma: &mut DharmaDef) {
+        walk_dharma(self, dharma);
+    }
+
+    fn visit_mantra(&mut self, mantra: &mut MantraDef) {
+        walk_mantra(self, mantra);
+    }
+
+    fn visit_sutra(&mut self, sutra: &mut SutraDef) {
+        walk_sutra(self, sutra);
+    }
+
+    fn visit_gunadharma(&mut self, gunadharma: &mut GunadharmaDef) {
+        walk_gunadharma(self, gunadharma);
+    }
+
+    fn visit_karyanvaya(&mut self, karyanvaya: &mut KaryanvayaDef) {
+        walk_karyanvaya(self, karyanvaya);
+    }
+
+    /// The statements of a block or of a top-level प्रारब्ध
+    fn visit_statements(&mut self, stmts: &mut Vec<Statement>) {
+        stmts.iter_mut().for_each(|stmt| self.visit_statement(stmt));
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        walk_statement(self, stmt);
+    }
+
+    fn visit_block(&mut self, block: &mut Block) {
+        self.visit_statements(&mut block.stmts);
+        if let Some(expr) = &mut block.expr {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_pattern(&mut self, pattern: &mut Pattern) {
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_type(&mut self, ty: &mut Type) {
+        walk_type(self, ty);
+    }
+
+    /// A path naming a value, or a dharma in a pattern or struct literal.
+    /// Paths in types are part of [`VisitMut::visit_type`].
+    fn visit_path(&mut self, _path: &mut Path) {}
+}
+
+pub fn walk_program<V: VisitMut + ?Sized>(visitor: &mut V, program: &mut Program) {
+    visitor.visit_items(&mut program.items);
+}
+
+pub fn walk_item<V: VisitMut + ?Sized>(visitor: &mut V, item: &mut Item) {
+    match item {
+        Item::Dharma(dharma) => visitor.visit_dharma(dharma),
+        Item::Mantra(mantra) => visitor.visit_mantra(mantra),
+        Item::Sutra(sutra) => visitor.visit_sutra(sutra),
+        Item::Yantra(yantra) => visitor.visit_items(&mut yantra.items),
+        Item::Gunadharma(gunadharma) => visitor.visit_gunadharma(gunadharma),
+        Item::Karyanvaya(karyanvaya) => visitor.visit_karyanvaya(karyanvaya),
+        Item::Praarabdha(stmts) => visitor.visit_statements(stmts),
+        Item::Aanaya(_) => {}
+    }
+}
+
+pub fn walk_dharma<V: VisitMut + ?Sized>(visitor: &mut V, dharma: &mut DharmaDef) {
+    for field in &mut dharma.fields {
+        visitor.visit_type(&mut field.ty);
+        if let Some(default) = &mut field.default_value {
+            visitor.visit_expr(default);
+        }
+    }
+    dharma.methods.iter_mut().for_each(|method| visitor.visit_mantra(method));
+}
+
+pub fn walk_mantra<V: VisitMut + ?Sized>(visitor: &mut V, mantra: &mut MantraDef) {
+    for param in &mut mantra.params {
+        visitor.visit_type(&mut param.ty);
+        if let Some(default) = &mut param.default_value {
+            visitor.visit_expr(default);
+        }
+    }
+    visitor.visit_type(&mut mantra.return_type);
+    visitor.visit_block(&mut mantra.body);
+}
+
+pub fn walk_sutra<V: VisitMut + ?Sized>(visitor: &mut V, sutra: &mut SutraDef) {
+    visitor.visit_pattern(&mut sutra.pattern);
+    if let Some(ty) = &mut sutra.type_annotation {
+        visitor.visit_type(ty);
+    }
+    visitor.visit_expr(&mut sutra.value);
+}
+
+pub fn walk_gunadharma<V: VisitMut + ?Sized>(visitor: &mut V, gunadharma: &mut GunadharmaDef) {
+    for method in &mut gunadharma.methods {
+        method.params.iter_mut().for_each(|param| visitor.visit_type(&mut param.ty));
+        visitor.visit_type(&mut method.return_type);
+    }
+}
+
+/// The paths naming the dharma and the गुणधर्म are left to overrides
+pub fn walk_karyanvaya<V: VisitMut + ?Sized>(visitor: &mut V, karyanvaya: &mut KaryanvayaDef) {
+    karyanvaya.methods.iter_mut().for_each(|method| visitor.visit_mantra(method));
+}
+
+pub fn walk_statement<V: VisitMut + ?Sized>(visitor: &mut V, stmt: &mut Statement) {
+    match stmt {
+        Statement::Item(item) => visitor.visit_item(item),
+        Statement::Sutra(sutra) => visitor.visit_sutra(sutra),
+        Statement::Expr(expr) => visitor.visit_expr(expr),
+        Statement::Block(block) => visitor.visit_block(block),
+        Statement::Yadi { condition, then_branch, else_branch } => {
+            visitor.visit_expr(condition);
+            visitor.visit_statement(then_branch);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_statement(else_branch);
+            }
+        }
+        Statement::Yaavat { condition, body, .. } => {
+            visitor.visit_expr(condition);
+            visitor.visit_statement(body);
+        }
+        Statement::Pratyeka { pattern, iterable, body, .. } => {
+            visitor.visit_pattern(pattern);
+            visitor.visit_expr(iterable);
+            visitor.visit_statement(body);
+        }
+        Statement::Pratyahara(value) | Statement::Nirgama { value, .. } => {
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        Statement::Anuvrtti(_) | Statement::Shunya => {}
+    }
+}
+
+pub fn walk_expr<V: VisitMut + ?Sized>(visitor: &mut V, expr: &mut Expr) {
+    match expr {
+        Expr::Variable(path, _) => visitor.visit_path(path),
+        Expr::FieldAccess(object, _, _) | Expr::Unary(_, object, _) | Expr::Await(object, _) => {
+            visitor.visit_expr(object)
+        }
+        Expr::MethodCall(receiver, _, type_args, args, _) => {
+            visitor.visit_expr(receiver);
+            type_args.iter_mut().for_each(|t| visitor.visit_type(t));
+            args.iter_mut().for_each(|a| visitor.visit_expr(a));
+        }
+        Expr::Call(callee, args, _) => {
+            visitor.visit_expr(callee);
+            args.iter_mut().for_each(|a| visitor.visit_expr(a));
+        }
+        Expr::Binary(left, _, right, _) | Expr::Assign(left, right, _) => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::Block(block, _) | Expr::Loop(block, _, _) | Expr::Async(block, _) | Expr::Try(block, _) => {
+            visitor.visit_block(block)
+        }
+        Expr::If(condition, then_block, else_expr, _) => {
+            visitor.visit_expr(condition);
+            visitor.visit_block(then_block);
+            if let Some(else_expr) = else_expr {
+                visitor.visit_expr(else_expr);
+            }
+        }
+        Expr::While(condition, body, _, _) => {
+            visitor.visit_expr(condition);
+            visitor.visit_block(body);
+        }
+        Expr::For(pattern, iterable, body, _, _) => {
+            visitor.visit_pattern(pattern);
+            visitor.visit_expr(iterable);
+            visitor.visit_block(body);
+        }
+        Expr::Match(scrutinee, arms, _) => {
+            visitor.visit_expr(scrutinee);
+            for arm in arms {
+                visitor.visit_pattern(&mut arm.pattern);
+                match &mut arm.guard {
+                    Some(Guard::If(condition)) => visitor.visit_expr(condition),
+                    Some(Guard::IfLet(pattern, value)) => {
+                        visitor.visit_pattern(pattern);
+                        visitor.visit_expr(value);
+                    }
+                    None => {}
+                }
+                visitor.visit_expr(&mut arm.body);
+            }
+        }
+        Expr::Return(value, _) | Expr::Break(_, value, _) => {
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        Expr::Lambda(params, body, _) => {
+            for param in params.iter_mut() {
+                visitor.visit_type(&mut param.ty);
+            }
+            visitor.visit_expr(body);
+        }
+        Expr::Array(elems, _) | Expr::Tuple(elems, _) => elems.iter_mut().for_each(|e| visitor.visit_expr(e)),
+        Expr::Struct(path, fields, _) => {
+            visitor.visit_path(path);
+            fields.iter_mut().for_each(|f| visitor.visit_expr(&mut f.value));
+        }
+        Expr::Range(start, end, _, _) => {
+            for bound in [start, end].into_iter().flatten() {
+                visitor.visit_expr(bound);
+            }
+        }
+        Expr::Literal(..) | Expr::Continue(..) | Expr::Error(_) => {}
+    }
+}
+
+pub fn walk_pattern<V: VisitMut + ?Sized>(visitor: &mut V, pattern: &mut Pattern) {
+    match pattern {
+        Pattern::Bind { subpattern: Some(sub), .. } => visitor.visit_pattern(sub),
+        Pattern::Tuple(elems, _) => elems.iter_mut().for_each(|p| visitor.visit_pattern(p)),
+        Pattern::Struct { path, fields, .. } => {
+            visitor.visit_path(path);
+            fields.iter_mut().for_each(|f| visitor.visit_pattern(&mut f.pattern));
+        }
+        Pattern::Any(_) | Pattern::Literal(_) | Pattern::Bind { .. } => {}
+    }
+}
+
+pub fn walk_type<V: VisitMut + ?Sized>(visitor: &mut V, ty: &mut Type) {
+    match ty {
+        Type::Named(_, args) | Type::Tuple(args, _) => args.iter_mut().for_each(|t| visitor.visit_type(t)),
+        Type::Function(params, ret, _) => {
+            params.iter_mut().for_each(|t| visitor.visit_type(t));
+            visitor.visit_type(ret);
+        }
+        Type::Reference(inner, _, _) | Type::Slice(inner, _) => visitor.visit_type(inner),
+        Type::Array(elem, len, _) => {
+            visitor.visit_type(elem);
+            if let Some(len) = len {
+                visitor.visit_expr(len);
+            }
+        }
+        Type::Never(_) | Type::Infer(_) | Type::Error => {}
+    }
+}
@@ -83,6 +83,16 @@ pub enum Instruction {
     /// Call function
     Call(String, u8), // function name, arg count
     
+    /// Call the function value below the arguments (mantra or closure)
+    CallValue(u8), // arg count
+    
+    /// Call a गुणधर्म method through the vtable of the object below the
+    /// arguments, which is passed as the first argument
+    CallVirtual(String, u16, u8), // gunadharma name, method slot, arg count
+    
+    /// Create a closure over the top values of the stack
+    MakeClosure(String, u8), // function name, capture count
+    
     /// Return from function
     Return,
     
@@ -149,6 +159,9 @@ pub enum Constant {
     /// Integer constant (सङ्ख्या)
     Integer(i64),
     
+    /// Fractional constant (दशांश)
+    Float(f64),
+    
     /// Boolean constant (सत्यासत्य)
     Boolean(bool),
     
@@ -173,6 +186,10 @@ pub struct BytecodeProgram {
     
     /// Entry point
     pub entry_point: u32,
+    
+    /// Vtables by गुणधर्म and then dharma: the functions implementing the
+    /// गुणधर्म's methods, in the order it declares them
+    pub vtables: std::collections::HashMap<String, std::collections::HashMap<String, Vec<String>>>,
 }
 
 /// Function information
@@ -220,6 +237,11 @@ impl fmt::Display for Instruction {
             Instruction::JumpIf(addr) => write!(f, "JUMP_IF {}", addr),
             Instruction::JumpIfNot(addr) => write!(f, "JUMP_IF_NOT {}", addr),
             Instruction::Call(name, argc) => write!(f, "CALL {} {}", name, argc),
+            Instruction::CallValue(argc) => write!(f, "CALL_VALUE {}", argc),
+            Instruction::CallVirtual(gunadharma, slot, argc) => {
+                write!(f, "CALL_VIRTUAL {}#{} {}", gunadharma, slot, argc)
+            }
+            Instruction::MakeClosure(name, count) => write!(f, "MAKE_CLOSURE {} {}", name, count),
             Instruction::Return => write!(f, "RETURN"),
             Instruction::LoadLocal(idx) => write!(f, "LOAD_LOCAL {}", idx),
             Instruction::StoreLocal(idx) => write!(f, "STORE_LOCAL {}", idx),
@@ -250,6 +272,7 @@ impl BytecodeProgram {
             constants: Vec::new(),
             functions: std::collections::HashMap::new(),
             entry_point: 0,
+            vtables: std::collections::HashMap::new(),
         }
     }
     
@@ -287,6 +310,17 @@ impl BytecodeProgram {
         self.functions.get(name)
     }
     
+    /// Add the vtable of `dharma` for `gunadharma`
+    pub fn add_vtable(&mut self, gunadharma: String, dharma: String, methods: Vec<String>) {
+        self.vtables.entry(gunadharma).or_default().insert(dharma, methods);
+    }
+    
+    /// The function implementing method `slot` of `gunadharma` for `dharma`
+    pub fn get_virtual(&self, gunadharma: &str, dharma: &str, slot: u16) -> Option<&str> {
+        let methods = self.vtables.get(gunadharma)?.get(dharma)?;
+        methods.get(slot as usize).map(String::as_str)
+    }
+    
     /// Disassemble the program for debugging
     pub fn disassemble(&self) -> String {
         let mut output = String::new();
@@ -312,6 +346,17 @@ impl BytecodeProgram {
             output.push('\n');
         }
         
+        // Vtables
+        if !self.vtables.is_empty() {
+            output.push_str("VTables:\n");
+            for (gunadharma, dharmas) in &self.vtables {
+                for (dharma, methods) in dharmas {
+                    output.push_str(&format!("  {} for {}: [{}]\n", gunadharma, dharma, methods.join(", ")));
+                }
+            }
+            output.push('\n');
+        }
+        
         // Instructions
         output.push_str("Instructions:\n");
         for (i, instruction) in self.instructions.iter().enumerate() {
@@ -180,11 +180,16 @@ impl GarbageCollector {
                     self.mark_value(item_value);
                 }
             }
-            RuntimeValue::Dharma(obj) => {
+            RuntimeValue::Dharma(_, obj) => {
                 for field_value in obj.values() {
                     self.mark_value(field_value);
                 }
             }
+            RuntimeValue::Closure(closure) => {
+                for captured in &closure.captures {
+                    self.mark_value(captured);
+                }
+            }
             _ => {
                 // For other types, no additional marking needed
             }
@@ -251,6 +256,7 @@ impl GarbageCollector {
     fn estimate_size(&self, value: &RuntimeValue) -> usize {
         match value {
             RuntimeValue::Sankhya(_) => 8,
+            RuntimeValue::Dashamsha(_) => 8,
             RuntimeValue::Satyasatya(_) => 1,
             RuntimeValue::Shabda(s) => s.len() + 24, // String overhead
             RuntimeValue::Suchi(list) => {
@@ -267,7 +273,7 @@ impl GarbageCollector {
                 }
                 size
             }
-            RuntimeValue::Dharma(obj) => {
+            RuntimeValue::Dharma(_, obj) => {
                 let mut size = 48; // HashMap overhead
                 for (key, value) in obj {
                     size += key.len() + self.estimate_size(value);
@@ -276,6 +282,13 @@ impl GarbageCollector {
             }
             RuntimeValue::Shunya => 0,
             RuntimeValue::Mantra(_) => 8, // Function pointer
+            RuntimeValue::Closure(closure) => {
+                let mut size = 16 + closure.function.len(); // Arc + name
+                for captured in &closure.captures {
+                    size += self.estimate_size(captured);
+                }
+                size
+            }
         }
     }
     
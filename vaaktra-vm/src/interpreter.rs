@@ -10,8 +10,11 @@ use parking_lot::RwLock;
 use crate::{VmError, VmResult};
 use crate::bytecode::{BytecodeProgram, Instruction, Constant, FunctionInfo};
 use crate::stack::{VmStack, CallFrame};
-use vaaktra_jit::runtime::RuntimeValue;
-use vaaktra_parser::ast::{Program, Item, MantraDef, Expr, Statement, BinaryOp};
+use vaaktra_jit::runtime::{Closure, NumericPair, RuntimeValue};
+use vaaktra_parser::ast::{
+    Program, Item, MantraDef, KaryanvayaDef, Expr, Statement, BinaryOp, Block, Pattern, Path, RcStr, FieldDef, Type,
+};
+use vaaktra_semantics::captures::lambda_captures;
 
 /// Bytecode interpreter
 pub struct BytecodeInterpreter {
@@ -51,12 +54,36 @@ impl BytecodeInterpreter {
         let mut bytecode = BytecodeProgram::new();
         let mut compiler = BytecodeCompiler::new();
         
+        // Dharmas and गुणधर्म methods first, as any function may use them
+        for item in &program.items {
+            match item {
+                Item::Dharma(dharma) => {
+                    compiler.dharmas.insert(dharma.name.to_string(), dharma.fields.clone());
+                }
+                Item::Gunadharma(gunadharma) => {
+                    let methods = gunadharma.methods.iter().map(|m| m.name.clone()).collect();
+                    compiler.gunadharmas.insert(gunadharma.name.to_string(), methods);
+                }
+                _ => {}
+            }
+        }
+        
         // Compile all items
         for item in &program.items {
             match item {
                 Item::Mantra(mantra) => {
-                    compiler.compile_function(mantra, &mut bytecode)?;
+                    compiler.compile_function(mantra.name.to_string(), mantra, &mut bytecode)?;
+                }
+                Item::Dharma(dharma) => {
+                    for method in &dharma.methods {
+                        let name = format!("{}::{}", dharma.name, method.name);
+                        compiler.compile_function(name, method, &mut bytecode)?;
+                    }
                 }
+                Item::Karyanvaya(karyanvaya) => {
+                    compiler.compile_karyanvaya(karyanvaya, &mut bytecode)?;
+                }
+                Item::Gunadharma(_) => {}
                 _ => {
                     // Handle other item types
                     log::warn!("Unhandled item type in bytecode compilation");
@@ -64,8 +91,8 @@ impl BytecodeInterpreter {
             }
         }
         
-        // Set entry point (main function if exists)
-        if let Some(main_func) = bytecode.get_function("main") {
+        // Set entry point (मुख्य, the main function, if it exists)
+        if let Some(main_func) = bytecode.get_function("मुख्य") {
             bytecode.entry_point = main_func.start_address;
         }
         
@@ -222,22 +249,70 @@ impl BytecodeInterpreter {
             
             Instruction::Call(func_name, arg_count) => {
                 self.stats.function_calls += 1;
+                self.enter_function(func_name, *arg_count as usize, stack)
+            }
+            
+            Instruction::CallValue(arg_count) => {
+                self.stats.function_calls += 1;
+                
+                let mut args = Vec::with_capacity(*arg_count as usize);
+                for _ in 0..*arg_count {
+                    args.push(stack.pop()?);
+                }
+                args.reverse();
                 
-                // Get function info
-                let program = self.program.as_ref().unwrap();
-                let func_info = program.get_function(func_name)
-                    .ok_or_else(|| VmError::ExecutionError(format!("Function {} not found", func_name)))?;
+                match stack.pop()? {
+                    RuntimeValue::Mantra(func) => {
+                        let result = func(&args)
+                            .map_err(|e| VmError::RuntimeError(e.to_string()))?;
+                        stack.push(result)?;
+                        Ok(ExecutionResult::Continue)
+                    }
+                    RuntimeValue::Closure(closure) => {
+                        // Captured values occupy the first parameter slots
+                        let call_args = closure.call_args(&args);
+                        let arg_count = call_args.len();
+                        for value in call_args {
+                            stack.push(value)?;
+                        }
+                        self.enter_function(&closure.function, arg_count, stack)
+                    }
+                    other => Err(VmError::ExecutionError(format!("{} is not callable", other.to_string()))),
+                }
+            }
+            
+            Instruction::CallVirtual(gunadharma, slot, arg_count) => {
+                self.stats.function_calls += 1;
                 
-                // Create call frame
-                let frame = CallFrame {
-                    return_address: self.ip + 1,
-                    base_pointer: stack.size() - *arg_count as usize,
-                    function_name: func_name.clone(),
-                    local_count: func_info.local_count,
+                // The receiver is below the arguments and its dharma
+                // selects the vtable
+                let dharma = match stack.peek_at(*arg_count as usize)? {
+                    RuntimeValue::Dharma(dharma, _) => dharma.clone(),
+                    other => {
+                        return Err(VmError::ExecutionError(format!(
+                            "{} has no methods of {}", other.to_string(), gunadharma
+                        )));
+                    }
                 };
+                let program = self.program.as_ref()
+                    .ok_or_else(|| VmError::ExecutionError("No program loaded".to_string()))?;
+                let method = program.get_virtual(gunadharma, &dharma, *slot)
+                    .ok_or_else(|| VmError::ExecutionError(format!(
+                        "{} does not implement method {} of {}", dharma, slot, gunadharma
+                    )))?
+                    .to_string();
+                self.enter_function(&method, *arg_count as usize + 1, stack)
+            }
+            
+            Instruction::MakeClosure(func_name, capture_count) => {
+                let mut captures = Vec::with_capacity(*capture_count as usize);
+                for _ in 0..*capture_count {
+                    captures.push(stack.pop()?);
+                }
+                captures.reverse();
                 
-                stack.push_frame(frame)?;
-                Ok(ExecutionResult::Jump(func_info.start_address))
+                stack.push(RuntimeValue::Closure(Arc::new(Closure::new(func_name.clone(), captures))))?;
+                Ok(ExecutionResult::Continue)
             }
             
             Instruction::Return => {
@@ -279,6 +354,85 @@ impl BytecodeInterpreter {
                 Ok(ExecutionResult::Continue)
             }
             
+            // Array operations
+            Instruction::NewArray(size) => {
+                self.stats.memory_allocations += 1;
+                let mut elems = Vec::with_capacity(*size as usize);
+                for _ in 0..*size {
+                    elems.push(stack.pop()?);
+                }
+                elems.reverse();
+                stack.push(RuntimeValue::Suchi(elems))?;
+                Ok(ExecutionResult::Continue)
+            }
+            
+            Instruction::ArrayGet => {
+                let index = stack.pop()?;
+                let array = stack.pop()?;
+                let elem = match (&array, &index) {
+                    (RuntimeValue::Suchi(elems), RuntimeValue::Sankhya(i)) => {
+                        usize::try_from(*i).ok().and_then(|i| elems.get(i)).cloned().ok_or_else(|| {
+                            VmError::ExecutionError(format!("Index {} out of bounds for length {}", i, elems.len()))
+                        })?
+                    }
+                    _ => {
+                        return Err(VmError::ExecutionError(format!(
+                            "Cannot index {} with {}", array.to_string(), index.to_string()
+                        )));
+                    }
+                };
+                stack.push(elem)?;
+                Ok(ExecutionResult::Continue)
+            }
+            
+            Instruction::ArrayLen => {
+                match stack.pop()? {
+                    RuntimeValue::Suchi(elems) => stack.push(RuntimeValue::Sankhya(elems.len() as i64))?,
+                    other => return Err(VmError::ExecutionError(format!("{} is not a list", other.to_string()))),
+                }
+                Ok(ExecutionResult::Continue)
+            }
+            
+            // Object operations
+            Instruction::NewObject(dharma) => {
+                self.stats.memory_allocations += 1;
+                stack.push(RuntimeValue::Dharma(dharma.clone(), HashMap::new()))?;
+                Ok(ExecutionResult::Continue)
+            }
+            
+            Instruction::GetField(name) => {
+                let value = match stack.pop()? {
+                    RuntimeValue::Dharma(dharma, mut fields) => fields.remove(name).ok_or_else(|| {
+                        VmError::ExecutionError(format!("{} has no field {}", dharma, name))
+                    })?,
+                    other => {
+                        return Err(VmError::ExecutionError(format!(
+                            "{} has no field {}", other.to_string(), name
+                        )));
+                    }
+                };
+                stack.push(value)?;
+                Ok(ExecutionResult::Continue)
+            }
+            
+            // Sets a field of the object below the value and leaves the
+            // object on the stack
+            Instruction::SetField(name) => {
+                let value = stack.pop()?;
+                match stack.pop()? {
+                    RuntimeValue::Dharma(dharma, mut fields) => {
+                        fields.insert(name.clone(), value);
+                        stack.push(RuntimeValue::Dharma(dharma, fields))?;
+                    }
+                    other => {
+                        return Err(VmError::ExecutionError(format!(
+                            "{} has no field {}", other.to_string(), name
+                        )));
+                    }
+                }
+                Ok(ExecutionResult::Continue)
+            }
+            
             // Special operations
             Instruction::Print => {
                 let value = stack.pop()?;
@@ -305,10 +459,30 @@ impl BytecodeInterpreter {
         }
     }
     
+    /// Push a call frame for `func_name`, whose `arg_count` arguments are
+    /// already on the stack, and jump to its first instruction
+    fn enter_function(&mut self, func_name: &str, arg_count: usize, stack: &mut VmStack) -> VmResult<ExecutionResult> {
+        let program = self.program.as_ref()
+            .ok_or_else(|| VmError::ExecutionError("No program loaded".to_string()))?;
+        let func_info = program.get_function(func_name)
+            .ok_or_else(|| VmError::ExecutionError(format!("Function {} not found", func_name)))?;
+        
+        let frame = CallFrame {
+            return_address: self.ip + 1,
+            base_pointer: stack.size() - arg_count,
+            function_name: func_name.to_string(),
+            local_count: func_info.local_count,
+        };
+        
+        stack.push_frame(frame)?;
+        Ok(ExecutionResult::Jump(func_info.start_address))
+    }
+    
     /// Convert constant to runtime value
     fn constant_to_runtime_value(&self, constant: &Constant) -> RuntimeValue {
         match constant {
             Constant::Integer(i) => RuntimeValue::Sankhya(*i),
+            Constant::Float(x) => RuntimeValue::Dashamsha(*x),
             Constant::Boolean(b) => RuntimeValue::Satyasatya(*b),
             Constant::String(s) => RuntimeValue::Shabda(s.clone()),
             Constant::Null => RuntimeValue::Shunya,
@@ -317,12 +491,16 @@ impl BytecodeInterpreter {
     
     /// Add two runtime values
     fn add_values(&self, a: RuntimeValue, b: RuntimeValue) -> VmResult<RuntimeValue> {
+        match a.numeric_pair(&b) {
+            Some(NumericPair::Sankhya(x, y)) => return Ok(RuntimeValue::Sankhya(x + y)),
+            Some(NumericPair::Dashamsha(x, y)) => return Ok(RuntimeValue::Dashamsha(x + y)),
+            None => {}
+        }
         match (a, b) {
-            (RuntimeValue::Sankhya(x), RuntimeValue::Sankhya(y)) => {
-                Ok(RuntimeValue::Sankhya(x + y))
-            }
-            (RuntimeValue::Shabda(x), RuntimeValue::Shabda(y)) => {
-                Ok(RuntimeValue::Shabda(format!("{}{}", x, y)))
+            // A string on the left concatenates any value, as interpolated
+            // strings desugar to such chains
+            (RuntimeValue::Shabda(x), y) => {
+                Ok(RuntimeValue::Shabda(format!("{}{}", x, y.to_string())))
             }
             _ => Err(VmError::ExecutionError("Invalid operands for addition".to_string()))
         }
@@ -330,42 +508,43 @@ impl BytecodeInterpreter {
     
     /// Subtract two runtime values
     fn sub_values(&self, a: RuntimeValue, b: RuntimeValue) -> VmResult<RuntimeValue> {
-        match (a, b) {
-            (RuntimeValue::Sankhya(x), RuntimeValue::Sankhya(y)) => {
-                Ok(RuntimeValue::Sankhya(x - y))
-            }
-            _ => Err(VmError::ExecutionError("Invalid operands for subtraction".to_string()))
+        match a.numeric_pair(&b) {
+            Some(NumericPair::Sankhya(x, y)) => Ok(RuntimeValue::Sankhya(x - y)),
+            Some(NumericPair::Dashamsha(x, y)) => Ok(RuntimeValue::Dashamsha(x - y)),
+            None => Err(VmError::ExecutionError("Invalid operands for subtraction".to_string()))
         }
     }
     
     /// Multiply two runtime values
     fn mul_values(&self, a: RuntimeValue, b: RuntimeValue) -> VmResult<RuntimeValue> {
-        match (a, b) {
-            (RuntimeValue::Sankhya(x), RuntimeValue::Sankhya(y)) => {
-                Ok(RuntimeValue::Sankhya(x * y))
-            }
-            _ => Err(VmError::ExecutionError("Invalid operands for multiplication".to_string()))
+        match a.numeric_pair(&b) {
+            Some(NumericPair::Sankhya(x, y)) => Ok(RuntimeValue::Sankhya(x * y)),
+            Some(NumericPair::Dashamsha(x, y)) => Ok(RuntimeValue::Dashamsha(x * y)),
+            None => Err(VmError::ExecutionError("Invalid operands for multiplication".to_string()))
         }
     }
     
-    /// Divide two runtime values
+    /// Divide two runtime values. Integer division by zero is an error;
+    /// दशांश division follows IEEE 754 and yields an infinity or NaN.
     fn div_values(&self, a: RuntimeValue, b: RuntimeValue) -> VmResult<RuntimeValue> {
-        match (a, b) {
-            (RuntimeValue::Sankhya(x), RuntimeValue::Sankhya(y)) => {
-                if y == 0 {
-                    Err(VmError::ExecutionError("Division by zero".to_string()))
-                } else {
-                    Ok(RuntimeValue::Sankhya(x / y))
-                }
+        match a.numeric_pair(&b) {
+            Some(NumericPair::Sankhya(_, 0)) => {
+                Err(VmError::ExecutionError("Division by zero".to_string()))
             }
-            _ => Err(VmError::ExecutionError("Invalid operands for division".to_string()))
+            Some(NumericPair::Sankhya(x, y)) => Ok(RuntimeValue::Sankhya(x / y)),
+            Some(NumericPair::Dashamsha(x, y)) => Ok(RuntimeValue::Dashamsha(x / y)),
+            None => Err(VmError::ExecutionError("Invalid operands for division".to_string()))
         }
     }
     
     /// Check if two values are equal
     fn values_equal(&self, a: &RuntimeValue, b: &RuntimeValue) -> bool {
+        match a.numeric_pair(b) {
+            Some(NumericPair::Sankhya(x, y)) => return x == y,
+            Some(NumericPair::Dashamsha(x, y)) => return x == y,
+            None => {}
+        }
         match (a, b) {
-            (RuntimeValue::Sankhya(x), RuntimeValue::Sankhya(y)) => x == y,
             (RuntimeValue::Satyasatya(x), RuntimeValue::Satyasatya(y)) => x == y,
             (RuntimeValue::Shabda(x), RuntimeValue::Shabda(y)) => x == y,
             (RuntimeValue::Shunya, RuntimeValue::Shunya) => true,
@@ -375,8 +554,12 @@ impl BytecodeInterpreter {
     
     /// Check if first value is less than second
     fn less_than(&self, a: &RuntimeValue, b: &RuntimeValue) -> VmResult<bool> {
+        match a.numeric_pair(b) {
+            Some(NumericPair::Sankhya(x, y)) => return Ok(x < y),
+            Some(NumericPair::Dashamsha(x, y)) => return Ok(x < y),
+            None => {}
+        }
         match (a, b) {
-            (RuntimeValue::Sankhya(x), RuntimeValue::Sankhya(y)) => Ok(x < y),
             (RuntimeValue::Shabda(x), RuntimeValue::Shabda(y)) => Ok(x < y),
             _ => Err(VmError::ExecutionError("Invalid operands for comparison".to_string()))
         }
@@ -399,46 +582,189 @@ enum ExecutionResult {
 
 /// Bytecode compiler
 struct BytecodeCompiler {
-    // Compiler state would go here
+    /// Parameters and locals of the function being compiled, by slot
+    locals: Vec<Local>,
+    
+    /// Lambdas lifted out of the functions compiled so far
+    pending_lambdas: Vec<LiftedLambda>,
+    
+    /// Number of lambdas lifted, used to name them
+    lambda_count: usize,
+    
+    /// Fields of each dharma, in declaration order. Calling a dharma
+    /// makes an object of it.
+    dharmas: HashMap<String, Vec<FieldDef>>,
+    
+    /// Methods of each गुणधर्म, in vtable order
+    gunadharmas: HashMap<String, Vec<RcStr>>,
+}
+
+/// A parameter or local variable of the function being compiled
+#[derive(Clone)]
+struct Local {
+    name: RcStr,
+    /// The dharma of its value, when that is known, so that its methods
+    /// can be called directly
+    dharma: Option<String>,
+}
+
+/// A lambda compiled as a separate function whose leading parameters are
+/// its captured variables
+struct LiftedLambda {
+    name: String,
+    params: Vec<Local>,
+    body: Expr,
 }
 
 impl BytecodeCompiler {
     fn new() -> Self {
-        BytecodeCompiler {}
+        BytecodeCompiler {
+            locals: Vec::new(),
+            pending_lambdas: Vec::new(),
+            lambda_count: 0,
+            dharmas: HashMap::new(),
+            gunadharmas: HashMap::new(),
+        }
     }
     
-    fn compile_function(&mut self, mantra: &MantraDef, bytecode: &mut BytecodeProgram) -> VmResult<()> {
-        let start_addr = bytecode.instructions.len() as u32;
+    /// Compile `mantra` as the function `name`. A method's आत्मन् is its
+    /// first parameter.
+    fn compile_function(&mut self, name: String, mantra: &MantraDef, bytecode: &mut BytecodeProgram) -> VmResult<()> {
+        let params = mantra.params.iter().map(|p| Local {
+            name: p.name.clone(),
+            dharma: self.dharma_of_type(&p.ty),
+        }).collect();
+        self.compile_body(name, params, |compiler, bytecode| {
+            compiler.compile_block(&mantra.body, bytecode)
+        }, bytecode)?;
         
-        // Create function info
-        let func_info = FunctionInfo {
-            name: mantra.name.clone(),
-            start_address: start_addr,
-            param_count: mantra.params.len() as u8,
-            local_count: 0, // Simplified - would need to analyze locals
-            return_type: "unknown".to_string(), // Simplified
-        };
+        // Lambdas are emitted after the function that created them; lifting
+        // one may uncover further nested lambdas
+        while let Some(lambda) = self.pending_lambdas.pop() {
+            self.compile_body(lambda.name, lambda.params, |compiler, bytecode| {
+                compiler.compile_expression(&lambda.body, bytecode)
+            }, bytecode)?;
+        }
         
-        // Compile function body (simplified)
-        if let Some(body) = &mantra.body {
-            for statement in body {
-                self.compile_statement(statement, bytecode)?;
+        Ok(())
+    }
+    
+    /// Compile the methods of a कार्यान्वय as functions `dharma::method` and
+    /// add the dharma's vtable for the गुणधर्म
+    fn compile_karyanvaya(&mut self, karyanvaya: &KaryanvayaDef, bytecode: &mut BytecodeProgram) -> VmResult<()> {
+        let dharma = path_name(&karyanvaya.dharma);
+        let gunadharma = path_name(&karyanvaya.gunadharma);
+        for method in &karyanvaya.methods {
+            self.compile_function(format!("{}::{}", dharma, method.name), method, bytecode)?;
+        }
+        
+        let slots = self.gunadharmas.get(&gunadharma)
+            .ok_or_else(|| VmError::InvalidBytecode(format!("Unknown गुणधर्म {}", gunadharma)))?;
+        let mut vtable = Vec::with_capacity(slots.len());
+        for name in slots {
+            if !karyanvaya.methods.iter().any(|method| method.name == *name) {
+                return Err(VmError::InvalidBytecode(format!("{} does not define {} of {}", dharma, name, gunadharma)));
             }
+            vtable.push(format!("{}::{}", dharma, name));
         }
+        bytecode.add_vtable(gunadharma, dharma, vtable);
+        Ok(())
+    }
+    
+    /// Compile one function body with `params` in its first local slots
+    fn compile_body(
+        &mut self,
+        name: String,
+        params: Vec<Local>,
+        body: impl FnOnce(&mut Self, &mut BytecodeProgram) -> VmResult<()>,
+        bytecode: &mut BytecodeProgram,
+    ) -> VmResult<()> {
+        let start_addr = bytecode.instructions.len() as u32;
+        let param_count = params.len() as u8;
+        self.locals = params;
         
-        // Add return instruction
+        body(self, bytecode)?;
         bytecode.add_instruction(Instruction::Return);
         
-        // Add function info
-        bytecode.add_function(func_info);
+        bytecode.add_function(FunctionInfo {
+            name,
+            start_address: start_addr,
+            param_count,
+            local_count: self.locals.len() as u16,
+            return_type: "unknown".to_string(), // Simplified
+        });
         
         Ok(())
     }
     
+    /// Compile a block, leaving its value on the stack
+    fn compile_block(&mut self, block: &Block, bytecode: &mut BytecodeProgram) -> VmResult<()> {
+        for statement in &block.stmts {
+            self.compile_statement(statement, bytecode)?;
+        }
+        match &block.expr {
+            Some(expr) => self.compile_expression(expr, bytecode)?,
+            None => {
+                bytecode.add_instruction(Instruction::PushConst(Constant::Null));
+            }
+        }
+        Ok(())
+    }
+    
     fn compile_statement(&mut self, statement: &Statement, bytecode: &mut BytecodeProgram) -> VmResult<()> {
         match statement {
-            Statement::Expression(expr) => {
+            Statement::Expr(expr) => {
                 self.compile_expression(expr, bytecode)?;
+                bytecode.add_instruction(Instruction::Pop);
+            }
+            Statement::Sutra(sutra) => {
+                let Pattern::Bind { name, .. } = &sutra.pattern else {
+                    return Err(VmError::InvalidBytecode("Unsupported pattern in सूत्र".to_string()));
+                };
+                let dharma = match &sutra.type_annotation {
+                    Some(ty) => self.dharma_of_type(ty),
+                    None => self.dharma_of(&sutra.value),
+                };
+                self.compile_expression(&sutra.value, bytecode)?;
+                let slot = self.add_local(name.clone(), dharma);
+                bytecode.add_instruction(Instruction::StoreLocal(slot));
+            }
+            Statement::Block(block) => {
+                self.compile_block(block, bytecode)?;
+                bytecode.add_instruction(Instruction::Pop);
+            }
+            Statement::Pratyeka { pattern, iterable, body, .. } => {
+                let Pattern::Bind { name, .. } = pattern else {
+                    return Err(VmError::InvalidBytecode("Unsupported pattern in प्रत्येक".to_string()));
+                };
+                // The list and the index of the next element live in
+                // hidden locals
+                self.compile_expression(iterable, bytecode)?;
+                let list = self.add_local(RcStr::from("<सूची>"), None);
+                bytecode.add_instruction(Instruction::StoreLocal(list));
+                bytecode.add_instruction(Instruction::PushConst(Constant::Integer(0)));
+                let index = self.add_local(RcStr::from("<क्रमाङ्क>"), None);
+                bytecode.add_instruction(Instruction::StoreLocal(index));
+                
+                let start = bytecode.add_instruction(Instruction::LoadLocal(index));
+                bytecode.add_instruction(Instruction::LoadLocal(list));
+                bytecode.add_instruction(Instruction::ArrayLen);
+                bytecode.add_instruction(Instruction::Lt);
+                let exit = bytecode.add_instruction(Instruction::JumpIfNot(0));
+                
+                bytecode.add_instruction(Instruction::LoadLocal(list));
+                bytecode.add_instruction(Instruction::LoadLocal(index));
+                bytecode.add_instruction(Instruction::ArrayGet);
+                let elem = self.add_local(name.clone(), None);
+                bytecode.add_instruction(Instruction::StoreLocal(elem));
+                self.compile_statement(body, bytecode)?;
+                
+                bytecode.add_instruction(Instruction::LoadLocal(index));
+                bytecode.add_instruction(Instruction::PushConst(Constant::Integer(1)));
+                bytecode.add_instruction(Instruction::Add);
+                bytecode.add_instruction(Instruction::StoreLocal(index));
+                bytecode.add_instruction(Instruction::Jump(start));
+                bytecode.instructions[exit as usize] = Instruction::JumpIfNot(bytecode.instructions.len() as u32);
             }
             _ => {
                 // Handle other statement types
@@ -448,6 +774,92 @@ impl BytecodeCompiler {
         Ok(())
     }
     
+    /// Give `name` a new local slot
+    fn add_local(&mut self, name: RcStr, dharma: Option<String>) -> u16 {
+        self.locals.push(Local { name, dharma });
+        (self.locals.len() - 1) as u16
+    }
+    
+    /// Slot of the innermost local called `name`
+    fn local_slot(&self, name: &str) -> Option<u16> {
+        self.locals.iter().rposition(|local| local.name.as_str() == name).map(|slot| slot as u16)
+    }
+    
+    /// The dharma named by `ty`, if it is one
+    fn dharma_of_type(&self, ty: &Type) -> Option<String> {
+        match ty {
+            Type::Named(path, args) if args.is_empty() => {
+                let name = path_name(path);
+                self.dharmas.contains_key(name.as_str()).then_some(name)
+            }
+            _ => None,
+        }
+    }
+    
+    /// The dharma of the value of `expr`, if it can be told without types:
+    /// a local of a known dharma or a newly made object
+    fn dharma_of(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Variable(path, _) => {
+                let slot = self.local_slot(&path_name(path))?;
+                self.locals[slot as usize].dharma.clone()
+            }
+            Expr::Call(callee, _, _) => self.global_name(callee)
+                .filter(|name| self.dharmas.contains_key(name.as_str()))
+                .map(|name| name.to_string()),
+            Expr::Struct(path, _, _) => {
+                let name = path_name(path);
+                self.dharmas.contains_key(name.as_str()).then_some(name)
+            }
+            _ => None,
+        }
+    }
+    
+    /// Make an object of `dharma` from the field values in `given`; fields
+    /// left out take their default values
+    fn compile_object(
+        &mut self,
+        dharma: &str,
+        given: Vec<(RcStr, &Expr)>,
+        bytecode: &mut BytecodeProgram,
+    ) -> VmResult<()> {
+        let fields = self.dharmas.get(dharma).cloned().unwrap_or_default();
+        bytecode.add_instruction(Instruction::NewObject(dharma.to_string()));
+        for (name, value) in &given {
+            self.compile_expression(value, bytecode)?;
+            bytecode.add_instruction(Instruction::SetField(name.to_string()));
+        }
+        for field in &fields {
+            if given.iter().any(|(name, _)| *name == field.name) {
+                continue;
+            }
+            if let Some(default) = &field.default_value {
+                self.compile_expression(default, bytecode)?;
+                bytecode.add_instruction(Instruction::SetField(field.name.to_string()));
+            }
+        }
+        Ok(())
+    }
+    
+    /// The name of a single-segment variable that is not a local
+    fn global_name<'e>(&self, expr: &'e Expr) -> Option<&'e RcStr> {
+        match expr {
+            Expr::Variable(path, _) => match path.segments.as_slice() {
+                [segment] if self.local_slot(&segment.ident).is_none() => Some(&segment.ident),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+    
+    /// The गुणधर्म and vtable slot of a method call the checker named
+    /// `गुणधर्म::मन्त्र`, as it does calls on गुणधर्म values
+    fn virtual_slot(&self, name: &str) -> Option<(String, u16)> {
+        let (gunadharma, method) = name.rsplit_once("::")?;
+        let slot = self.gunadharmas.get(gunadharma)?.iter().position(|m| m.as_str() == method)?;
+        Some((gunadharma.to_string(), slot as u16))
+    }
+    
     fn compile_expression(&mut self, expr: &Expr, bytecode: &mut BytecodeProgram) -> VmResult<()> {
         match expr {
             Expr::Literal(literal, _span) => {
@@ -455,6 +867,22 @@ impl BytecodeCompiler {
                     vaaktra_parser::ast::Literal::Int(value) => {
                         bytecode.add_instruction(Instruction::PushConst(Constant::Integer(*value)));
                     }
+                    vaaktra_parser::ast::Literal::TypedInt(value, width) => {
+                        // The VM keeps every integer as a सङ्ख्या (i64)
+                        let value = i64::try_from(*value).map_err(|_| {
+                            VmError::InvalidBytecode(format!("{}{} does not fit in a VM integer", value, width))
+                        })?;
+                        bytecode.add_instruction(Instruction::PushConst(Constant::Integer(value)));
+                    }
+                    vaaktra_parser::ast::Literal::NegativeTypedInt(value, width) => {
+                        let value = u64::try_from(*value).ok().and_then(|value| 0i64.checked_sub_unsigned(value)).ok_or_else(|| {
+                            VmError::InvalidBytecode(format!("ऋण {}{} does not fit in a VM integer", value, width))
+                        })?;
+                        bytecode.add_instruction(Instruction::PushConst(Constant::Integer(value)));
+                    }
+                    vaaktra_parser::ast::Literal::Float(value) => {
+                        bytecode.add_instruction(Instruction::PushConst(Constant::Float(*value)));
+                    }
                     vaaktra_parser::ast::Literal::Bool(value) => {
                         bytecode.add_instruction(Instruction::PushConst(Constant::Boolean(*value)));
                     }
@@ -464,21 +892,147 @@ impl BytecodeCompiler {
                 }
             }
             
+            Expr::Variable(path, _span) => {
+                let name = path_name(path);
+                match self.local_slot(&name) {
+                    Some(slot) => bytecode.add_instruction(Instruction::LoadLocal(slot)),
+                    None => bytecode.add_instruction(Instruction::LoadGlobal(name)),
+                };
+            }
+            
             Expr::Binary(left, op, right, _span) => {
                 self.compile_expression(left, bytecode)?;
                 self.compile_expression(right, bytecode)?;
                 
                 match op {
                     BinaryOp::Add => bytecode.add_instruction(Instruction::Add),
-                    BinaryOp::Sub => bytecode.add_instruction(Instruction::Sub),
-                    BinaryOp::Mul => bytecode.add_instruction(Instruction::Mul),
-                    BinaryOp::Div => bytecode.add_instruction(Instruction::Div),
-                    BinaryOp::Eq => bytecode.add_instruction(Instruction::Eq),
-                    BinaryOp::Lt => bytecode.add_instruction(Instruction::Lt),
+                    BinaryOp::Subtract => bytecode.add_instruction(Instruction::Sub),
+                    BinaryOp::Multiply => bytecode.add_instruction(Instruction::Mul),
+                    BinaryOp::Divide => bytecode.add_instruction(Instruction::Div),
+                    BinaryOp::Equal => bytecode.add_instruction(Instruction::Eq),
+                    BinaryOp::Less => bytecode.add_instruction(Instruction::Lt),
                     _ => return Err(VmError::InvalidBytecode("Unsupported binary operator".to_string())),
                 };
             }
             
+            Expr::Call(callee, args, _span) => {
+                // Calling a dharma makes an object of it, its arguments
+                // giving the fields in order; calls to a named mantra go
+                // through the function table; anything else is a function
+                // value
+                let dharma = self.global_name(callee).filter(|name| self.dharmas.contains_key(name.as_str()));
+                if let Some(dharma) = dharma {
+                    let dharma = dharma.to_string();
+                    let fields = &self.dharmas[dharma.as_str()];
+                    if args.len() > fields.len() {
+                        return Err(VmError::InvalidBytecode(format!(
+                            "{} has {} fields but is given {} values", dharma, fields.len(), args.len()
+                        )));
+                    }
+                    let given = fields.iter().map(|field| field.name.clone()).zip(args).collect();
+                    self.compile_object(&dharma, given, bytecode)?;
+                } else if let Some(name) = self.global_name(callee) {
+                    let name = name.to_string();
+                    for arg in args {
+                        self.compile_expression(arg, bytecode)?;
+                    }
+                    bytecode.add_instruction(Instruction::Call(name, args.len() as u8));
+                } else {
+                    self.compile_expression(callee, bytecode)?;
+                    for arg in args {
+                        self.compile_expression(arg, bytecode)?;
+                    }
+                    bytecode.add_instruction(Instruction::CallValue(args.len() as u8));
+                }
+            }
+            
+            Expr::MethodCall(receiver, name, _, args, _span) => {
+                // Calls on a गुणधर्म value go through the vtable of the
+                // receiver; calls on a known dharma call its method directly
+                // with the receiver as आत्मन्
+                let call = match self.virtual_slot(name) {
+                    Some((gunadharma, slot)) => Instruction::CallVirtual(gunadharma, slot, args.len() as u8),
+                    None => match self.dharma_of(receiver) {
+                        Some(dharma) => Instruction::Call(format!("{}::{}", dharma, name), args.len() as u8 + 1),
+                        None => {
+                            return Err(VmError::InvalidBytecode(format!("Unsupported method call {}", name)));
+                        }
+                    },
+                };
+                self.compile_expression(receiver, bytecode)?;
+                for arg in args {
+                    self.compile_expression(arg, bytecode)?;
+                }
+                bytecode.add_instruction(call);
+            }
+            
+            Expr::FieldAccess(object, name, _span) => {
+                self.compile_expression(object, bytecode)?;
+                bytecode.add_instruction(Instruction::GetField(name.to_string()));
+            }
+            
+            Expr::Struct(path, fields, _span) => {
+                let dharma = path_name(path);
+                if !self.dharmas.contains_key(dharma.as_str()) {
+                    return Err(VmError::InvalidBytecode(format!("Unknown dharma {}", dharma)));
+                }
+                let given = fields.iter().map(|field| (field.name.clone(), &field.value)).collect();
+                self.compile_object(&dharma, given, bytecode)?;
+            }
+            
+            Expr::Array(elems, _span) => {
+                for elem in elems {
+                    self.compile_expression(elem, bytecode)?;
+                }
+                bytecode.add_instruction(Instruction::NewArray(elems.len() as u32));
+            }
+            
+            Expr::Assign(target, value, _span) => {
+                // Only variables; objects are values here, so a field
+                // assignment would change a copy
+                let Expr::Variable(path, _) = &**target else {
+                    return Err(VmError::InvalidBytecode("Unsupported assignment target".to_string()));
+                };
+                let name = path_name(path);
+                self.compile_expression(value, bytecode)?;
+                match self.local_slot(&name) {
+                    Some(slot) => bytecode.add_instruction(Instruction::StoreLocal(slot)),
+                    None => bytecode.add_instruction(Instruction::StoreGlobal(name)),
+                };
+                bytecode.add_instruction(Instruction::PushConst(Constant::Null));
+            }
+            
+            Expr::Block(block, _span) => {
+                // Locals declared in the block keep their slots afterwards
+                self.compile_block(block, bytecode)?;
+            }
+            
+            Expr::Lambda(params, body, _span) => {
+                let captures: Vec<Local> = lambda_captures(params, body)
+                    .into_iter()
+                    .filter_map(|name| self.local_slot(&name))
+                    .map(|slot| {
+                        bytecode.add_instruction(Instruction::LoadLocal(slot));
+                        self.locals[slot as usize].clone()
+                    })
+                    .collect();
+                
+                let name = format!("<lambda#{}>", self.lambda_count);
+                self.lambda_count += 1;
+                bytecode.add_instruction(Instruction::MakeClosure(name.clone(), captures.len() as u8));
+                
+                let mut lifted_params = captures;
+                lifted_params.extend(params.iter().map(|p| Local {
+                    name: p.name.clone(),
+                    dharma: self.dharma_of_type(&p.ty),
+                }));
+                self.pending_lambdas.push(LiftedLambda {
+                    name,
+                    params: lifted_params,
+                    body: (**body).clone(),
+                });
+            }
+            
             _ => {
                 return Err(VmError::InvalidBytecode("Unsupported expression type".to_string()));
             }
@@ -487,3 +1041,46 @@ impl BytecodeCompiler {
         Ok(())
     }
 }
+
+/// A path as one name, with its segments joined by `::`
+fn path_name(path: &Path) -> String {
+    path.segments.iter().map(|s| s.ident.as_str()).collect::<Vec<_>>().join("::")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VaaktraVm;
+    use vaaktra_lexer::Lexer;
+    use vaaktra_parser::Parser;
+    use vaaktra_semantics::VaaktraSemanticAnalyzer;
+
+    /// Check and run `input` from its मुख्य
+    fn run(input: &str) -> RuntimeValue {
+        let mut program = Parser::new(Lexer::new(input)).parse_program().unwrap();
+        VaaktraSemanticAnalyzer::new().analyze_program(&mut program).unwrap();
+        VaaktraVm::new().unwrap().execute_program(&program).unwrap()
+    }
+
+    #[test]
+    fn test_gunadharma_methods_dispatch_across_a_list() {
+        // Each element of the list calls the क्षेत्रफल of its own dharma
+        // through the vtable; the last call is on a known dharma and so
+        // calls its method directly
+        let input = "गुणधर्म आकार { मन्त्र क्षेत्रफल(आत्मन्) -> दशांश; }\n\
+                     धर्म वर्ग { सूत्र भुजा: दशांश; }\n\
+                     धर्म आयत { सूत्र लम्बाई: दशांश; सूत्र चौड़ाई: दशांश; }\n\
+                     कार्यान्वय वर्ग: आकार { मन्त्र क्षेत्रफल(आत्मन्) -> दशांश { आत्मन्.भुजा गुण आत्मन्.भुजा } }\n\
+                     कार्यान्वय आयत: आकार { मन्त्र क्षेत्रफल(आत्मन्) -> दशांश { आत्मन्.लम्बाई गुण आत्मन्.चौड़ाई } }\n\
+                     मन्त्र कुल(आकृतयः: सूची<आकार>) -> दशांश {\n\
+                         सूत्र चल योग = ०.०;\n\
+                         प्रत्येक आ मध्ये आकृतयः { योग = योग धन आ.क्षेत्रफल(); }\n\
+                         योग\n\
+                     }\n\
+                     मन्त्र मुख्य() -> दशांश { कुल([वर्ग(२.०), आयत(३.०, ४.०)]) धन आयत(१.०, ०.५).क्षेत्रफल() }";
+        match run(input) {
+            RuntimeValue::Dashamsha(total) => assert_eq!(total, 16.5),
+            other => panic!("Expected a दशांश, got {:?}", other),
+        }
+    }
+}
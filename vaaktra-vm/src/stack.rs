@@ -148,8 +148,8 @@ impl VmStack {
         let frame = self.frames.pop()
             .ok_or_else(|| VmError::ExecutionError("No call frame to pop".to_string()))?;
         
-        // Remove local variables from stack
-        for _ in 0..frame.local_count {
+        // Remove the arguments and local variables from the stack
+        while self.sp > frame.base_pointer {
             self.pop()?;
         }
         